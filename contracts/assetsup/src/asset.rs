@@ -8,6 +8,10 @@ pub enum DataKey {
     Asset(BytesN<32>),
     OwnerRegistry(Address),
     AssetCounter,
+    /// Token asset ID (u64) a registry asset is tokenized as
+    TokenizedAs(BytesN<32>),
+    /// Set once a registry asset's tokens have been detokenized
+    Detokenized(BytesN<32>),
 }
 
 #[contracttype]
@@ -34,6 +38,8 @@ pub struct AssetInfo {
     pub category: String,
     pub owner: Address,
     pub status: AssetStatus,
+    /// Whether a lease currently covers the asset
+    pub leased: bool,
 }
 
 // Note: Contract methods implemented in lib.rs
@@ -0,0 +1,219 @@
+use soroban_sdk::{contracttype, Bytes, BytesN, Env, Vec};
+
+use crate::error::Error;
+
+/// Maximum age (in seconds) of a signed attestation when it is submitted.
+pub const MAX_ATTESTATION_AGE: u64 = 7 * 24 * 60 * 60;
+
+// ─── Types ────────────────────────────────────────────────────────────────────
+
+/// Role of a registered off-chain attestor
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AttestorRole {
+    /// May attest valuations and valuation reports
+    Appraiser,
+    /// May attest legal documentation
+    Notary,
+}
+
+/// Field of a tokenized asset covered by an attestation.
+/// The discriminant is the field code used in the signed payload.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum AttestationField {
+    Valuation = 0,
+    LegalDocs = 1,
+    ValuationReport = 2,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attestor {
+    /// ed25519 public key used to sign attestations
+    pub public_key: BytesN<32>,
+    pub role: AttestorRole,
+    pub registered_at: u64,
+}
+
+/// A verified attestation as recorded on-chain
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Attestation {
+    pub asset_id: u64,
+    pub field: AttestationField,
+    pub value_hash: BytesN<32>,
+    /// Timestamp chosen by the attestor and covered by the signature
+    pub timestamp: u64,
+    pub attestor: BytesN<32>,
+    pub signature: BytesN<64>,
+    /// Ledger timestamp at which the attestation was accepted
+    pub recorded_at: u64,
+}
+
+// ─── Storage Keys ─────────────────────────────────────────────────────────────
+
+#[contracttype]
+pub enum DataKey {
+    Attestor(BytesN<32>),
+    AttestationHistory(u64),
+    LatestAttestation(u64, AttestationField),
+}
+
+// ─── Internal helpers ─────────────────────────────────────────────────────────
+
+fn role_allows(role: &AttestorRole, field: AttestationField) -> bool {
+    match role {
+        AttestorRole::Appraiser => matches!(
+            field,
+            AttestationField::Valuation | AttestationField::ValuationReport
+        ),
+        AttestorRole::Notary => field == AttestationField::LegalDocs,
+    }
+}
+
+/// Build the message an attestor signs:
+/// `asset_id (u64 BE) || field (u32 BE) || value_hash (32 bytes) || timestamp (u64 BE)`
+pub fn attestation_payload(
+    env: &Env,
+    asset_id: u64,
+    field: AttestationField,
+    value_hash: &BytesN<32>,
+    timestamp: u64,
+) -> Bytes {
+    let mut payload = Bytes::from_array(env, &asset_id.to_be_bytes());
+    payload.extend_from_array(&(field as u32).to_be_bytes());
+    payload.append(&Bytes::from(value_hash.clone()));
+    payload.extend_from_array(&timestamp.to_be_bytes());
+    payload
+}
+
+/// Hash committed to by a valuation attestation: sha256 of the i128 value in big-endian
+pub fn valuation_hash(env: &Env, valuation: i128) -> BytesN<32> {
+    let bytes = Bytes::from_array(env, &valuation.to_be_bytes());
+    env.crypto().sha256(&bytes).into()
+}
+
+// ─── Public functions (called from lib.rs) ────────────────────────────────────
+
+pub fn register_attestor(
+    env: &Env,
+    public_key: BytesN<32>,
+    role: AttestorRole,
+) -> Result<(), Error> {
+    let attestor = Attestor {
+        public_key: public_key.clone(),
+        role: role.clone(),
+        registered_at: env.ledger().timestamp(),
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::Attestor(public_key.clone()), &attestor);
+
+    env.events()
+        .publish(("attest", "attestor_registered"), (public_key, role));
+
+    Ok(())
+}
+
+pub fn remove_attestor(env: &Env, public_key: BytesN<32>) -> Result<(), Error> {
+    let key = DataKey::Attestor(public_key.clone());
+    if !env.storage().persistent().has(&key) {
+        return Err(Error::Unauthorized);
+    }
+    env.storage().persistent().remove(&key);
+
+    env.events()
+        .publish(("attest", "attestor_removed"), (public_key,));
+
+    Ok(())
+}
+
+pub fn get_attestor(env: &Env, public_key: BytesN<32>) -> Option<Attestor> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Attestor(public_key))
+}
+
+/// Verify a signed attestation and append it to the asset's history.
+/// The signature check traps if `signature` does not match the payload.
+pub fn verify_and_record(
+    env: &Env,
+    asset_id: u64,
+    field: AttestationField,
+    value_hash: BytesN<32>,
+    timestamp: u64,
+    attestor: BytesN<32>,
+    signature: BytesN<64>,
+) -> Result<Attestation, Error> {
+    let store = env.storage().persistent();
+
+    // Attestor must be registered with a role covering this field
+    let registered: Attestor = store
+        .get(&DataKey::Attestor(attestor.clone()))
+        .ok_or(Error::Unauthorized)?;
+    if !role_allows(&registered.role, field) {
+        return Err(Error::Unauthorized);
+    }
+
+    // Reject future-dated and stale attestations
+    let now = env.ledger().timestamp();
+    if timestamp > now || now - timestamp > MAX_ATTESTATION_AGE {
+        return Err(Error::InvalidAttestation);
+    }
+
+    // Reject replays and out-of-order attestations for the same field
+    let latest_key = DataKey::LatestAttestation(asset_id, field);
+    if let Some(latest) = store.get::<_, Attestation>(&latest_key) {
+        if timestamp <= latest.timestamp {
+            return Err(Error::InvalidAttestation);
+        }
+    }
+
+    let payload = attestation_payload(env, asset_id, field, &value_hash, timestamp);
+    env.crypto().ed25519_verify(&attestor, &payload, &signature);
+
+    let attestation = Attestation {
+        asset_id,
+        field,
+        value_hash: value_hash.clone(),
+        timestamp,
+        attestor: attestor.clone(),
+        signature,
+        recorded_at: now,
+    };
+
+    let history_key = DataKey::AttestationHistory(asset_id);
+    let mut history: Vec<Attestation> = store.get(&history_key).unwrap_or_else(|| Vec::new(env));
+    history.push_back(attestation.clone());
+    store.set(&history_key, &history);
+    store.set(&latest_key, &attestation);
+
+    // Emit event: (asset_id, field, value_hash, attestor)
+    env.events().publish(
+        ("attest", "attestation_recorded"),
+        (asset_id, field, value_hash, attestor),
+    );
+
+    Ok(attestation)
+}
+
+/// Get the full attestation history for an asset, oldest first
+pub fn get_attestations(env: &Env, asset_id: u64) -> Vec<Attestation> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AttestationHistory(asset_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Get the most recent attestation for a field of an asset
+pub fn get_latest_attestation(
+    env: &Env,
+    asset_id: u64,
+    field: AttestationField,
+) -> Option<Attestation> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::LatestAttestation(asset_id, field))
+}
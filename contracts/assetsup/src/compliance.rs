@@ -0,0 +1,302 @@
+use soroban_sdk::{contractclient, contracttype, Address, Env, String, Vec};
+
+use crate::error::Error;
+use crate::identity;
+use crate::math;
+use crate::tokenization;
+use crate::types::{TokenDataKey, TokenizedAsset};
+
+const SECONDS_PER_DAY: u64 = 86400;
+
+// ─── Types ────────────────────────────────────────────────────────────────────
+
+/// A transfer rule attached to an asset. Modules are evaluated in the order
+/// they were configured; the first failing module rejects the operation.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ComplianceModule {
+    /// Maximum number of investors holding a non-zero balance
+    MaxHolders(u32),
+    /// Maximum balance any investor may hold
+    MaxBalance(i128),
+    /// Seconds an investor must hold before transferring out
+    MinHoldingPeriod(u64),
+    /// Maximum number of investors from an ISO country code
+    CountryHolderCap(String, u32),
+    /// Maximum amount an investor may transfer out per UTC day
+    DailyVolumeLimit(i128),
+    /// Reject transfers to or from sanctioned addresses
+    SanctionsBlock,
+    /// External module contract implementing `ComplianceModuleInterface`
+    External(Address),
+}
+
+/// Interface for external compliance module contracts.
+/// `from` is `None` for mints and `to` is `None` for burns.
+#[allow(dead_code)]
+#[contractclient(name = "ComplianceModuleClient")]
+pub trait ComplianceModuleInterface {
+    fn can_transfer(
+        env: Env,
+        asset_id: u64,
+        from: Option<Address>,
+        to: Option<Address>,
+        amount: i128,
+    ) -> bool;
+}
+
+// ─── Storage Keys ─────────────────────────────────────────────────────────────
+
+#[contracttype]
+pub enum DataKey {
+    ComplianceModules(u64),
+    Sanctioned(Address),
+    DailyVolume(u64, Address, u64),
+}
+
+// ─── Internal helpers ─────────────────────────────────────────────────────────
+
+fn balance_of(env: &Env, asset_id: u64, holder: &Address) -> Result<i128, Error> {
+    tokenization::get_token_balance(env, asset_id, holder.clone())
+}
+
+/// Investors subject to per-holder rules: everyone except the tokenizer and
+/// the contract's own escrow
+fn is_investor(env: &Env, tokenized_asset: &TokenizedAsset, address: &Address) -> bool {
+    *address != tokenized_asset.tokenizer && *address != env.current_contract_address()
+}
+
+/// Count investors with a non-zero balance, optionally only from `country`
+fn count_investors(
+    env: &Env,
+    tokenized_asset: &TokenizedAsset,
+    country: Option<&String>,
+) -> Result<u32, Error> {
+    let holders: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&TokenDataKey::TokenHoldersList(tokenized_asset.asset_id))
+        .unwrap_or_else(|| Vec::new(env));
+
+    let mut count = 0;
+    for holder in holders.iter() {
+        if !is_investor(env, tokenized_asset, &holder)
+            || balance_of(env, tokenized_asset.asset_id, &holder)? <= 0
+        {
+            continue;
+        }
+        if let Some(country) = country {
+            match identity::get_valid_identity(env, holder) {
+                Some(claims) if claims.jurisdiction == *country => {}
+                _ => continue,
+            }
+        }
+        count += 1;
+    }
+    Ok(count)
+}
+
+fn check_module(
+    env: &Env,
+    tokenized_asset: &TokenizedAsset,
+    module: &ComplianceModule,
+    from: &Option<Address>,
+    to: &Option<Address>,
+    amount: i128,
+) -> Result<(), Error> {
+    let asset_id = tokenized_asset.asset_id;
+    let investor_from = from
+        .clone()
+        .filter(|a| is_investor(env, tokenized_asset, a));
+    let investor_to = to.clone().filter(|a| is_investor(env, tokenized_asset, a));
+    // Receiver becomes a new investor with this operation
+    let new_investor = match &investor_to {
+        Some(receiver) if balance_of(env, asset_id, receiver)? == 0 => Some(receiver.clone()),
+        _ => None,
+    };
+
+    let passed = match module {
+        ComplianceModule::MaxHolders(max) => match &new_investor {
+            Some(_) => {
+                let mut count = count_investors(env, tokenized_asset, None)? + 1;
+                if let Some(sender) = &investor_from {
+                    if balance_of(env, asset_id, sender)? == amount {
+                        count -= 1;
+                    }
+                }
+                count <= *max
+            }
+            None => true,
+        },
+        ComplianceModule::MaxBalance(max) => match &investor_to {
+            Some(receiver) => math::add(balance_of(env, asset_id, receiver)?, amount)? <= *max,
+            None => true,
+        },
+        ComplianceModule::MinHoldingPeriod(period) => match &investor_from {
+            Some(sender) => tokenization::load_ownership(env, asset_id, sender)?
+                // An unlock time past u64::MAX never arrives
+                .map(|record| {
+                    env.ledger().timestamp() >= record.acquisition_timestamp.saturating_add(*period)
+                })
+                .unwrap_or(true),
+            None => true,
+        },
+        ComplianceModule::CountryHolderCap(country, cap) => {
+            match new_investor.and_then(|a| identity::get_valid_identity(env, a)) {
+                Some(claims) if claims.jurisdiction == *country => {
+                    count_investors(env, tokenized_asset, Some(country))? < *cap
+                }
+                _ => true,
+            }
+        }
+        ComplianceModule::DailyVolumeLimit(limit) => match &investor_from {
+            Some(sender) => {
+                let now = env.ledger().timestamp();
+                let day = now / SECONDS_PER_DAY;
+                let key = DataKey::DailyVolume(asset_id, sender.clone(), day);
+                let volume: i128 = env.storage().temporary().get(&key).unwrap_or(0);
+                let new_volume = volume.checked_add(amount).ok_or(Error::MathOverflow)?;
+                env.storage().temporary().set(&key, &new_volume);
+                // Keep the day's volume live until the day is over. Ledgers
+                // close no more than once a second, so a ledger per remaining
+                // second outlasts it.
+                let remaining = ((day + 1) * SECONDS_PER_DAY - now) as u32;
+                env.storage()
+                    .temporary()
+                    .extend_ttl(&key, remaining, remaining);
+                new_volume <= *limit
+            }
+            None => true,
+        },
+        ComplianceModule::SanctionsBlock => {
+            !from.clone().is_some_and(|a| is_sanctioned(env, a))
+                && !to.clone().is_some_and(|a| is_sanctioned(env, a))
+        }
+        ComplianceModule::External(module) => {
+            ComplianceModuleClient::new(env, module).can_transfer(&asset_id, from, to, &amount)
+        }
+    };
+
+    if passed {
+        Ok(())
+    } else {
+        Err(Error::TransferRestrictionFailed)
+    }
+}
+
+// ─── Public functions (called from lib.rs) ────────────────────────────────────
+
+/// Replace an asset's ordered list of compliance modules. Only the tokenizer can configure them.
+pub fn set_compliance_modules(
+    env: &Env,
+    asset_id: u64,
+    modules: Vec<ComplianceModule>,
+    caller: Address,
+) -> Result<(), Error> {
+    let tokenized_asset: TokenizedAsset = env
+        .storage()
+        .persistent()
+        .get(&TokenDataKey::TokenizedAsset(asset_id))
+        .ok_or(Error::AssetNotTokenized)?;
+    if tokenized_asset.tokenizer != caller {
+        return Err(Error::Unauthorized);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::ComplianceModules(asset_id), &modules);
+
+    // Emit event: (asset_id, module_count)
+    env.events()
+        .publish(("compliance", "modules_set"), (asset_id, modules.len()));
+
+    Ok(())
+}
+
+pub fn get_compliance_modules(env: &Env, asset_id: u64) -> Vec<ComplianceModule> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ComplianceModules(asset_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Mark or clear an address as sanctioned. Only reached through
+/// `enforcement::set_global_denylisted`, which checks admin auth and logs it.
+pub fn set_sanctioned(env: &Env, address: Address, sanctioned: bool) -> Result<(), Error> {
+    let key = DataKey::Sanctioned(address.clone());
+    if sanctioned {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+
+    // Emit event: (address, sanctioned)
+    env.events()
+        .publish(("compliance", "sanctions_updated"), (address, sanctioned));
+
+    Ok(())
+}
+
+pub fn is_sanctioned(env: &Env, address: Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Sanctioned(address))
+}
+
+/// Re-denominate token-amount limits after a split or consolidation
+pub fn rescale_modules(
+    env: &Env,
+    asset_id: u64,
+    ratio_from: u32,
+    ratio_to: u32,
+) -> Result<(), Error> {
+    let modules = get_compliance_modules(env, asset_id);
+    if modules.is_empty() {
+        return Ok(());
+    }
+
+    let mut rescaled = Vec::new(env);
+    for module in modules.iter() {
+        rescaled.push_back(match module {
+            ComplianceModule::MaxBalance(max) => ComplianceModule::MaxBalance(
+                tokenization::scale_by_ratio(max, ratio_from, ratio_to)?,
+            ),
+            ComplianceModule::DailyVolumeLimit(limit) => ComplianceModule::DailyVolumeLimit(
+                tokenization::scale_by_ratio(limit, ratio_from, ratio_to)?,
+            ),
+            other => other,
+        });
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::ComplianceModules(asset_id), &rescaled);
+    Ok(())
+}
+
+/// Run every configured module for a supply movement. `from` is `None` for
+/// mints and `to` is `None` for burns. Daily volume is recorded as it is checked,
+/// so callers must abort the operation on error.
+pub fn check_transfer(
+    env: &Env,
+    asset_id: u64,
+    from: Option<Address>,
+    to: Option<Address>,
+    amount: i128,
+) -> Result<(), Error> {
+    let modules = get_compliance_modules(env, asset_id);
+    if modules.is_empty() {
+        return Ok(());
+    }
+
+    let tokenized_asset: TokenizedAsset = env
+        .storage()
+        .persistent()
+        .get(&TokenDataKey::TokenizedAsset(asset_id))
+        .ok_or(Error::AssetNotTokenized)?;
+
+    for module in modules.iter() {
+        check_module(env, &tokenized_asset, &module, &from, &to, amount)?;
+    }
+
+    Ok(())
+}
@@ -1,10 +1,13 @@
+use crate::asset;
+use crate::enforcement;
 use crate::error::Error;
 use crate::types::{
     ActiveProposal, DetokenizationProposal, ExecutedProposal, RejectedProposal, TokenDataKey,
     TokenizedAsset,
 };
+use crate::vesting;
 use crate::voting;
-use soroban_sdk::{Address, Env};
+use soroban_sdk::{Address, BytesN, Env};
 
 /// Propose detokenization (requires voting)
 pub fn propose_detokenization(env: &Env, asset_id: u64, proposer: Address) -> Result<u64, Error> {
@@ -90,6 +93,24 @@ pub fn execute_detokenization(env: &Env, asset_id: u64, proposal_id: u64) -> Res
             store.remove(&lock_key);
         }
 
+        // Remove partial locks
+        let locks_key = TokenDataKey::TokenLocks(asset_id, holder.clone());
+        if store.has(&locks_key) {
+            store.remove(&locks_key);
+        }
+
+        // Remove freezes
+        let freeze_key = enforcement::DataKey::Frozen(asset_id, holder.clone());
+        if store.has(&freeze_key) {
+            store.remove(&freeze_key);
+        }
+
+        // Remove vesting schedules
+        let vesting_key = vesting::DataKey::VestingSchedules(asset_id, holder.clone());
+        if store.has(&vesting_key) {
+            store.remove(&vesting_key);
+        }
+
         // Remove unclaimed dividends
         let dividend_key = TokenDataKey::UnclaimedDividend(asset_id, holder);
         if store.has(&dividend_key) {
@@ -97,6 +118,12 @@ pub fn execute_detokenization(env: &Env, asset_id: u64, proposal_id: u64) -> Res
         }
     }
 
+    // The contract's own escrow is not listed as a holder
+    store.remove(&TokenDataKey::TokenHolder(
+        asset_id,
+        env.current_contract_address(),
+    ));
+
     // Remove token holders list
     if store.has(&holders_list_key) {
         store.remove(&holders_list_key);
@@ -120,6 +147,26 @@ pub fn execute_detokenization(env: &Env, asset_id: u64, proposal_id: u64) -> Res
         store.remove(&metadata_key);
     }
 
+    // Remove minting policy
+    let policy_key = TokenDataKey::MintPolicy(asset_id);
+    if store.has(&policy_key) {
+        store.remove(&policy_key);
+    }
+
+    // Remove corporate action history; new holder records start at epoch zero
+    let actions_key = TokenDataKey::CorporateActions(asset_id);
+    if store.has(&actions_key) {
+        store.remove(&actions_key);
+    }
+
+    // The linked registry asset stays registered but can no longer be leased
+    let link_key = TokenDataKey::RegistryAsset(asset_id);
+    if let Some(registry_id) = store.get::<_, BytesN<32>>(&link_key) {
+        store.remove(&link_key);
+        store.remove(&asset::DataKey::TokenizedAs(registry_id.clone()));
+        store.set(&asset::DataKey::Detokenized(registry_id), &true);
+    }
+
     // Remove the tokenized asset record (this eliminates all tokens from circulation)
     if store.has(&key) {
         store.remove(&key);
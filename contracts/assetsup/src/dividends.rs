@@ -1,6 +1,106 @@
 use crate::error::Error;
-use crate::types::{OwnershipRecord, TokenDataKey, TokenizedAsset};
-use soroban_sdk::{Address, Env, Vec};
+use crate::identity;
+use crate::math::{self, Rounding};
+use crate::tokenization;
+use crate::types::{TokenDataKey, TokenizedAsset};
+use crate::valuation;
+use soroban_sdk::{contracttype, token, Address, BytesN, Env, String, Vec};
+
+/// Default time holders have to claim a distribution round: 365 days
+pub const DEFAULT_CLAIM_PERIOD: u64 = 365 * 86400;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RoundStatus {
+    /// Holders can claim their entitlement
+    Open,
+    /// Expired; unclaimed funds returned to the issuer
+    Reclaimed,
+}
+
+/// A funded distribution of a SEP-41 payment token to holders as of `record_date`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DistributionRound {
+    pub round_id: u32,
+    pub asset_id: u64,
+    pub payment_token: Address,
+    pub total_amount: i128,
+    pub claimed_amount: i128,
+    /// Balances are snapshotted at this timestamp
+    pub record_date: u64,
+    /// Claims close and the issuer may reclaim the remainder from this timestamp
+    pub expires_at: u64,
+    /// Hash of an off-chain memo describing the distribution's source
+    pub memo_hash: BytesN<32>,
+    pub status: RoundStatus,
+}
+
+/// A holder's share of a distribution round
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundEntitlement {
+    pub amount: i128,
+    pub claimed: bool,
+    pub claimed_at: u64,
+}
+
+/// A completed claim. `net_amount` is what the holder received after the
+/// platform fee and jurisdiction withholding were taken from `gross_amount`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DividendClaim {
+    pub round_id: u32,
+    pub payment_token: Address,
+    pub gross_amount: i128,
+    pub fee_amount: i128,
+    pub withheld_amount: i128,
+    pub net_amount: i128,
+    /// Asset tokens bought with the net amount for holders who reinvest
+    pub reinvested_tokens: i128,
+    pub claimed_at: u64,
+}
+
+/// Where reinvested dividends buy their tokens
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ReinvestmentSource {
+    /// Tokens are transferred from the tokenizer's balance
+    Treasury,
+    /// New tokens are minted, subject to the asset's minting policy
+    Mint,
+}
+
+/// Platform fee charged on every round claim. The fee is paid to `treasury`
+/// at claim time; withheld tax is pooled until `tax_authority` or `treasury`
+/// withdraws it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DividendFeeConfig {
+    pub fee_bps: u32,
+    pub treasury: Address,
+    pub tax_authority: Address,
+}
+
+#[contracttype]
+pub enum DataKey {
+    DistributionRound(u64, u32),
+    RoundCounter(u64),
+    RoundEntitlement(u64, u32, Address),
+    /// Vec<u32> of rounds a holder is entitled to, oldest first
+    HolderRounds(u64, Address),
+    ClaimPeriod(u64),
+    RoundClaim(u64, u32, Address),
+    DividendFees,
+    /// Withholding rate in bps for an ISO country code
+    WithholdingRate(String),
+    /// Withheld tax pooled per payment token
+    WithheldBalance(Address),
+    Reinvest(u64, Address),
+    ReinvestSource(u64),
+    /// Payment left over after buying whole base units, per payment token
+    ReinvestCarry(u64, Address, Address),
+}
 
 /// Distribute dividends proportionally to all token holders
 pub fn distribute_dividends(env: &Env, asset_id: u64, total_amount: i128) -> Result<(), Error> {
@@ -18,6 +118,15 @@ pub fn distribute_dividends(env: &Env, asset_id: u64, total_amount: i128) -> Res
         return Err(Error::InvalidDividendAmount);
     }
 
+    // Tokens held in contract escrow earn nothing
+    let eligible_supply = math::sub(
+        tokenized_asset.total_supply,
+        tokenization::get_token_balance(env, asset_id, env.current_contract_address())?,
+    )?;
+    if eligible_supply <= 0 {
+        return Err(Error::InvalidTokenSupply);
+    }
+
     // Get all token holders
     let holders_key = TokenDataKey::TokenHoldersList(asset_id);
     let holders: Vec<Address> = store.get(&holders_key).ok_or(Error::AssetNotTokenized)?;
@@ -25,13 +134,19 @@ pub fn distribute_dividends(env: &Env, asset_id: u64, total_amount: i128) -> Res
     // Distribute proportionally to each holder
     for holder in holders.iter() {
         let holder_key = TokenDataKey::TokenHolder(asset_id, holder.clone());
-        let mut ownership: OwnershipRecord = store.get(&holder_key).ok_or(Error::HolderNotFound)?;
+        let mut ownership =
+            tokenization::load_ownership(env, asset_id, &holder)?.ok_or(Error::HolderNotFound)?;
 
-        // Calculate proportional dividend: (balance / total_supply) * total_amount
-        let proportion = (ownership.balance * total_amount) / tokenized_asset.total_supply;
+        // Calculate proportional dividend: (balance / eligible_supply) * total_amount
+        let proportion = math::mul_div(
+            ownership.balance,
+            total_amount,
+            eligible_supply,
+            Rounding::Down,
+        )?;
 
         // Add to unclaimed dividends
-        ownership.unclaimed_dividends += proportion;
+        ownership.unclaimed_dividends = math::add(ownership.unclaimed_dividends, proportion)?;
 
         store.set(&holder_key, &ownership);
     }
@@ -55,7 +170,8 @@ pub fn claim_dividends(env: &Env, asset_id: u64, holder: Address) -> Result<i128
 
     // Get holder's ownership record
     let holder_key = TokenDataKey::TokenHolder(asset_id, holder.clone());
-    let mut ownership: OwnershipRecord = store.get(&holder_key).ok_or(Error::HolderNotFound)?;
+    let mut ownership =
+        tokenization::load_ownership(env, asset_id, &holder)?.ok_or(Error::HolderNotFound)?;
 
     // Get unclaimed amount
     let unclaimed = ownership.unclaimed_dividends;
@@ -67,6 +183,8 @@ pub fn claim_dividends(env: &Env, asset_id: u64, holder: Address) -> Result<i128
     // Clear unclaimed dividends
     ownership.unclaimed_dividends = 0;
     store.set(&holder_key, &ownership);
+    // A holder who has sold out leaves the holders list once paid
+    tokenization::sync_holder(env, asset_id, &holder)?;
 
     // Emit event: (asset_id, holder, amount)
     env.events()
@@ -84,8 +202,7 @@ pub fn get_unclaimed_dividends(env: &Env, asset_id: u64, holder: Address) -> Res
     let _: TokenizedAsset = store.get(&key).ok_or(Error::AssetNotTokenized)?;
 
     // Get holder's ownership record
-    let holder_key = TokenDataKey::TokenHolder(asset_id, holder);
-    match store.get::<_, OwnershipRecord>(&holder_key) {
+    match tokenization::load_ownership(env, asset_id, &holder)? {
         Some(ownership) => Ok(ownership.unclaimed_dividends),
         None => Ok(0),
     }
@@ -116,3 +233,705 @@ pub fn disable_revenue_sharing(env: &Env, asset_id: u64) -> Result<(), Error> {
 
     Ok(())
 }
+
+fn load_round(env: &Env, asset_id: u64, round_id: u32) -> Result<DistributionRound, Error> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DistributionRound(asset_id, round_id))
+        .ok_or(Error::NoDividendsToClaim)
+}
+
+fn save_round(env: &Env, round: &DistributionRound) {
+    env.storage().persistent().set(
+        &DataKey::DistributionRound(round.asset_id, round.round_id),
+        round,
+    );
+}
+
+fn holder_rounds(env: &Env, asset_id: u64, holder: &Address) -> Vec<u32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::HolderRounds(asset_id, holder.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Set how long holders have to claim future rounds (tokenizer only)
+pub fn set_claim_period(
+    env: &Env,
+    asset_id: u64,
+    period: u64,
+    caller: Address,
+) -> Result<(), Error> {
+    let tokenized_asset = tokenization::get_tokenized_asset(env, asset_id)?;
+    if tokenized_asset.tokenizer != caller {
+        return Err(Error::Unauthorized);
+    }
+    if period == 0 {
+        return Err(Error::InvalidTimestamps);
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::ClaimPeriod(asset_id), &period);
+    Ok(())
+}
+
+pub fn get_claim_period(env: &Env, asset_id: u64) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ClaimPeriod(asset_id))
+        .unwrap_or(DEFAULT_CLAIM_PERIOD)
+}
+
+/// Fund a distribution round from the tokenizer's wallet. Entitlements are
+/// fixed from current balances; tokens held in contract escrow are excluded.
+pub fn create_distribution_round(
+    env: &Env,
+    asset_id: u64,
+    payment_token: Address,
+    total_amount: i128,
+    memo_hash: BytesN<32>,
+    caller: Address,
+) -> Result<u32, Error> {
+    if total_amount <= 0 {
+        return Err(Error::InvalidDividendAmount);
+    }
+    let tokenized_asset = tokenization::get_tokenized_asset(env, asset_id)?;
+    if tokenized_asset.tokenizer != caller {
+        return Err(Error::Unauthorized);
+    }
+    if !tokenized_asset.revenue_sharing_enabled {
+        return Err(Error::InvalidDividendAmount);
+    }
+
+    let escrow = env.current_contract_address();
+    let eligible_supply = math::sub(
+        tokenized_asset.total_supply,
+        tokenization::get_token_balance(env, asset_id, escrow.clone())?,
+    )?;
+    if eligible_supply <= 0 {
+        return Err(Error::InvalidTokenSupply);
+    }
+
+    token::Client::new(env, &payment_token).transfer(&caller, &escrow, &total_amount);
+
+    let store = env.storage().persistent();
+    let round_id: u32 = store.get(&DataKey::RoundCounter(asset_id)).unwrap_or(0u32) + 1;
+    store.set(&DataKey::RoundCounter(asset_id), &round_id);
+
+    let mut reinvestors = Vec::new(env);
+    for holder in tokenization::get_token_holders(env, asset_id)?.iter() {
+        if holder == escrow {
+            continue;
+        }
+        let balance = tokenization::get_token_balance(env, asset_id, holder.clone())?;
+        let amount = math::mul_div(balance, total_amount, eligible_supply, Rounding::Down)?;
+        if amount <= 0 {
+            continue;
+        }
+        store.set(
+            &DataKey::RoundEntitlement(asset_id, round_id, holder.clone()),
+            &RoundEntitlement {
+                amount,
+                claimed: false,
+                claimed_at: 0,
+            },
+        );
+        let mut rounds = holder_rounds(env, asset_id, &holder);
+        rounds.push_back(round_id);
+        store.set(&DataKey::HolderRounds(asset_id, holder.clone()), &rounds);
+        if holder != caller && is_reinvesting(env, asset_id, &holder) {
+            reinvestors.push_back(holder);
+        }
+    }
+
+    let now = env.ledger().timestamp();
+    let mut round = DistributionRound {
+        round_id,
+        asset_id,
+        payment_token: payment_token.clone(),
+        total_amount,
+        claimed_amount: 0,
+        record_date: now,
+        expires_at: now.saturating_add(get_claim_period(env, asset_id)),
+        memo_hash: memo_hash.clone(),
+        status: RoundStatus::Open,
+    };
+    reinvest_entitlements(env, &tokenized_asset, &mut round, reinvestors)?;
+    save_round(env, &round);
+
+    // Emit event: (asset_id, round_id, payment_token, total_amount, memo_hash)
+    env.events().publish(
+        ("dividend", "round_created"),
+        (asset_id, round_id, payment_token, total_amount, memo_hash),
+    );
+
+    Ok(round_id)
+}
+
+/// Split a gross payout into the platform fee, jurisdiction withholding and
+/// what the holder receives. Returns `(fee, withheld, net)`.
+fn fee_split(env: &Env, holder: &Address, gross: i128) -> Result<(i128, i128, i128), Error> {
+    let fee = match get_dividend_fee_config(env) {
+        Some(config) => math::apply_bps(gross, config.fee_bps as i128, Rounding::Down)?,
+        None => 0,
+    };
+    let after_fee = math::sub(gross, fee)?;
+    let withheld = math::apply_bps(
+        after_fee,
+        withholding_rate_for(env, holder) as i128,
+        Rounding::Down,
+    )?;
+    Ok((fee, withheld, math::sub(after_fee, withheld)?))
+}
+
+/// Take the platform fee and jurisdiction withholding from a gross payout.
+/// The fee is paid to the treasury and withholding is pooled in escrow.
+/// Returns `(fee, withheld, net)`.
+fn take_fees(
+    env: &Env,
+    payment_token: &Address,
+    holder: &Address,
+    gross: i128,
+) -> Result<(i128, i128, i128), Error> {
+    let (fee, withheld, net) = fee_split(env, holder, gross)?;
+    if fee > 0 {
+        if let Some(config) = get_dividend_fee_config(env) {
+            token::Client::new(env, payment_token).transfer(
+                &env.current_contract_address(),
+                &config.treasury,
+                &fee,
+            );
+        }
+    }
+    if withheld > 0 {
+        let pool_key = DataKey::WithheldBalance(payment_token.clone());
+        let pooled: i128 = env.storage().persistent().get(&pool_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&pool_key, &math::add(pooled, withheld)?);
+    }
+    Ok((fee, withheld, net))
+}
+
+/// Whether the tokenizer can deliver `tokens` to a reinvesting holder from
+/// the configured source, checked without minting or moving anything
+fn check_reinvestment(
+    env: &Env,
+    asset_id: u64,
+    tokenizer: &Address,
+    holder: &Address,
+    tokens: i128,
+    source: &ReinvestmentSource,
+) -> Result<(), Error> {
+    let mut balance = tokenization::get_token_balance(env, asset_id, tokenizer.clone())?;
+    if *source == ReinvestmentSource::Mint {
+        tokenization::check_mint(env, asset_id, tokens, tokenizer)?;
+        balance = math::add(balance, tokens)?;
+    }
+    tokenization::check_transfer_rules(env, asset_id, tokenizer, holder, tokens, balance)
+}
+
+/// Convert opted-in holders' entitlements into asset tokens at the current
+/// price per token. Payment for the tokens goes to the tokenizer; whatever is
+/// too small to buy a base unit carries forward to the holder's next round.
+/// A holder whose tokens cannot be delivered (compliance, enforcement, the
+/// tokenizer's balance or the minting policy) keeps a cash entitlement
+/// instead, so one holder never blocks the round.
+fn reinvest_entitlements(
+    env: &Env,
+    tokenized_asset: &TokenizedAsset,
+    round: &mut DistributionRound,
+    reinvestors: Vec<Address>,
+) -> Result<(), Error> {
+    let asset_id = tokenized_asset.asset_id;
+    let price = valuation::get_price_per_token(env, asset_id)?;
+    // Without a valuation there is no price, so everyone is paid in cash
+    if reinvestors.is_empty() || price <= 0 {
+        return Ok(());
+    }
+    let unit = math::pow10(tokenized_asset.decimals)?;
+    let store = env.storage().persistent();
+    let now = env.ledger().timestamp();
+    let tokenizer = tokenized_asset.tokenizer.clone();
+    let source = get_reinvestment_source(env, asset_id);
+
+    let mut total_cost = 0i128;
+    for holder in reinvestors.iter() {
+        let key = DataKey::RoundEntitlement(asset_id, round.round_id, holder.clone());
+        let mut entitlement: RoundEntitlement = store.get(&key).ok_or(Error::NoDividendsToClaim)?;
+        let gross = entitlement.amount;
+        let (_, _, net) = fee_split(env, &holder, gross)?;
+
+        let carry_key =
+            DataKey::ReinvestCarry(asset_id, holder.clone(), round.payment_token.clone());
+        let budget = math::add(net, store.get(&carry_key).unwrap_or(0))?;
+        let tokens = math::mul_div(budget, unit, price, Rounding::Down)?;
+        if tokens > 0
+            && check_reinvestment(env, asset_id, &tokenizer, &holder, tokens, &source).is_err()
+        {
+            // Emit event: (asset_id, round_id, holder)
+            env.events().publish(
+                ("dividend", "reinvest_skipped"),
+                (asset_id, round.round_id, holder),
+            );
+            continue;
+        }
+        let (fee, withheld, net) = take_fees(env, &round.payment_token, &holder, gross)?;
+        let cost = math::mul_div(tokens, price, unit, Rounding::Up)?;
+        let carry = math::sub(budget, cost)?;
+        if carry > 0 {
+            store.set(&carry_key, &carry);
+        } else {
+            store.remove(&carry_key);
+        }
+
+        entitlement.claimed = true;
+        entitlement.claimed_at = now;
+        store.set(&key, &entitlement);
+        round.claimed_amount = math::add(round.claimed_amount, gross)?;
+        store.set(
+            &DataKey::RoundClaim(asset_id, round.round_id, holder.clone()),
+            &DividendClaim {
+                round_id: round.round_id,
+                payment_token: round.payment_token.clone(),
+                gross_amount: gross,
+                fee_amount: fee,
+                withheld_amount: withheld,
+                net_amount: net,
+                reinvested_tokens: tokens,
+                claimed_at: now,
+            },
+        );
+
+        if tokens > 0 {
+            if source == ReinvestmentSource::Mint {
+                tokenization::mint_tokens(env, asset_id, tokens, tokenizer.clone())?;
+            }
+            tokenization::transfer_tokens(
+                env,
+                asset_id,
+                tokenizer.clone(),
+                holder.clone(),
+                tokens,
+            )?;
+            tokenization::record_purchase_price(env, asset_id, holder.clone(), tokens, price)?;
+        }
+        total_cost = math::add(total_cost, cost)?;
+
+        // Emit event: (asset_id, round_id, holder, cost, tokens, carry)
+        env.events().publish(
+            ("dividend", "reinvested"),
+            (asset_id, round.round_id, holder, cost, tokens, carry),
+        );
+    }
+
+    if total_cost > 0 {
+        token::Client::new(env, &round.payment_token).transfer(
+            &env.current_contract_address(),
+            &tokenizer,
+            &total_cost,
+        );
+    }
+
+    Ok(())
+}
+
+/// Pay out a holder's entitlement for one round
+fn settle_round_claim(
+    env: &Env,
+    asset_id: u64,
+    round_id: u32,
+    holder: &Address,
+) -> Result<DividendClaim, Error> {
+    let mut round = load_round(env, asset_id, round_id)?;
+    let now = env.ledger().timestamp();
+    if round.status != RoundStatus::Open || now >= round.expires_at {
+        return Err(Error::NoDividendsToClaim);
+    }
+
+    let store = env.storage().persistent();
+    let key = DataKey::RoundEntitlement(asset_id, round_id, holder.clone());
+    let mut entitlement: RoundEntitlement = store.get(&key).ok_or(Error::NoDividendsToClaim)?;
+    if entitlement.claimed {
+        return Err(Error::NoDividendsToClaim);
+    }
+
+    let gross = entitlement.amount;
+    let (fee, withheld, net) = take_fees(env, &round.payment_token, holder, gross)?;
+
+    entitlement.claimed = true;
+    entitlement.claimed_at = now;
+    store.set(&key, &entitlement);
+    round.claimed_amount = math::add(round.claimed_amount, gross)?;
+    save_round(env, &round);
+
+    if net > 0 {
+        token::Client::new(env, &round.payment_token).transfer(
+            &env.current_contract_address(),
+            holder,
+            &net,
+        );
+    }
+
+    let claim = DividendClaim {
+        round_id,
+        payment_token: round.payment_token,
+        gross_amount: gross,
+        fee_amount: fee,
+        withheld_amount: withheld,
+        net_amount: net,
+        reinvested_tokens: 0,
+        claimed_at: now,
+    };
+    store.set(
+        &DataKey::RoundClaim(asset_id, round_id, holder.clone()),
+        &claim,
+    );
+
+    // Emit event: (asset_id, round_id, holder, gross, fee, withheld, net)
+    env.events().publish(
+        ("dividend", "round_claimed"),
+        (
+            asset_id,
+            round_id,
+            holder.clone(),
+            gross,
+            fee,
+            withheld,
+            net,
+        ),
+    );
+
+    Ok(claim)
+}
+
+/// Claim a holder's share of a single round
+pub fn claim_round(
+    env: &Env,
+    asset_id: u64,
+    round_id: u32,
+    holder: Address,
+) -> Result<DividendClaim, Error> {
+    settle_round_claim(env, asset_id, round_id, &holder)
+}
+
+/// Claim every open round the holder has not yet claimed
+pub fn claim_all_rounds(
+    env: &Env,
+    asset_id: u64,
+    holder: Address,
+) -> Result<Vec<DividendClaim>, Error> {
+    let mut claims = Vec::new(env);
+    for round_id in get_claimable_rounds(env, asset_id, holder.clone()).iter() {
+        claims.push_back(settle_round_claim(env, asset_id, round_id, &holder)?);
+    }
+    if claims.is_empty() {
+        return Err(Error::NoDividendsToClaim);
+    }
+    Ok(claims)
+}
+
+/// Return the unclaimed remainder of an expired round to the tokenizer
+pub fn reclaim_expired_round(
+    env: &Env,
+    asset_id: u64,
+    round_id: u32,
+    caller: Address,
+) -> Result<i128, Error> {
+    let tokenized_asset = tokenization::get_tokenized_asset(env, asset_id)?;
+    if tokenized_asset.tokenizer != caller {
+        return Err(Error::Unauthorized);
+    }
+    let mut round = load_round(env, asset_id, round_id)?;
+    if round.status != RoundStatus::Open {
+        return Err(Error::NoDividendsToClaim);
+    }
+    if env.ledger().timestamp() < round.expires_at {
+        return Err(Error::InvalidTimestamps);
+    }
+
+    let remainder = math::sub(round.total_amount, round.claimed_amount)?;
+    round.status = RoundStatus::Reclaimed;
+    save_round(env, &round);
+    if remainder > 0 {
+        token::Client::new(env, &round.payment_token).transfer(
+            &env.current_contract_address(),
+            &caller,
+            &remainder,
+        );
+    }
+
+    // Emit event: (asset_id, round_id, amount)
+    env.events().publish(
+        ("dividend", "round_reclaimed"),
+        (asset_id, round_id, remainder),
+    );
+
+    Ok(remainder)
+}
+
+pub fn get_distribution_round(
+    env: &Env,
+    asset_id: u64,
+    round_id: u32,
+) -> Result<DistributionRound, Error> {
+    load_round(env, asset_id, round_id)
+}
+
+pub fn get_round_entitlement(
+    env: &Env,
+    asset_id: u64,
+    round_id: u32,
+    holder: Address,
+) -> Option<RoundEntitlement> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RoundEntitlement(asset_id, round_id, holder))
+}
+
+/// Open, unexpired rounds the holder has not claimed yet
+pub fn get_claimable_rounds(env: &Env, asset_id: u64, holder: Address) -> Vec<u32> {
+    let now = env.ledger().timestamp();
+    let mut claimable = Vec::new(env);
+    for round_id in holder_rounds(env, asset_id, &holder).iter() {
+        let open = load_round(env, asset_id, round_id)
+            .map(|round| round.status == RoundStatus::Open && now < round.expires_at)
+            .unwrap_or(false);
+        let unclaimed = get_round_entitlement(env, asset_id, round_id, holder.clone())
+            .map(|entitlement| !entitlement.claimed)
+            .unwrap_or(false);
+        if open && unclaimed {
+            claimable.push_back(round_id);
+        }
+    }
+    claimable
+}
+
+/// A holder's completed claims across all rounds, oldest round first
+pub fn get_claim_history(env: &Env, asset_id: u64, holder: Address) -> Vec<DividendClaim> {
+    let mut history = Vec::new(env);
+    for round_id in holder_rounds(env, asset_id, &holder).iter() {
+        if let Some(claim) =
+            env.storage()
+                .persistent()
+                .get(&DataKey::RoundClaim(asset_id, round_id, holder.clone()))
+        {
+            history.push_back(claim);
+        }
+    }
+    history
+}
+
+/// Withholding rate for the holder's verified jurisdiction. Holders without a
+/// valid identity claim have nothing withheld.
+fn withholding_rate_for(env: &Env, holder: &Address) -> u32 {
+    match identity::get_valid_identity(env, holder.clone()) {
+        Some(claims) => get_withholding_rate(env, claims.jurisdiction),
+        None => 0,
+    }
+}
+
+/// Set the platform fee and payout accounts. Admin auth is checked in lib.rs.
+pub fn set_dividend_fee_config(
+    env: &Env,
+    fee_bps: u32,
+    treasury: Address,
+    tax_authority: Address,
+) -> Result<(), Error> {
+    if fee_bps as i128 > math::BPS_DENOMINATOR {
+        return Err(Error::InvalidDividendAmount);
+    }
+    env.storage().persistent().set(
+        &DataKey::DividendFees,
+        &DividendFeeConfig {
+            fee_bps,
+            treasury: treasury.clone(),
+            tax_authority: tax_authority.clone(),
+        },
+    );
+
+    // Emit event: (fee_bps, treasury, tax_authority)
+    env.events().publish(
+        ("dividend", "fee_config_set"),
+        (fee_bps, treasury, tax_authority),
+    );
+
+    Ok(())
+}
+
+pub fn get_dividend_fee_config(env: &Env) -> Option<DividendFeeConfig> {
+    env.storage().persistent().get(&DataKey::DividendFees)
+}
+
+/// Set the withholding rate for an ISO country code. Admin auth is checked in lib.rs.
+pub fn set_withholding_rate(env: &Env, jurisdiction: String, rate_bps: u32) -> Result<(), Error> {
+    if rate_bps as i128 > math::BPS_DENOMINATOR {
+        return Err(Error::InvalidDividendAmount);
+    }
+    let key = DataKey::WithholdingRate(jurisdiction.clone());
+    if rate_bps == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &rate_bps);
+    }
+
+    // Emit event: (jurisdiction, rate_bps)
+    env.events()
+        .publish(("dividend", "withholding_set"), (jurisdiction, rate_bps));
+
+    Ok(())
+}
+
+pub fn get_withholding_rate(env: &Env, jurisdiction: String) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::WithholdingRate(jurisdiction))
+        .unwrap_or(0)
+}
+
+pub fn get_withheld_balance(env: &Env, payment_token: Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::WithheldBalance(payment_token))
+        .unwrap_or(0)
+}
+
+/// Pull pooled withholding to the caller (tax authority or treasury only)
+pub fn withdraw_withheld(
+    env: &Env,
+    payment_token: Address,
+    amount: i128,
+    caller: Address,
+) -> Result<(), Error> {
+    let config = get_dividend_fee_config(env).ok_or(Error::Unauthorized)?;
+    if caller != config.tax_authority && caller != config.treasury {
+        return Err(Error::Unauthorized);
+    }
+    if amount <= 0 {
+        return Err(Error::InvalidDividendAmount);
+    }
+    let pooled = get_withheld_balance(env, payment_token.clone());
+    if amount > pooled {
+        return Err(Error::InsufficientBalance);
+    }
+
+    env.storage().persistent().set(
+        &DataKey::WithheldBalance(payment_token.clone()),
+        &(pooled - amount),
+    );
+    token::Client::new(env, &payment_token).transfer(
+        &env.current_contract_address(),
+        &caller,
+        &amount,
+    );
+
+    // Emit event: (payment_token, caller, amount)
+    env.events().publish(
+        ("dividend", "withholding_withdrawn"),
+        (payment_token, caller, amount),
+    );
+
+    Ok(())
+}
+
+fn is_reinvesting(env: &Env, asset_id: u64, holder: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Reinvest(asset_id, holder.clone()))
+}
+
+/// Opt a holder in or out of reinvesting future distribution rounds
+pub fn set_reinvestment(
+    env: &Env,
+    asset_id: u64,
+    holder: Address,
+    enabled: bool,
+) -> Result<(), Error> {
+    tokenization::get_tokenized_asset(env, asset_id)?;
+    let key = DataKey::Reinvest(asset_id, holder.clone());
+    if enabled {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+
+    // Emit event: (asset_id, holder, enabled)
+    env.events().publish(
+        ("dividend", "reinvestment_set"),
+        (asset_id, holder, enabled),
+    );
+
+    Ok(())
+}
+
+pub fn get_reinvestment(env: &Env, asset_id: u64, holder: Address) -> bool {
+    is_reinvesting(env, asset_id, &holder)
+}
+
+/// Choose whether reinvested dividends mint new tokens or draw on the
+/// tokenizer's balance (tokenizer only)
+pub fn set_reinvestment_source(
+    env: &Env,
+    asset_id: u64,
+    source: ReinvestmentSource,
+    caller: Address,
+) -> Result<(), Error> {
+    let tokenized_asset = tokenization::get_tokenized_asset(env, asset_id)?;
+    if tokenized_asset.tokenizer != caller {
+        return Err(Error::Unauthorized);
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::ReinvestSource(asset_id), &source);
+    Ok(())
+}
+
+pub fn get_reinvestment_source(env: &Env, asset_id: u64) -> ReinvestmentSource {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ReinvestSource(asset_id))
+        .unwrap_or(ReinvestmentSource::Treasury)
+}
+
+pub fn get_reinvestment_carry(
+    env: &Env,
+    asset_id: u64,
+    holder: Address,
+    payment_token: Address,
+) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ReinvestCarry(asset_id, holder, payment_token))
+        .unwrap_or(0)
+}
+
+/// Pay out a holder's carried-forward reinvestment remainder in cash
+pub fn withdraw_reinvestment_carry(
+    env: &Env,
+    asset_id: u64,
+    holder: Address,
+    payment_token: Address,
+) -> Result<i128, Error> {
+    let carry = get_reinvestment_carry(env, asset_id, holder.clone(), payment_token.clone());
+    if carry <= 0 {
+        return Err(Error::NoDividendsToClaim);
+    }
+    env.storage().persistent().remove(&DataKey::ReinvestCarry(
+        asset_id,
+        holder.clone(),
+        payment_token.clone(),
+    ));
+    token::Client::new(env, &payment_token).transfer(
+        &env.current_contract_address(),
+        &holder,
+        &carry,
+    );
+
+    // Emit event: (asset_id, holder, payment_token, amount)
+    env.events().publish(
+        ("dividend", "carry_withdrawn"),
+        (asset_id, holder, payment_token, carry),
+    );
+
+    Ok(carry)
+}
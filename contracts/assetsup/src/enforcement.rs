@@ -0,0 +1,427 @@
+use soroban_sdk::{contracttype, Address, BytesN, Env, Vec};
+
+use crate::compliance;
+use crate::error::Error;
+use crate::tokenization;
+use crate::transfer_restrictions;
+use crate::types::{TokenDataKey, TokenizedAsset};
+use crate::vesting;
+
+// ─── Types ────────────────────────────────────────────────────────────────────
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EnforcementAction {
+    AgentGranted,
+    AgentRevoked,
+    Frozen,
+    Unfrozen,
+    Denylisted,
+    RemovedFromDenylist,
+    ForcedTransfer,
+}
+
+/// Freeze placed on a holder. A full freeze blocks every outgoing transfer;
+/// otherwise `amount` tokens must stay with the holder.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FreezeState {
+    pub full: bool,
+    pub amount: i128,
+    pub frozen_at: u64,
+    /// Number of corporate actions already applied to `amount`
+    pub scale_epoch: u32,
+}
+
+/// Audit log entry for a regulatory action on a tokenized asset
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EnforcementRecord {
+    pub action: EnforcementAction,
+    pub actor: Address,
+    pub target: Address,
+    pub amount: i128,
+    pub reason_hash: Option<BytesN<32>>,
+    pub timestamp: u64,
+}
+
+// ─── Storage Keys ─────────────────────────────────────────────────────────────
+
+#[contracttype]
+pub enum DataKey {
+    /// Compliance agent allowed to freeze and denylist for an asset
+    AssetAgent(u64, Address),
+    Frozen(u64, Address),
+    Denylisted(u64, Address),
+    EnforcementLog(u64),
+    /// Audit log for contract-wide actions such as sanctions
+    GlobalEnforcementLog,
+}
+
+// ─── Internal helpers ─────────────────────────────────────────────────────────
+
+fn load_tokenized_asset(env: &Env, asset_id: u64) -> Result<TokenizedAsset, Error> {
+    env.storage()
+        .persistent()
+        .get(&TokenDataKey::TokenizedAsset(asset_id))
+        .ok_or(Error::AssetNotTokenized)
+}
+
+/// The tokenizer (issuer) or one of the asset's compliance agents
+fn require_agent(env: &Env, asset_id: u64, caller: &Address) -> Result<(), Error> {
+    let tokenized_asset = load_tokenized_asset(env, asset_id)?;
+    if tokenized_asset.tokenizer == *caller || is_agent(env, asset_id, caller.clone()) {
+        Ok(())
+    } else {
+        Err(Error::Unauthorized)
+    }
+}
+
+fn require_issuer(env: &Env, asset_id: u64, caller: &Address) -> Result<(), Error> {
+    if load_tokenized_asset(env, asset_id)?.tokenizer == *caller {
+        Ok(())
+    } else {
+        Err(Error::Unauthorized)
+    }
+}
+
+fn append_log(env: &Env, key: &DataKey, entry: EnforcementRecord) {
+    let mut log: Vec<EnforcementRecord> = env
+        .storage()
+        .persistent()
+        .get(key)
+        .unwrap_or_else(|| Vec::new(env));
+    log.push_back(entry);
+    env.storage().persistent().set(key, &log);
+}
+
+fn record(
+    env: &Env,
+    asset_id: u64,
+    action: EnforcementAction,
+    actor: Address,
+    target: Address,
+    amount: i128,
+    reason_hash: Option<BytesN<32>>,
+) {
+    append_log(
+        env,
+        &DataKey::EnforcementLog(asset_id),
+        EnforcementRecord {
+            action: action.clone(),
+            actor: actor.clone(),
+            target: target.clone(),
+            amount,
+            reason_hash,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    // Emit event: (asset_id, action, actor, target, amount)
+    env.events().publish(
+        ("enforce", "action_recorded"),
+        (asset_id, action, actor, target, amount),
+    );
+}
+
+// ─── Public functions (called from lib.rs) ────────────────────────────────────
+
+/// Appoint a compliance agent for an asset (issuer only)
+pub fn grant_agent(env: &Env, asset_id: u64, agent: Address, caller: Address) -> Result<(), Error> {
+    require_issuer(env, asset_id, &caller)?;
+    env.storage()
+        .persistent()
+        .set(&DataKey::AssetAgent(asset_id, agent.clone()), &true);
+    record(
+        env,
+        asset_id,
+        EnforcementAction::AgentGranted,
+        caller,
+        agent,
+        0,
+        None,
+    );
+    Ok(())
+}
+
+pub fn revoke_agent(
+    env: &Env,
+    asset_id: u64,
+    agent: Address,
+    caller: Address,
+) -> Result<(), Error> {
+    require_issuer(env, asset_id, &caller)?;
+    env.storage()
+        .persistent()
+        .remove(&DataKey::AssetAgent(asset_id, agent.clone()));
+    record(
+        env,
+        asset_id,
+        EnforcementAction::AgentRevoked,
+        caller,
+        agent,
+        0,
+        None,
+    );
+    Ok(())
+}
+
+pub fn is_agent(env: &Env, asset_id: u64, address: Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::AssetAgent(asset_id, address))
+}
+
+/// Freeze a holder's tokens. `amount` of `None` freezes the full balance,
+/// including tokens received later. Replaces any existing freeze.
+pub fn freeze_tokens(
+    env: &Env,
+    asset_id: u64,
+    holder: Address,
+    amount: Option<i128>,
+    caller: Address,
+) -> Result<(), Error> {
+    require_agent(env, asset_id, &caller)?;
+
+    let state = match amount {
+        None => FreezeState {
+            full: true,
+            amount: 0,
+            frozen_at: env.ledger().timestamp(),
+            scale_epoch: tokenization::current_scale_epoch(env, asset_id),
+        },
+        Some(amount) => {
+            if amount <= 0 {
+                return Err(Error::InvalidTokenSupply);
+            }
+            if amount > tokenization::get_token_balance(env, asset_id, holder.clone())? {
+                return Err(Error::InsufficientBalance);
+            }
+            FreezeState {
+                full: false,
+                amount,
+                frozen_at: env.ledger().timestamp(),
+                scale_epoch: tokenization::current_scale_epoch(env, asset_id),
+            }
+        }
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::Frozen(asset_id, holder.clone()), &state);
+    tokenization::sync_holder(env, asset_id, &holder)?;
+
+    record(
+        env,
+        asset_id,
+        EnforcementAction::Frozen,
+        caller,
+        holder,
+        state.amount,
+        None,
+    );
+    Ok(())
+}
+
+pub fn unfreeze_tokens(
+    env: &Env,
+    asset_id: u64,
+    holder: Address,
+    caller: Address,
+) -> Result<(), Error> {
+    require_agent(env, asset_id, &caller)?;
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Frozen(asset_id, holder.clone()));
+    tokenization::sync_holder(env, asset_id, &holder)?;
+    record(
+        env,
+        asset_id,
+        EnforcementAction::Unfrozen,
+        caller,
+        holder,
+        0,
+        None,
+    );
+    Ok(())
+}
+
+/// Get a holder's freeze with the frozen amount in the current denomination
+pub fn get_freeze(env: &Env, asset_id: u64, holder: Address) -> Result<Option<FreezeState>, Error> {
+    let stored: Option<FreezeState> =
+        tokenization::load_scaled_record(env, &DataKey::Frozen(asset_id, holder))?;
+    let Some(mut state) = stored else {
+        return Ok(None);
+    };
+
+    let actions = tokenization::get_corporate_actions(env, asset_id);
+    state.amount = tokenization::scale_amount(&actions, state.scale_epoch, state.amount)?;
+    state.scale_epoch = actions.len();
+    Ok(Some(state))
+}
+
+/// Amount of a holder's balance that cannot move because of a freeze
+pub fn get_frozen_amount(env: &Env, asset_id: u64, holder: Address) -> Result<i128, Error> {
+    match get_freeze(env, asset_id, holder.clone())? {
+        Some(state) if state.full => tokenization::get_token_balance(env, asset_id, holder),
+        Some(state) => Ok(state.amount),
+        None => Ok(0),
+    }
+}
+
+/// Add or remove an address on an asset's denylist (issuer or agent)
+pub fn set_denylisted(
+    env: &Env,
+    asset_id: u64,
+    address: Address,
+    denylisted: bool,
+    caller: Address,
+) -> Result<(), Error> {
+    require_agent(env, asset_id, &caller)?;
+
+    let key = DataKey::Denylisted(asset_id, address.clone());
+    let action = if denylisted {
+        env.storage().persistent().set(&key, &true);
+        EnforcementAction::Denylisted
+    } else {
+        env.storage().persistent().remove(&key);
+        EnforcementAction::RemovedFromDenylist
+    };
+    record(env, asset_id, action, caller, address, 0, None);
+    Ok(())
+}
+
+/// Add or remove an address on the contract-wide denylist, which is the
+/// admin's sanctions list. Admin auth is checked in lib.rs.
+pub fn set_global_denylisted(
+    env: &Env,
+    address: Address,
+    denylisted: bool,
+    admin: Address,
+) -> Result<(), Error> {
+    compliance::set_sanctioned(env, address.clone(), denylisted)?;
+
+    let action = if denylisted {
+        EnforcementAction::Denylisted
+    } else {
+        EnforcementAction::RemovedFromDenylist
+    };
+    append_log(
+        env,
+        &DataKey::GlobalEnforcementLog,
+        EnforcementRecord {
+            action: action.clone(),
+            actor: admin.clone(),
+            target: address.clone(),
+            amount: 0,
+            reason_hash: None,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    // Emit event: (action, admin, address)
+    env.events().publish(
+        ("enforce", "global_action_recorded"),
+        (action, admin, address),
+    );
+
+    Ok(())
+}
+
+/// True if the address is denylisted for the asset or sanctioned globally
+pub fn is_denylisted(env: &Env, asset_id: u64, address: Address) -> bool {
+    compliance::is_sanctioned(env, address.clone())
+        || env
+            .storage()
+            .persistent()
+            .has(&DataKey::Denylisted(asset_id, address))
+}
+
+/// Reject transfers involving denylisted addresses or a fully frozen sender.
+/// Partial freezes are checked with the holder's other restrictions in
+/// `tokenization::transfer_tokens`.
+pub fn check_transfer(env: &Env, asset_id: u64, from: &Address, to: &Address) -> Result<(), Error> {
+    if is_denylisted(env, asset_id, from.clone()) || is_denylisted(env, asset_id, to.clone()) {
+        return Err(Error::TransferRestrictionFailed);
+    }
+
+    if let Some(state) = get_freeze(env, asset_id, from.clone())? {
+        if state.full {
+            return Err(Error::TokensAreLocked);
+        }
+    }
+
+    Ok(())
+}
+
+/// Move tokens out of a holder's wallet regardless of locks, freezes and vesting,
+/// e.g. to recover tokens from a lost or compromised wallet. Issuer only.
+/// The receiver must still pass identity and compliance checks, and the
+/// holder's restrictions shrink to fit what is left in the wallet.
+pub fn forced_transfer(
+    env: &Env,
+    asset_id: u64,
+    from: Address,
+    to: Address,
+    amount: i128,
+    reason_hash: BytesN<32>,
+    caller: Address,
+) -> Result<(), Error> {
+    require_issuer(env, asset_id, &caller)?;
+    if amount <= 0 {
+        return Err(Error::InvalidTokenSupply);
+    }
+    if is_denylisted(env, asset_id, to.clone()) {
+        return Err(Error::TransferRestrictionFailed);
+    }
+    // Receiver-side rules only, as for a mint: the sender's rules are what is
+    // bypassed, so tokens can be recovered from a wallet whose KYC has lapsed
+    transfer_restrictions::validate_receiver(env, asset_id, to.clone())?;
+    compliance::check_transfer(env, asset_id, None, Some(to.clone()), amount)?;
+
+    // Partial locks are shrunk by `move_tokens` itself
+    tokenization::move_tokens(env, asset_id, from.clone(), to.clone(), amount)?;
+    let balance = tokenization::get_token_balance(env, asset_id, from.clone())?;
+
+    // A partial freeze cannot exceed what is left in the wallet
+    let freeze_key = DataKey::Frozen(asset_id, from.clone());
+    if let Some(mut state) = get_freeze(env, asset_id, from.clone())? {
+        if !state.full && state.amount > balance {
+            state.amount = balance;
+            env.storage().persistent().set(&freeze_key, &state);
+        }
+    }
+    vesting::clamp_unvested(env, asset_id, &from, balance)?;
+
+    record(
+        env,
+        asset_id,
+        EnforcementAction::ForcedTransfer,
+        caller,
+        from.clone(),
+        amount,
+        Some(reason_hash.clone()),
+    );
+
+    // Emit event: (asset_id, from, to, amount, reason_hash)
+    env.events().publish(
+        ("enforce", "forced_transfer"),
+        (asset_id, from, to, amount, reason_hash),
+    );
+
+    Ok(())
+}
+
+pub fn get_enforcement_log(env: &Env, asset_id: u64) -> Vec<EnforcementRecord> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::EnforcementLog(asset_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Audit log of contract-wide actions such as global denylisting
+pub fn get_global_enforcement_log(env: &Env) -> Vec<EnforcementRecord> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::GlobalEnforcementLog)
+        .unwrap_or_else(|| Vec::new(env))
+}
@@ -60,6 +60,12 @@ pub enum Error {
     LeaseAlreadyStarted = 44,
     LeaseNotExpired = 45,
     InvalidTimestamps = 46,
+    // Attestation errors
+    InvalidAttestation = 47,
+    // Marketplace errors
+    OrderNotFound = 48,
+    // Offering errors
+    OfferingNotActive = 49,
 }
 
 pub fn handle_error(env: &Env, error: Error) -> ! {
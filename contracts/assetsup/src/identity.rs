@@ -0,0 +1,139 @@
+use soroban_sdk::{contracttype, Address, Env, String};
+
+use crate::error::Error;
+
+// ─── Types ────────────────────────────────────────────────────────────────────
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InvestorType {
+    Retail,
+    Professional,
+    Institutional,
+}
+
+/// Compliance claims about an investor, issued by a trusted claim issuer
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IdentityClaims {
+    pub accredited: bool,
+    /// ISO 3166-1 alpha-2 country code
+    pub jurisdiction: String,
+    /// KYC verification is valid until this timestamp
+    pub kyc_expiry: u64,
+    pub investor_type: InvestorType,
+    pub issuer: Address,
+    pub issued_at: u64,
+}
+
+// ─── Storage Keys ─────────────────────────────────────────────────────────────
+
+#[contracttype]
+pub enum DataKey {
+    ClaimIssuer(Address),
+    Identity(Address),
+}
+
+// ─── Public functions (called from lib.rs) ────────────────────────────────────
+
+/// Trust an address to issue identity claims. Admin auth is checked in lib.rs.
+pub fn add_claim_issuer(env: &Env, issuer: Address) -> Result<(), Error> {
+    env.storage()
+        .persistent()
+        .set(&DataKey::ClaimIssuer(issuer.clone()), &true);
+
+    // Emit event: (issuer)
+    env.events()
+        .publish(("identity", "claim_issuer_added"), (issuer,));
+
+    Ok(())
+}
+
+pub fn remove_claim_issuer(env: &Env, issuer: Address) -> Result<(), Error> {
+    let key = DataKey::ClaimIssuer(issuer.clone());
+    if !env.storage().persistent().has(&key) {
+        return Err(Error::Unauthorized);
+    }
+    env.storage().persistent().remove(&key);
+
+    // Emit event: (issuer)
+    env.events()
+        .publish(("identity", "claim_issuer_removed"), (issuer,));
+
+    Ok(())
+}
+
+pub fn is_claim_issuer(env: &Env, issuer: Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::ClaimIssuer(issuer))
+}
+
+/// Record claims for an investor, replacing any previous claims
+pub fn set_identity(
+    env: &Env,
+    issuer: Address,
+    investor: Address,
+    accredited: bool,
+    jurisdiction: String,
+    kyc_expiry: u64,
+    investor_type: InvestorType,
+) -> Result<(), Error> {
+    if !is_claim_issuer(env, issuer.clone()) {
+        return Err(Error::Unauthorized);
+    }
+    if kyc_expiry <= env.ledger().timestamp() {
+        return Err(Error::InvalidTimestamps);
+    }
+
+    let claims = IdentityClaims {
+        accredited,
+        jurisdiction,
+        kyc_expiry,
+        investor_type,
+        issuer: issuer.clone(),
+        issued_at: env.ledger().timestamp(),
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::Identity(investor.clone()), &claims);
+
+    // Emit event: (investor, issuer, accredited, kyc_expiry)
+    env.events().publish(
+        ("identity", "identity_set"),
+        (investor, issuer, accredited, kyc_expiry),
+    );
+
+    Ok(())
+}
+
+/// Remove an investor's claims. Any trusted claim issuer may revoke.
+pub fn revoke_identity(env: &Env, issuer: Address, investor: Address) -> Result<(), Error> {
+    if !is_claim_issuer(env, issuer.clone()) {
+        return Err(Error::Unauthorized);
+    }
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Identity(investor.clone()));
+
+    // Emit event: (investor, issuer)
+    env.events()
+        .publish(("identity", "identity_revoked"), (investor, issuer));
+
+    Ok(())
+}
+
+pub fn get_identity(env: &Env, investor: Address) -> Option<IdentityClaims> {
+    env.storage().persistent().get(&DataKey::Identity(investor))
+}
+
+/// Claims that can be relied on right now: KYC not expired and the
+/// issuing party still trusted
+pub fn get_valid_identity(env: &Env, investor: Address) -> Option<IdentityClaims> {
+    let claims = get_identity(env, investor)?;
+    if env.ledger().timestamp() >= claims.kyc_expiry || !is_claim_issuer(env, claims.issuer.clone())
+    {
+        return None;
+    }
+    Some(claims)
+}
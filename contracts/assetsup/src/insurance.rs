@@ -1,7 +1,9 @@
 #![allow(dead_code)]
 
+use crate::asset;
+use crate::math;
 use crate::Error;
-use soroban_sdk::{contracttype, log, Address, BytesN, Env, Vec};
+use soroban_sdk::{contracttype, log, token, Address, Bytes, BytesN, Env, Vec};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -72,6 +74,37 @@ pub struct InsuranceClaim {
     pub status: ClaimStatus,
     pub filed_at: u64,
     pub approved_amount: i128,
+    /// Approved loss less the deductible, capped at the policy's remaining coverage
+    pub payout_amount: i128,
+}
+
+/// On-chain premium billing for a policy. The premium for each term is split
+/// into equal installments, the first due at the start of the term.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PremiumSchedule {
+    /// SEP-41 token premiums are paid in
+    pub payment_token: Address,
+    pub installments: u32,
+    /// Time after an installment's due date before the policy is suspended
+    pub grace_period: u64,
+    pub term_start: u64,
+    pub term_end: u64,
+    /// Installments paid in the current term
+    pub installments_paid: u32,
+    /// Whether the policy was suspended for a missed installment
+    pub lapsed: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PremiumPayment {
+    pub term_start: u64,
+    /// Index of the first installment covered, from 0
+    pub first_installment: u32,
+    pub installments: u32,
+    pub amount: i128,
+    pub paid_at: u64,
 }
 
 #[contracttype]
@@ -81,6 +114,128 @@ pub enum DataKey {
     Claim(BytesN<32>),
     AssetPolicies(BytesN<32>),
     AssetClaims(BytesN<32>),
+    PremiumSchedule(BytesN<32>),
+    PremiumPayments(BytesN<32>),
+    /// Page of live billed policies; see `BILLED_PAGE_SIZE`
+    BilledPolicyPage(u32),
+    /// Number of live billed policies across all pages
+    BilledPolicyCount,
+    /// Position of a policy in the billed index
+    BilledPolicySlot(BytesN<32>),
+    PolicyClaims(BytesN<32>),
+    /// Payouts approved on a policy so far, counted against its coverage
+    PolicyClaimedAmount(BytesN<32>),
+    ClaimCounter,
+}
+
+/// Billed policies are indexed in fixed-size pages so no single entry grows
+/// with the number of policies
+pub const BILLED_PAGE_SIZE: u32 = 100;
+
+fn billed_page(env: &Env, page: u32) -> Vec<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BilledPolicyPage(page))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn billed_count(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BilledPolicyCount)
+        .unwrap_or(0)
+}
+
+/// Add a policy to the end of the billed index, if it is not already there
+fn index_billed_policy(env: &Env, policy_id: &BytesN<32>) {
+    let store = env.storage().persistent();
+    let slot_key = DataKey::BilledPolicySlot(policy_id.clone());
+    if store.has(&slot_key) {
+        return;
+    }
+    let slot = billed_count(env);
+    let mut page = billed_page(env, slot / BILLED_PAGE_SIZE);
+    page.push_back(policy_id.clone());
+    store.set(&DataKey::BilledPolicyPage(slot / BILLED_PAGE_SIZE), &page);
+    store.set(&slot_key, &slot);
+    store.set(&DataKey::BilledPolicyCount, &(slot + 1));
+}
+
+/// Drop a finished policy from the billed index by moving the last entry
+/// into its slot
+fn unindex_billed_policy(env: &Env, policy_id: &BytesN<32>) {
+    let store = env.storage().persistent();
+    let slot_key = DataKey::BilledPolicySlot(policy_id.clone());
+    let Some(slot) = store.get::<_, u32>(&slot_key) else {
+        return;
+    };
+    let last = billed_count(env) - 1;
+    let last_page_key = DataKey::BilledPolicyPage(last / BILLED_PAGE_SIZE);
+    let mut last_page = billed_page(env, last / BILLED_PAGE_SIZE);
+    let moved = last_page.pop_back_unchecked();
+    if last_page.is_empty() {
+        store.remove(&last_page_key);
+    } else {
+        store.set(&last_page_key, &last_page);
+    }
+
+    if slot != last {
+        let mut page = billed_page(env, slot / BILLED_PAGE_SIZE);
+        page.set(slot % BILLED_PAGE_SIZE, moved.clone());
+        store.set(&DataKey::BilledPolicyPage(slot / BILLED_PAGE_SIZE), &page);
+        store.set(&DataKey::BilledPolicySlot(moved), &slot);
+    }
+    store.remove(&slot_key);
+    store.set(&DataKey::BilledPolicyCount, &last);
+}
+
+fn load_schedule(env: &Env, policy_id: &BytesN<32>) -> Option<PremiumSchedule> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PremiumSchedule(policy_id.clone()))
+}
+
+fn save_schedule(env: &Env, policy_id: &BytesN<32>, schedule: &PremiumSchedule) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::PremiumSchedule(policy_id.clone()), schedule);
+}
+
+/// Due date of installment `index` in the current term
+fn installment_due(schedule: &PremiumSchedule, index: u32) -> u64 {
+    let interval = (schedule.term_end - schedule.term_start) / schedule.installments as u64;
+    schedule.term_start + interval * index as u64
+}
+
+/// Amount of installment `index`; the last one absorbs any rounding remainder
+fn installment_amount(premium: i128, installments: u32, index: u32) -> i128 {
+    let base = premium / installments as i128;
+    if index + 1 == installments {
+        premium - base * (installments as i128 - 1)
+    } else {
+        base
+    }
+}
+
+/// Whether the next unpaid installment is past its grace period
+fn is_overdue(schedule: &PremiumSchedule, now: u64) -> bool {
+    schedule.installments_paid < schedule.installments
+        && now
+            > installment_due(schedule, schedule.installments_paid)
+                .saturating_add(schedule.grace_period)
+}
+
+/// Report an active policy as suspended once an installment is missed, ahead
+/// of `process_renewals` recording it
+fn with_premium_status(env: &Env, mut policy: InsurancePolicy) -> InsurancePolicy {
+    if policy.status == PolicyStatus::Active {
+        if let Some(schedule) = load_schedule(env, &policy.policy_id) {
+            if is_overdue(&schedule, env.ledger().timestamp()) {
+                policy.status = PolicyStatus::Suspended;
+            }
+        }
+    }
+    policy
 }
 
 /// Create a new insurance policy with date validation and asset indexing
@@ -148,6 +303,7 @@ pub fn cancel_policy(env: Env, policy_id: BytesN<32>, caller: Address) -> Result
 
     policy.status = PolicyStatus::Cancelled;
     store.set(&key, &policy);
+    unindex_billed_policy(&env, &policy_id);
 
     log!(&env, "PolicyCancelled: {:?}", policy_id);
     Ok(())
@@ -196,8 +352,18 @@ pub fn expire_policy(env: Env, policy_id: BytesN<32>) -> Result<(), Error> {
         return Err(Error::Unauthorized);
     }
 
+    // A fully paid auto-renewing policy rolls over in `process_renewals` instead
+    if policy.auto_renew && policy.status == PolicyStatus::Active {
+        if let Some(schedule) = load_schedule(&env, &policy_id) {
+            if schedule.installments_paid == schedule.installments {
+                return Err(Error::Unauthorized);
+            }
+        }
+    }
+
     policy.status = PolicyStatus::Expired;
     store.set(&key, &policy);
+    unindex_billed_policy(&env, &policy_id);
 
     log!(&env, "PolicyExpired: {:?}", policy_id);
     Ok(())
@@ -238,6 +404,20 @@ pub fn renew_policy(
         return Err(Error::InvalidPayment);
     }
 
+    // A billed policy must have paid the current term before starting the next
+    if let Some(mut schedule) = load_schedule(&env, &policy_id) {
+        if schedule.installments_paid < schedule.installments || new_end_date <= schedule.term_end {
+            return Err(Error::InvalidPayment);
+        }
+        schedule.term_start = schedule.term_end;
+        schedule.term_end = new_end_date;
+        schedule.installments_paid = 0;
+        schedule.lapsed = false;
+        save_schedule(&env, &policy_id, &schedule);
+        // An expired policy was dropped from billing; renewal brings it back
+        index_billed_policy(&env, &policy_id);
+    }
+
     // Update policy
     policy.end_date = new_end_date;
     policy.premium = new_premium;
@@ -258,58 +438,90 @@ pub fn get_asset_policies(env: Env, asset_id: BytesN<32>) -> Vec<BytesN<32>> {
         .unwrap_or_else(|| Vec::new(&env))
 }
 
-/// File a new insurance claim against an active policy
-pub fn file_insurance_claim(env: Env, claim: InsuranceClaim) -> Result<(), Error> {
-    // Claimant must authenticate
-    claim.claimant.require_auth();
+/// Derive a claim ID from the policy and a contract-wide claim counter
+fn next_claim_id(env: &Env, policy_id: &BytesN<32>) -> BytesN<32> {
+    let store = env.storage().persistent();
+    let counter: u64 = store.get(&DataKey::ClaimCounter).unwrap_or(0) + 1;
+    store.set(&DataKey::ClaimCounter, &counter);
+
+    let mut payload = Bytes::from(policy_id.clone());
+    payload.append(&Bytes::from_array(env, &counter.to_be_bytes()));
+    env.crypto().sha256(&payload).into()
+}
 
+/// File a new insurance claim against an active policy (holder only).
+/// The claim is linked to the policy's registered asset and must exceed the
+/// deductible. Returns the generated claim ID.
+pub fn file_insurance_claim(
+    env: Env,
+    policy_id: BytesN<32>,
+    claimant: Address,
+    claim_type: ClaimType,
+    amount: i128,
+) -> Result<BytesN<32>, Error> {
     let store = env.storage().persistent();
-    let policy_key = DataKey::Policy(claim.policy_id.clone());
+    let policy_key = DataKey::Policy(policy_id.clone());
 
-    // Verify policy exists and is Active
+    // Verify policy exists and is Active, with no missed premium installment
     let policy: InsurancePolicy = store.get(&policy_key).ok_or(Error::AssetNotFound)?;
+    let policy = with_premium_status(&env, policy);
     if policy.status != PolicyStatus::Active {
         return Err(Error::Unauthorized);
     }
 
-    // Verify claim amount is positive
-    if claim.amount <= 0 {
-        return Err(Error::InvalidPayment);
+    // Only the policy holder can claim
+    if claimant != policy.holder {
+        return Err(Error::Unauthorized);
     }
 
-    // Verify claim doesn't already exist
-    let claim_key = DataKey::Claim(claim.claim_id.clone());
-    if store.has(&claim_key) {
-        return Err(Error::AssetAlreadyExists);
+    // The insured asset must be in the registry
+    if !store.has(&asset::DataKey::Asset(policy.asset_id.clone())) {
+        return Err(Error::AssetNotFound);
     }
 
-    // Verify claim status is Submitted
-    if claim.status != ClaimStatus::Submitted {
-        return Err(Error::Unauthorized);
+    // Losses within the deductible are not claimable
+    if amount <= policy.deductible {
+        return Err(Error::InvalidPayment);
     }
 
-    // Store the claim
-    store.set(&claim_key, &claim);
-
-    // Index claim by asset_id
+    let claim_id = next_claim_id(&env, &policy_id);
+    let claim = InsuranceClaim {
+        claim_id: claim_id.clone(),
+        policy_id: policy_id.clone(),
+        asset_id: policy.asset_id.clone(),
+        claimant,
+        claim_type,
+        amount,
+        status: ClaimStatus::Submitted,
+        filed_at: env.ledger().timestamp(),
+        approved_amount: 0,
+        payout_amount: 0,
+    };
+    store.set(&DataKey::Claim(claim_id.clone()), &claim);
+
+    // Index claim by asset_id and policy_id
     let mut asset_claims: Vec<BytesN<32>> = store
         .get(&DataKey::AssetClaims(claim.asset_id.clone()))
         .unwrap_or_else(|| Vec::new(&env));
-    asset_claims.push_back(claim.claim_id.clone());
+    asset_claims.push_back(claim_id.clone());
     store.set(&DataKey::AssetClaims(claim.asset_id.clone()), &asset_claims);
 
-    log!(&env, "ClaimFiled: {:?}", claim.claim_id);
-    Ok(())
+    let mut policy_claims: Vec<BytesN<32>> = store
+        .get(&DataKey::PolicyClaims(policy_id.clone()))
+        .unwrap_or_else(|| Vec::new(&env));
+    policy_claims.push_back(claim_id.clone());
+    store.set(&DataKey::PolicyClaims(policy_id), &policy_claims);
+
+    log!(&env, "ClaimFiled: {:?}", claim_id);
+    Ok(claim_id)
 }
 
-/// Move a claim from Submitted to UnderReview status
+/// Move a claim from Submitted or Disputed to UnderReview status
 pub fn mark_insurance_claim_under_review(
     env: Env,
     claim_id: BytesN<32>,
     insurer: Address,
 ) -> Result<(), Error> {
-    insurer.require_auth();
-
     let store = env.storage().persistent();
     let claim_key = DataKey::Claim(claim_id.clone());
 
@@ -323,8 +535,8 @@ pub fn mark_insurance_claim_under_review(
         return Err(Error::Unauthorized);
     }
 
-    // Validate status transition: only Submitted claims can move to UnderReview
-    if claim.status != ClaimStatus::Submitted {
+    // Validate status transition: only Submitted or Disputed claims can move to UnderReview
+    if claim.status != ClaimStatus::Submitted && claim.status != ClaimStatus::Disputed {
         return Err(Error::Unauthorized);
     }
 
@@ -342,8 +554,6 @@ pub fn approve_insurance_claim(
     insurer: Address,
     approved_amount: i128,
 ) -> Result<(), Error> {
-    insurer.require_auth();
-
     let store = env.storage().persistent();
     let claim_key = DataKey::Claim(claim_id.clone());
 
@@ -367,14 +577,29 @@ pub fn approve_insurance_claim(
         return Err(Error::InvalidPayment);
     }
 
-    // Approved amount cannot exceed coverage amount
-    if approved_amount > policy.coverage_amount {
+    // Approved amount cannot exceed the loss claimed
+    if approved_amount > claim.amount {
+        return Err(Error::InvalidPayment);
+    }
+
+    // Payouts across all claims on the policy are capped at its coverage
+    let claimed_key = DataKey::PolicyClaimedAmount(claim.policy_id.clone());
+    let claimed: i128 = store.get(&claimed_key).unwrap_or(0);
+    let remaining = math::sub(policy.coverage_amount, claimed)?;
+    // Nothing is paid out on a loss within the deductible
+    if approved_amount <= policy.deductible {
+        return Err(Error::InvalidPayment);
+    }
+    let payout = math::sub(approved_amount, policy.deductible)?.min(remaining);
+    if payout <= 0 {
         return Err(Error::InvalidPayment);
     }
 
     claim.status = ClaimStatus::Approved;
     claim.approved_amount = approved_amount;
+    claim.payout_amount = payout;
     store.set(&claim_key, &claim);
+    store.set(&claimed_key, &math::add(claimed, payout)?);
 
     log!(&env, "ClaimApproved: {:?}", claim_id);
     Ok(())
@@ -386,8 +611,6 @@ pub fn reject_insurance_claim(
     claim_id: BytesN<32>,
     insurer: Address,
 ) -> Result<(), Error> {
-    insurer.require_auth();
-
     let store = env.storage().persistent();
     let claim_key = DataKey::Claim(claim_id.clone());
 
@@ -419,8 +642,6 @@ pub fn dispute_insurance_claim(
     claim_id: BytesN<32>,
     claimant: Address,
 ) -> Result<(), Error> {
-    claimant.require_auth();
-
     let store = env.storage().persistent();
     let claim_key = DataKey::Claim(claim_id.clone());
 
@@ -445,8 +666,6 @@ pub fn dispute_insurance_claim(
 
 /// Mark an approved claim as paid
 pub fn pay_insurance_claim(env: Env, claim_id: BytesN<32>, insurer: Address) -> Result<(), Error> {
-    insurer.require_auth();
-
     let store = env.storage().persistent();
     let claim_key = DataKey::Claim(claim_id.clone());
 
@@ -485,6 +704,235 @@ pub fn get_asset_insurance_claims(env: Env, asset_id: BytesN<32>) -> Vec<BytesN<
         .unwrap_or_else(|| Vec::new(&env))
 }
 
+/// Get all claims filed on a policy
+pub fn get_policy_claims(env: Env, policy_id: BytesN<32>) -> Vec<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PolicyClaims(policy_id))
+        .unwrap_or_else(|| Vec::new(&env))
+}
+
+/// Total payouts approved on a policy, counted against its coverage
+pub fn get_policy_claimed_amount(env: Env, policy_id: BytesN<32>) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PolicyClaimedAmount(policy_id))
+        .unwrap_or(0)
+}
+
 pub fn get_policy(env: Env, policy_id: BytesN<32>) -> Option<InsurancePolicy> {
-    env.storage().persistent().get(&DataKey::Policy(policy_id))
+    env.storage()
+        .persistent()
+        .get(&DataKey::Policy(policy_id))
+        .map(|policy| with_premium_status(&env, policy))
+}
+
+/// Bill an active policy's premium on-chain in `installments` equal parts over
+/// its current term (insurer only)
+pub fn set_premium_schedule(
+    env: Env,
+    policy_id: BytesN<32>,
+    insurer: Address,
+    payment_token: Address,
+    installments: u32,
+    grace_period: u64,
+) -> Result<(), Error> {
+    let store = env.storage().persistent();
+    let policy: InsurancePolicy = store
+        .get(&DataKey::Policy(policy_id.clone()))
+        .ok_or(Error::AssetNotFound)?;
+
+    // Only insurer can set up billing
+    if insurer != policy.insurer {
+        return Err(Error::Unauthorized);
+    }
+
+    if policy.status != PolicyStatus::Active {
+        return Err(Error::Unauthorized);
+    }
+
+    // Billing is set up once; later terms roll over on renewal
+    if store.has(&DataKey::PremiumSchedule(policy_id.clone())) {
+        return Err(Error::AssetAlreadyExists);
+    }
+
+    // Every installment must have its own due date
+    let term = policy.end_date - policy.start_date;
+    if installments == 0 || term < installments as u64 {
+        return Err(Error::InvalidPayment);
+    }
+
+    let schedule = PremiumSchedule {
+        payment_token,
+        installments,
+        grace_period,
+        term_start: policy.start_date,
+        term_end: policy.end_date,
+        installments_paid: 0,
+        lapsed: false,
+    };
+    save_schedule(&env, &policy_id, &schedule);
+    index_billed_policy(&env, &policy_id);
+
+    log!(&env, "PremiumScheduleSet: {:?}", policy_id);
+    Ok(())
+}
+
+/// Pay the next `installments` premium installments of the current term to the
+/// insurer (holder only). A policy suspended for non-payment is reinstated once
+/// no installment is overdue. Returns the amount paid.
+pub fn pay_premium(
+    env: Env,
+    policy_id: BytesN<32>,
+    payer: Address,
+    installments: u32,
+) -> Result<i128, Error> {
+    let store = env.storage().persistent();
+    let key = DataKey::Policy(policy_id.clone());
+
+    let mut policy: InsurancePolicy = store.get(&key).ok_or(Error::AssetNotFound)?;
+    let mut schedule = load_schedule(&env, &policy_id).ok_or(Error::AssetNotFound)?;
+
+    // Only the holder pays premiums
+    if payer != policy.holder {
+        return Err(Error::Unauthorized);
+    }
+
+    // Validate status: cancelled or expired policies take no premium
+    if policy.status != PolicyStatus::Active && policy.status != PolicyStatus::Suspended {
+        return Err(Error::Unauthorized);
+    }
+
+    // Premiums can be prepaid up to the end of the current term
+    let first = schedule.installments_paid;
+    if installments == 0 || installments > schedule.installments - first {
+        return Err(Error::InvalidPayment);
+    }
+
+    let mut amount: i128 = 0;
+    for index in first..first + installments {
+        amount = math::add(
+            amount,
+            installment_amount(policy.premium, schedule.installments, index),
+        )?;
+    }
+    token::Client::new(&env, &schedule.payment_token).transfer(&payer, &policy.insurer, &amount);
+
+    let now = env.ledger().timestamp();
+    schedule.installments_paid += installments;
+    if schedule.lapsed && !is_overdue(&schedule, now) {
+        schedule.lapsed = false;
+        policy.status = PolicyStatus::Active;
+    }
+    save_schedule(&env, &policy_id, &schedule);
+
+    policy.last_payment = now;
+    store.set(&key, &policy);
+
+    let payments_key = DataKey::PremiumPayments(policy_id.clone());
+    let mut payments: Vec<PremiumPayment> =
+        store.get(&payments_key).unwrap_or_else(|| Vec::new(&env));
+    payments.push_back(PremiumPayment {
+        term_start: schedule.term_start,
+        first_installment: first,
+        installments,
+        amount,
+        paid_at: now,
+    });
+    store.set(&payments_key, &payments);
+
+    log!(&env, "PremiumPaid: {:?}", policy_id);
+    Ok(amount)
+}
+
+/// Bring a page of billed policies up to date (permissionless). Policies with an
+/// installment past its grace period are suspended, and `auto_renew` policies
+/// whose term has ended fully paid roll into a new term of the same length.
+/// Cancelled and expired policies leave the index, so positions can shift
+/// between calls; a policy missed by one pass is reached on the next.
+/// Returns the number of policies renewed.
+pub fn process_renewals(env: Env, start: u32, limit: u32) -> Result<u32, Error> {
+    let store = env.storage().persistent();
+    let now = env.ledger().timestamp();
+
+    let mut renewed = 0;
+    let end = start.saturating_add(limit).min(billed_count(&env));
+    let mut page = Vec::new(&env);
+    for index in start..end {
+        if index == start || index % BILLED_PAGE_SIZE == 0 {
+            page = billed_page(&env, index / BILLED_PAGE_SIZE);
+        }
+        let policy_id = page.get_unchecked(index % BILLED_PAGE_SIZE);
+        let key = DataKey::Policy(policy_id.clone());
+        let mut policy: InsurancePolicy = store.get(&key).ok_or(Error::AssetNotFound)?;
+        let mut schedule = load_schedule(&env, &policy_id).ok_or(Error::AssetNotFound)?;
+        if policy.status != PolicyStatus::Active {
+            continue;
+        }
+
+        if is_overdue(&schedule, now) {
+            policy.status = PolicyStatus::Suspended;
+            schedule.lapsed = true;
+            log!(&env, "PolicyLapsed: {:?}", policy_id);
+        } else if policy.auto_renew
+            && now >= schedule.term_end
+            && schedule.installments_paid == schedule.installments
+        {
+            let term = schedule.term_end - schedule.term_start;
+            schedule.term_start = schedule.term_end;
+            schedule.term_end = schedule
+                .term_end
+                .checked_add(term)
+                .ok_or(Error::MathOverflow)?;
+            schedule.installments_paid = 0;
+            policy.end_date = schedule.term_end;
+            renewed += 1;
+            log!(&env, "PolicyAutoRenewed: {:?}", policy_id);
+        } else {
+            continue;
+        }
+
+        save_schedule(&env, &policy_id, &schedule);
+        store.set(&key, &policy);
+    }
+
+    Ok(renewed)
+}
+
+pub fn get_billed_policy_count(env: Env) -> u32 {
+    billed_count(&env)
+}
+
+pub fn get_premium_schedule(env: Env, policy_id: BytesN<32>) -> Option<PremiumSchedule> {
+    load_schedule(&env, &policy_id)
+}
+
+/// Premium installments of the current term that have fallen due and are unpaid
+pub fn get_premium_due(env: Env, policy_id: BytesN<32>) -> Result<i128, Error> {
+    let store = env.storage().persistent();
+    let policy: InsurancePolicy = store
+        .get(&DataKey::Policy(policy_id.clone()))
+        .ok_or(Error::AssetNotFound)?;
+    let schedule = load_schedule(&env, &policy_id).ok_or(Error::AssetNotFound)?;
+
+    let now = env.ledger().timestamp();
+    let mut due: i128 = 0;
+    for index in schedule.installments_paid..schedule.installments {
+        if installment_due(&schedule, index) > now {
+            break;
+        }
+        due = math::add(
+            due,
+            installment_amount(policy.premium, schedule.installments, index),
+        )?;
+    }
+    Ok(due)
+}
+
+/// Every premium payment made on a policy, oldest first
+pub fn get_premium_payments(env: Env, policy_id: BytesN<32>) -> Vec<PremiumPayment> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PremiumPayments(policy_id))
+        .unwrap_or_else(|| Vec::new(&env))
 }
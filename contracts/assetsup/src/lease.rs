@@ -1,16 +1,148 @@
-use soroban_sdk::{contracttype, Address, BytesN, Env, Vec};
+use soroban_sdk::{contracttype, symbol_short, token, Address, BytesN, Env, Vec};
 
+use crate::asset::{self, Asset};
 use crate::error::Error;
+use crate::math::{self, Rounding};
+use crate::types::AssetStatus;
 
 // ─── Types ────────────────────────────────────────────────────────────────────
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum LeaseStatus {
+    /// Booked; becomes Active at `start_timestamp`
+    Scheduled,
     Active,
     Returned,
     Cancelled,
     Expired,
+    /// Ended early by the lessee
+    Terminated,
+}
+
+/// How rent is paid. Rent for each period is due at the period's start and
+/// becomes late once `grace_period` seconds have passed without payment.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LeasePaymentTerms {
+    /// SEP-41 token rent and deposit are paid in
+    pub payment_token: Address,
+    pub period_length: u64,
+    pub grace_period: u64,
+    /// Flat fee charged for each period paid late
+    pub late_fee: i128,
+    /// Optional property manager receiving a share of every payment
+    pub manager: Option<Address>,
+    pub management_fee_bps: u32,
+    /// Penalty the lessee pays to end the lease before `end_timestamp`
+    pub early_termination_fee: i128,
+}
+
+/// Automatic renewal: unless either party gives notice at least
+/// `notice_period` seconds before the end, the lease extends by `term`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RenewalTerms {
+    pub term: u64,
+    pub notice_period: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RenewalOption {
+    /// Lease ends at its end date
+    Manual,
+    Auto(RenewalTerms),
+}
+
+/// Changes the lessor proposes and the lessee must accept
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LeaseAmendment {
+    pub new_end_timestamp: u64,
+    pub new_rent_per_period: i128,
+    pub renewal: RenewalOption,
+    pub proposed_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LeaseChange {
+    Created,
+    Amended,
+    Renewed,
+    RenewalCancelled,
+    Terminated,
+    /// The asset changed hands and the new owner took over as lessor
+    LessorTransferred,
+    /// Assigned to a new lessee with the lessor's consent
+    Assigned,
+    /// Ended because the lease it sublets from ended
+    ParentEnded,
+}
+
+/// Snapshot of a lease's terms after each change
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LeaseVersion {
+    pub version: u32,
+    pub change: LeaseChange,
+    pub start_timestamp: u64,
+    pub end_timestamp: u64,
+    pub rent_per_period: i128,
+    pub renewal: RenewalOption,
+    pub recorded_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SettlementStatus {
+    /// Deductions proposed; the lessee may accept or dispute until `respond_by`
+    Proposed,
+    /// Lessee disputed; awaiting admin resolution
+    Disputed,
+    /// Deposit paid out
+    Settled,
+}
+
+/// Post-return inspection of a leased asset and the deposit split it proposes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositSettlement {
+    pub lease_id: BytesN<32>,
+    /// Hash of the lessor's condition report
+    pub report_hash: BytesN<32>,
+    /// Hash of a linked asset-maintenance record, if the damage was repaired
+    pub maintenance_record_hash: Option<BytesN<32>>,
+    pub proposed_deduction: i128,
+    /// Amount finally paid to the lessor, once settled
+    pub final_deduction: i128,
+    pub filed_at: u64,
+    pub respond_by: u64,
+    pub status: SettlementStatus,
+}
+
+/// A lease's slot in its asset's calendar, covering `[start, end)`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Booking {
+    pub lease_id: BytesN<32>,
+    pub start: u64,
+    pub end: u64,
+}
+
+/// Snapshot of a lessor's current leases
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LessorPortfolio {
+    pub total_leases: u32,
+    pub scheduled_leases: u32,
+    pub active_leases: u32,
+    /// Rent on live leases for periods falling due within the queried window
+    pub expected_rent: i128,
+    /// Live leases with rent past its grace period or late fees unpaid
+    pub overdue_leases: u32,
+    pub arrears: i128,
 }
 
 #[contracttype]
@@ -18,6 +150,8 @@ pub enum LeaseStatus {
 pub struct Lease {
     pub lease_id: BytesN<32>,
     pub asset_id: BytesN<32>,
+    /// Lease this one sublets from; the lessor is the parent's lessee
+    pub parent: Option<BytesN<32>>,
     pub lessor: Address,
     pub lessee: Address,
     pub start_timestamp: u64,
@@ -25,6 +159,22 @@ pub struct Lease {
     pub rent_per_period: i128,
     pub deposit: i128,
     pub status: LeaseStatus,
+    pub terms: LeasePaymentTerms,
+    /// Whole periods of rent paid so far, from the start of the lease
+    pub periods_paid: u32,
+    /// Periods that went past their grace period unpaid
+    pub missed_payments: u32,
+    /// Late fees assessed but not yet paid
+    pub late_fees_owed: i128,
+    /// Periods already checked for lateness
+    pub late_periods_assessed: u32,
+    /// Deposit held in contract escrow
+    pub deposit_held: i128,
+    /// When the lease was returned, expired or terminated; 0 while active
+    pub ended_at: u64,
+    pub renewal: RenewalOption,
+    /// Current entry in the lease's version history
+    pub version: u32,
 }
 
 // ─── Storage Keys ─────────────────────────────────────────────────────────────
@@ -32,17 +182,45 @@ pub struct Lease {
 #[contracttype]
 pub enum DataKey {
     Lease(BytesN<32>),
-    AssetActiveLease(BytesN<32>),
+    /// Vec<Booking> of live leases on an asset, ordered by start
+    AssetCalendar(BytesN<32>),
     LesseeLeases(Address),
+    /// Scheduled and active leases the address is lessor of, oldest first;
+    /// leases leave it when they end
+    LessorLeases(Address),
+    /// Every lease ever created on an asset, oldest first
+    AssetLeases(BytesN<32>),
+    /// Every sublease created under a lease, oldest first
+    Subleases(BytesN<32>),
+    /// Vec<Booking> of live subleases under a lease, ordered by start
+    SubleaseCalendar(BytesN<32>),
+    /// New lessee the lessor has consented to assign a lease to
+    AssignmentConsent(BytesN<32>),
+    DepositSettlement(BytesN<32>),
+    PendingAmendment(BytesN<32>),
+    LeaseVersions(BytesN<32>),
+    /// Owner who delegated leasing of an asset to a manager
+    LeaseManager(BytesN<32>, Address),
 }
 
+/// Time the lessor has after the lease ends to file a condition report
+pub const INSPECTION_PERIOD: u64 = 14 * 86400;
+/// Time the lessee has to accept or dispute proposed deductions
+pub const RESPONSE_PERIOD: u64 = 7 * 86400;
+
 // ─── Internal helpers ─────────────────────────────────────────────────────────
 
+/// Load a lease, promoting it to Active once its start time has arrived
 fn load_lease(env: &Env, lease_id: &BytesN<32>) -> Result<Lease, Error> {
-    env.storage()
+    let mut lease: Lease = env
+        .storage()
         .persistent()
         .get(&DataKey::Lease(lease_id.clone()))
-        .ok_or(Error::LeaseNotFound)
+        .ok_or(Error::LeaseNotFound)?;
+    if lease.status == LeaseStatus::Scheduled && env.ledger().timestamp() >= lease.start_timestamp {
+        lease.status = LeaseStatus::Active;
+    }
+    Ok(lease)
 }
 
 fn save_lease(env: &Env, lease: &Lease) {
@@ -51,35 +229,428 @@ fn save_lease(env: &Env, lease: &Lease) {
         .set(&DataKey::Lease(lease.lease_id.clone()), lease);
 }
 
-fn set_asset_active_lease(env: &Env, asset_id: &BytesN<32>, lease_id: &BytesN<32>) {
+/// Booked or running, and so holding a place in the calendar
+fn is_live(lease: &Lease) -> bool {
+    matches!(lease.status, LeaseStatus::Scheduled | LeaseStatus::Active)
+}
+
+/// Calendar a lease is booked in: its asset's, or its parent's for a sublease
+fn calendar_key(asset_id: &BytesN<32>, parent: &Option<BytesN<32>>) -> DataKey {
+    match parent {
+        Some(parent) => DataKey::SubleaseCalendar(parent.clone()),
+        None => DataKey::AssetCalendar(asset_id.clone()),
+    }
+}
+
+fn lease_calendar(lease: &Lease) -> DataKey {
+    calendar_key(&lease.asset_id, &lease.parent)
+}
+
+fn load_calendar(env: &Env, key: &DataKey) -> Vec<Booking> {
     env.storage()
         .persistent()
-        .set(&DataKey::AssetActiveLease(asset_id.clone()), lease_id);
+        .get(key)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn save_calendar(env: &Env, key: &DataKey, calendar: &Vec<Booking>) {
+    if calendar.is_empty() {
+        env.storage().persistent().remove(key);
+    } else {
+        env.storage().persistent().set(key, calendar);
+    }
+}
+
+/// Insert or move a lease's booking, keeping the calendar ordered by start.
+/// Fails if `[start, end)` overlaps any other booking.
+fn book(
+    env: &Env,
+    key: &DataKey,
+    lease_id: &BytesN<32>,
+    start: u64,
+    end: u64,
+) -> Result<(), Error> {
+    let calendar = load_calendar(env, key);
+    let mut updated = Vec::new(env);
+    let mut inserted = false;
+    for booking in calendar.iter() {
+        if booking.lease_id == *lease_id {
+            continue;
+        }
+        if booking.start < end && start < booking.end {
+            return Err(Error::AssetAlreadyLeased);
+        }
+        if !inserted && start < booking.start {
+            updated.push_back(Booking {
+                lease_id: lease_id.clone(),
+                start,
+                end,
+            });
+            inserted = true;
+        }
+        updated.push_back(booking);
+    }
+    if !inserted {
+        updated.push_back(Booking {
+            lease_id: lease_id.clone(),
+            start,
+            end,
+        });
+    }
+    save_calendar(env, key, &updated);
+    Ok(())
+}
+
+/// Free a lease's slot in the calendar
+fn unbook(env: &Env, key: &DataKey, lease_id: &BytesN<32>) {
+    let calendar = load_calendar(env, key);
+    let mut updated = Vec::new(env);
+    for booking in calendar.iter() {
+        if booking.lease_id != *lease_id {
+            updated.push_back(booking);
+        }
+    }
+    save_calendar(env, key, &updated);
 }
 
-fn clear_asset_active_lease(env: &Env, asset_id: &BytesN<32>) {
+fn load_asset(env: &Env, asset_id: &BytesN<32>) -> Result<Asset, Error> {
     env.storage()
         .persistent()
-        .remove(&DataKey::AssetActiveLease(asset_id.clone()));
+        .get(&asset::DataKey::Asset(asset_id.clone()))
+        .ok_or(Error::AssetNotFound)
 }
 
-fn get_active_lease_id(env: &Env, asset_id: &BytesN<32>) -> Option<BytesN<32>> {
+/// Whether `manager` holds a leasing delegation from the asset's current owner
+fn is_lease_manager(env: &Env, asset: &Asset, manager: &Address) -> bool {
     env.storage()
         .persistent()
-        .get(&DataKey::AssetActiveLease(asset_id.clone()))
+        .get::<_, Address>(&DataKey::LeaseManager(asset.id.clone(), manager.clone()))
+        .is_some_and(|delegator| delegator == asset.owner)
 }
 
-fn append_lessee_lease(env: &Env, lessee: &Address, lease_id: &BytesN<32>) {
-    let key = DataKey::LesseeLeases(lessee.clone());
-    let mut ids: Vec<BytesN<32>> = env
+/// The asset must be registered and not retired, and the lessor must be its
+/// owner or a manager the owner delegated leasing to. Assets whose tokens
+/// were detokenized cannot be leased either.
+fn validate_lessor(env: &Env, asset_id: &BytesN<32>, lessor: &Address) -> Result<(), Error> {
+    let asset = load_asset(env, asset_id)?;
+    let detokenized = env
         .storage()
         .persistent()
-        .get(&key)
-        .unwrap_or_else(|| Vec::new(env));
+        .has(&asset::DataKey::Detokenized(asset_id.clone()));
+    if asset.status == AssetStatus::Retired || detokenized {
+        return Err(Error::InvalidLeaseStatus);
+    }
+    if asset.owner != *lessor && !is_lease_manager(env, &asset, lessor) {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+/// A sublease must fall within its live parent's term and cannot auto-renew
+fn validate_sublease_term(
+    env: &Env,
+    parent_id: &BytesN<32>,
+    start: u64,
+    end: u64,
+    renewal: &RenewalOption,
+) -> Result<(), Error> {
+    let parent = load_lease(env, parent_id)?;
+    if !is_live(&parent) || *renewal != RenewalOption::Manual {
+        return Err(Error::InvalidLeaseStatus);
+    }
+    if start < parent.start_timestamp || end > parent.end_timestamp {
+        return Err(Error::InvalidTimestamps);
+    }
+    Ok(())
+}
+
+/// A lease cannot be shortened below the end of its live subleases
+fn validate_covers_subleases(env: &Env, lease_id: &BytesN<32>, end: u64) -> Result<(), Error> {
+    let subleases = load_calendar(env, &DataKey::SubleaseCalendar(lease_id.clone()));
+    if subleases.iter().any(|booking| booking.end > end) {
+        return Err(Error::InvalidTimestamps);
+    }
+    Ok(())
+}
+
+/// Take a lease that has just ended off its calendar and its lessor's index,
+/// then end its subleases
+fn close_lease(env: &Env, lease: &Lease) -> Result<(), Error> {
+    unbook(env, &lease_calendar(lease), &lease.lease_id);
+    remove_from_index(
+        env,
+        DataKey::LessorLeases(lease.lessor.clone()),
+        &lease.lease_id,
+    );
+    end_subleases(env, &lease.lease_id, &lease.status)
+}
+
+/// End every live sublease of a lease that has just ended, and theirs in turn.
+/// Cancelled subleases refund their deposit; otherwise the term is cut short
+/// at the parent's end and the deposit awaits the sublessor's inspection.
+fn end_subleases(env: &Env, parent_id: &BytesN<32>, status: &LeaseStatus) -> Result<(), Error> {
+    let key = DataKey::SubleaseCalendar(parent_id.clone());
+    let now = env.ledger().timestamp();
+    for booking in load_calendar(env, &key).iter() {
+        let mut sublease = load_lease(env, &booking.lease_id)?;
+        if *status == LeaseStatus::Cancelled {
+            release_deposit(env, &mut sublease, 0)?;
+        } else {
+            sublease.ended_at = now;
+            sublease.end_timestamp = sublease
+                .end_timestamp
+                .min(now.max(sublease.start_timestamp));
+        }
+        sublease.status = status.clone();
+        record_version(env, &mut sublease, LeaseChange::ParentEnded);
+        save_lease(env, &sublease);
+        remove_from_index(
+            env,
+            DataKey::LessorLeases(sublease.lessor.clone()),
+            &booking.lease_id,
+        );
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingAmendment(booking.lease_id.clone()));
+        end_subleases(env, &booking.lease_id, status)?;
+
+        env.events().publish(
+            (symbol_short!("sub_end"),),
+            (booking.lease_id, parent_id.clone(), now),
+        );
+    }
+    save_calendar(env, &key, &Vec::new(env));
+    Ok(())
+}
+
+fn load_index(env: &Env, key: &DataKey) -> Vec<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(key)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn append_to_index(env: &Env, key: DataKey, lease_id: &BytesN<32>) {
+    let mut ids = load_index(env, &key);
     ids.push_back(lease_id.clone());
     env.storage().persistent().set(&key, &ids);
 }
 
+fn remove_from_index(env: &Env, key: DataKey, lease_id: &BytesN<32>) {
+    let mut ids = load_index(env, &key);
+    if let Some(index) = ids.iter().position(|id| id == *lease_id) {
+        ids.remove(index as u32);
+        env.storage().persistent().set(&key, &ids);
+    }
+}
+
+/// Page through an index, keeping only leases in `status` when given
+fn page_leases(
+    env: &Env,
+    key: DataKey,
+    status: Option<LeaseStatus>,
+    start: u32,
+    limit: u32,
+) -> Result<Vec<Lease>, Error> {
+    let mut leases = Vec::new(env);
+    let mut skipped = 0;
+    for id in load_index(env, &key).iter() {
+        if leases.len() >= limit {
+            break;
+        }
+        let lease = load_lease(env, &id)?;
+        if status
+            .as_ref()
+            .is_some_and(|status| *status != lease.status)
+        {
+            continue;
+        }
+        if skipped < start {
+            skipped += 1;
+            continue;
+        }
+        leases.push_back(lease);
+    }
+    Ok(leases)
+}
+
+/// Whether the lease has ended and its deposit awaits inspection
+fn has_ended(lease: &Lease) -> bool {
+    matches!(
+        lease.status,
+        LeaseStatus::Returned | LeaseStatus::Expired | LeaseStatus::Terminated
+    )
+}
+
+/// Bump the lease version and append a snapshot of its terms to the history
+fn record_version(env: &Env, lease: &mut Lease, change: LeaseChange) {
+    lease.version += 1;
+    let key = DataKey::LeaseVersions(lease.lease_id.clone());
+    let mut versions: Vec<LeaseVersion> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env));
+    versions.push_back(LeaseVersion {
+        version: lease.version,
+        change,
+        start_timestamp: lease.start_timestamp,
+        end_timestamp: lease.end_timestamp,
+        rent_per_period: lease.rent_per_period,
+        renewal: lease.renewal.clone(),
+        recorded_at: env.ledger().timestamp(),
+    });
+    env.storage().persistent().set(&key, &versions);
+}
+
+fn validate_renewal(renewal: &RenewalOption) -> Result<(), Error> {
+    match renewal {
+        RenewalOption::Auto(terms) if terms.term == 0 => Err(Error::InvalidTimestamps),
+        _ => Ok(()),
+    }
+}
+
+fn renewal_terms(lease: &Lease) -> Result<RenewalTerms, Error> {
+    match &lease.renewal {
+        RenewalOption::Auto(terms) => Ok(terms.clone()),
+        RenewalOption::Manual => Err(Error::InvalidLeaseStatus),
+    }
+}
+
+/// Number of rent periods in the lease term; a partial final period counts in full
+fn total_periods(lease: &Lease) -> u32 {
+    let duration = lease.end_timestamp - lease.start_timestamp;
+    duration.div_ceil(lease.terms.period_length) as u32
+}
+
+/// Periods whose due date (period start) is at or before `at`
+fn periods_started(lease: &Lease, at: u64) -> u32 {
+    if at < lease.start_timestamp {
+        return 0;
+    }
+    let started = (at - lease.start_timestamp) / lease.terms.period_length + 1;
+    started.min(total_periods(lease) as u64) as u32
+}
+
+/// Periods whose grace period has ended by `at`
+fn periods_late(lease: &Lease, at: u64) -> u32 {
+    let cutoff = lease
+        .start_timestamp
+        .saturating_add(lease.terms.grace_period);
+    if at <= cutoff {
+        return 0;
+    }
+    let late = (at - cutoff - 1) / lease.terms.period_length + 1;
+    late.min(total_periods(lease) as u64) as u32
+}
+
+/// Periods whose due date falls within `[from, to)`
+fn periods_due_between(lease: &Lease, from: u64, to: u64) -> u32 {
+    let before = match from.checked_sub(1) {
+        Some(at) => periods_started(lease, at),
+        None => 0,
+    };
+    periods_started(lease, to - 1).saturating_sub(before)
+}
+
+/// Overdue rent and late fees as of `at`, assessing any newly late periods
+fn arrears(lease: &mut Lease, at: u64) -> Result<i128, Error> {
+    assess_late_periods(lease, at)?;
+    let overdue = periods_late(lease, at).saturating_sub(lease.periods_paid);
+    math::add(
+        math::mul(overdue as i128, lease.rent_per_period)?,
+        lease.late_fees_owed,
+    )
+}
+
+/// Charge late fees for periods that became late since the last assessment.
+/// Returns the number of newly missed payments.
+fn assess_late_periods(lease: &mut Lease, at: u64) -> Result<u32, Error> {
+    let late = periods_late(lease, at);
+    let from = lease.periods_paid.max(lease.late_periods_assessed);
+    if late <= from {
+        return Ok(0);
+    }
+    let missed = late - from;
+    lease.missed_payments += missed;
+    lease.late_fees_owed = math::add(
+        lease.late_fees_owed,
+        math::mul(missed as i128, lease.terms.late_fee)?,
+    )?;
+    lease.late_periods_assessed = late;
+    Ok(missed)
+}
+
+/// Pay `amount` of `payment_token` from `from` to the lessor, less the manager's share
+fn pay_lessor(env: &Env, lease: &Lease, from: &Address, amount: i128) -> Result<(), Error> {
+    let payment = token::Client::new(env, &lease.terms.payment_token);
+    let mut lessor_share = amount;
+    if let Some(manager) = &lease.terms.manager {
+        let management = math::apply_bps(
+            amount,
+            lease.terms.management_fee_bps as i128,
+            Rounding::Down,
+        )?;
+        if management > 0 {
+            payment.transfer(from, manager, &management);
+            lessor_share = math::sub(amount, management)?;
+        }
+    }
+    if lessor_share > 0 {
+        payment.transfer(from, &lease.lessor, &lessor_share);
+    }
+    Ok(())
+}
+
+/// Pay `deduction` of the escrowed deposit to the lessor and the rest to the lessee
+fn release_deposit(env: &Env, lease: &mut Lease, deduction: i128) -> Result<(), Error> {
+    let payment = token::Client::new(env, &lease.terms.payment_token);
+    let escrow = env.current_contract_address();
+    let refund = math::sub(lease.deposit_held, deduction)?;
+    if deduction > 0 {
+        payment.transfer(&escrow, &lease.lessor, &deduction);
+    }
+    if refund > 0 {
+        payment.transfer(&escrow, &lease.lessee, &refund);
+    }
+    lease.deposit_held = 0;
+    Ok(())
+}
+
+fn load_settlement(env: &Env, lease_id: &BytesN<32>) -> Option<DepositSettlement> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DepositSettlement(lease_id.clone()))
+}
+
+fn save_settlement(env: &Env, settlement: &DepositSettlement) {
+    env.storage().persistent().set(
+        &DataKey::DepositSettlement(settlement.lease_id.clone()),
+        settlement,
+    );
+}
+
+/// Pay out the deposit and close the settlement
+fn finish_settlement(
+    env: &Env,
+    lease: &mut Lease,
+    settlement: &mut DepositSettlement,
+    deduction: i128,
+) -> Result<(), Error> {
+    release_deposit(env, lease, deduction)?;
+    settlement.final_deduction = deduction;
+    settlement.status = SettlementStatus::Settled;
+    save_lease(env, lease);
+    save_settlement(env, settlement);
+
+    env.events().publish(
+        (symbol_short!("dep_settl"),),
+        (lease.lease_id.clone(), deduction, env.ledger().timestamp()),
+    );
+
+    Ok(())
+}
+
 // ─── Public functions (called from lib.rs) ────────────────────────────────────
 
 pub fn create_lease(
@@ -92,10 +663,76 @@ pub fn create_lease(
     end: u64,
     rent: i128,
     deposit: i128,
+    terms: LeasePaymentTerms,
+    renewal: RenewalOption,
+) -> Result<(), Error> {
+    open_lease(
+        env, asset_id, None, lease_id, lessor, lessee, start, end, rent, deposit, terms, renewal,
+    )
+}
+
+/// Sublet part or all of a live lease's term. Callable by the lease's lessee,
+/// who becomes the sublease's lessor. Subleases do not auto-renew and end
+/// with their parent.
+pub fn create_sublease(
+    env: &Env,
+    parent_id: BytesN<32>,
+    sublease_id: BytesN<32>,
+    caller: Address,
+    sublessee: Address,
+    start: u64,
+    end: u64,
+    rent: i128,
+    deposit: i128,
+    terms: LeasePaymentTerms,
+) -> Result<(), Error> {
+    let parent = load_lease(env, &parent_id)?;
+    if caller != parent.lessee {
+        return Err(Error::Unauthorized);
+    }
+
+    open_lease(
+        env,
+        parent.asset_id,
+        Some(parent_id),
+        sublease_id,
+        caller,
+        sublessee,
+        start,
+        end,
+        rent,
+        deposit,
+        terms,
+        RenewalOption::Manual,
+    )
+}
+
+fn open_lease(
+    env: &Env,
+    asset_id: BytesN<32>,
+    parent: Option<BytesN<32>>,
+    lease_id: BytesN<32>,
+    lessor: Address,
+    lessee: Address,
+    start: u64,
+    end: u64,
+    rent: i128,
+    deposit: i128,
+    terms: LeasePaymentTerms,
+    renewal: RenewalOption,
 ) -> Result<(), Error> {
-    if end <= start {
+    if end <= start || terms.period_length == 0 {
         return Err(Error::InvalidTimestamps);
     }
+    validate_renewal(&renewal)?;
+    if rent < 0
+        || deposit < 0
+        || terms.late_fee < 0
+        || terms.early_termination_fee < 0
+        || terms.management_fee_bps as i128 > math::BPS_DENOMINATOR
+    {
+        return Err(Error::InvalidPayment);
+    }
 
     if env
         .storage()
@@ -104,33 +741,68 @@ pub fn create_lease(
     {
         return Err(Error::LeaseAlreadyExists);
     }
-
-    // Asset must not already have an Active lease
-    if let Some(existing_id) = get_active_lease_id(env, &asset_id) {
-        let existing = load_lease(env, &existing_id)?;
-        if existing.status == LeaseStatus::Active {
-            return Err(Error::AssetAlreadyLeased);
-        }
+    match &parent {
+        Some(parent_id) => validate_sublease_term(env, parent_id, start, end, &renewal)?,
+        None => validate_lessor(env, &asset_id, &lessor)?,
     }
 
-    let lease = Lease {
+    // The term must not overlap another booking on the asset, or on the
+    // parent lease for a sublease
+    book(
+        env,
+        &calendar_key(&asset_id, &parent),
+        &lease_id,
+        start,
+        end,
+    )?;
+
+    let status = if env.ledger().timestamp() >= start {
+        LeaseStatus::Active
+    } else {
+        LeaseStatus::Scheduled
+    };
+    let mut lease = Lease {
         lease_id: lease_id.clone(),
         asset_id: asset_id.clone(),
+        parent: parent.clone(),
         lessor: lessor.clone(),
         lessee: lessee.clone(),
         start_timestamp: start,
         end_timestamp: end,
         rent_per_period: rent,
         deposit,
-        status: LeaseStatus::Active,
+        status,
+        terms,
+        periods_paid: 0,
+        missed_payments: 0,
+        late_fees_owed: 0,
+        late_periods_assessed: 0,
+        deposit_held: deposit,
+        ended_at: 0,
+        renewal,
+        version: 0,
     };
+    record_version(env, &mut lease, LeaseChange::Created);
+
+    // Deposit is escrowed until the lease ends
+    if deposit > 0 {
+        token::Client::new(env, &lease.terms.payment_token).transfer(
+            &lessee,
+            &env.current_contract_address(),
+            &deposit,
+        );
+    }
 
     save_lease(env, &lease);
-    set_asset_active_lease(env, &asset_id, &lease_id);
-    append_lessee_lease(env, &lessee, &lease_id);
+    append_to_index(env, DataKey::LesseeLeases(lessee.clone()), &lease_id);
+    append_to_index(env, DataKey::LessorLeases(lessor.clone()), &lease_id);
+    append_to_index(env, DataKey::AssetLeases(asset_id.clone()), &lease_id);
+    if let Some(parent_id) = &parent {
+        append_to_index(env, DataKey::Subleases(parent_id.clone()), &lease_id);
+    }
 
     env.events().publish(
-        (soroban_sdk::symbol_short!("lease_new"),),
+        (symbol_short!("lease_new"),),
         (lease_id, asset_id, lessor, lessee, env.ledger().timestamp()),
     );
 
@@ -148,12 +820,20 @@ pub fn return_leased_asset(env: &Env, lease_id: BytesN<32>, caller: Address) ->
         return Err(Error::InvalidLeaseStatus);
     }
 
+    // The lessee settles arrears before handing the asset back
+    let now = env.ledger().timestamp();
+    if caller == lease.lessee && arrears(&mut lease, now)? > 0 {
+        return Err(Error::InvalidPayment);
+    }
+
+    // The deposit stays in escrow until the return inspection settles
     lease.status = LeaseStatus::Returned;
+    lease.ended_at = now;
     save_lease(env, &lease);
-    clear_asset_active_lease(env, &lease.asset_id);
+    close_lease(env, &lease)?;
 
     env.events().publish(
-        (soroban_sdk::symbol_short!("lease_ret"),),
+        (symbol_short!("lease_ret"),),
         (lease_id, caller, env.ledger().timestamp()),
     );
 
@@ -167,7 +847,7 @@ pub fn cancel_lease(env: &Env, lease_id: BytesN<32>, caller: Address) -> Result<
         return Err(Error::Unauthorized);
     }
 
-    if lease.status != LeaseStatus::Active {
+    if !is_live(&lease) {
         return Err(Error::InvalidLeaseStatus);
     }
 
@@ -176,11 +856,12 @@ pub fn cancel_lease(env: &Env, lease_id: BytesN<32>, caller: Address) -> Result<
     }
 
     lease.status = LeaseStatus::Cancelled;
+    release_deposit(env, &mut lease, 0)?;
     save_lease(env, &lease);
-    clear_asset_active_lease(env, &lease.asset_id);
+    close_lease(env, &lease)?;
 
     env.events().publish(
-        (soroban_sdk::symbol_short!("lease_can"),),
+        (symbol_short!("lease_can"),),
         (lease_id, caller, env.ledger().timestamp()),
     );
 
@@ -190,7 +871,7 @@ pub fn cancel_lease(env: &Env, lease_id: BytesN<32>, caller: Address) -> Result<
 pub fn expire_lease(env: &Env, lease_id: BytesN<32>) -> Result<(), Error> {
     let mut lease = load_lease(env, &lease_id)?;
 
-    if lease.status != LeaseStatus::Active {
+    if !is_live(&lease) {
         return Err(Error::InvalidLeaseStatus);
     }
 
@@ -198,29 +879,845 @@ pub fn expire_lease(env: &Env, lease_id: BytesN<32>) -> Result<(), Error> {
         return Err(Error::LeaseNotExpired);
     }
 
+    // Auto-renewing leases without notice renew instead of expiring
+    if lease.renewal != RenewalOption::Manual {
+        return Err(Error::InvalidLeaseStatus);
+    }
+
     lease.status = LeaseStatus::Expired;
+    lease.ended_at = env.ledger().timestamp();
     save_lease(env, &lease);
-    clear_asset_active_lease(env, &lease.asset_id);
+    close_lease(env, &lease)?;
 
     env.events().publish(
-        (soroban_sdk::symbol_short!("lease_exp"),),
+        (symbol_short!("lease_exp"),),
         (lease_id, env.ledger().timestamp()),
     );
 
     Ok(())
 }
 
-pub fn get_lease(env: &Env, lease_id: BytesN<32>) -> Result<Lease, Error> {
-    load_lease(env, &lease_id)
+/// Pay any late fees owed plus `periods` whole periods of rent. Rent can be
+/// prepaid up to the end of the lease; once the lease has ended only periods
+/// already due can be paid. Returns the amount paid.
+pub fn pay_rent(
+    env: &Env,
+    lease_id: BytesN<32>,
+    payer: Address,
+    periods: u32,
+) -> Result<i128, Error> {
+    let mut lease = load_lease(env, &lease_id)?;
+
+    if payer != lease.lessee {
+        return Err(Error::Unauthorized);
+    }
+
+    let now = env.ledger().timestamp();
+    let payable = if is_live(&lease) {
+        total_periods(&lease)
+    } else if has_ended(&lease) {
+        periods_started(&lease, now)
+    } else {
+        return Err(Error::InvalidLeaseStatus);
+    };
+
+    let periods_paid = lease
+        .periods_paid
+        .checked_add(periods)
+        .ok_or(Error::InvalidPayment)?;
+    if periods_paid > payable {
+        return Err(Error::InvalidPayment);
+    }
+
+    assess_late_periods(&mut lease, now)?;
+
+    let rent = math::mul(periods as i128, lease.rent_per_period)?;
+    let amount = math::add(rent, lease.late_fees_owed)?;
+    if amount <= 0 && periods == 0 {
+        return Err(Error::InvalidPayment);
+    }
+
+    pay_lessor(env, &lease, &payer, amount)?;
+    let late_fees = lease.late_fees_owed;
+    lease.late_fees_owed = 0;
+    lease.periods_paid = periods_paid;
+    save_lease(env, &lease);
+
+    env.events().publish(
+        (symbol_short!("rent_paid"),),
+        (lease_id, payer, periods, rent, late_fees, now),
+    );
+
+    Ok(amount)
 }
 
-pub fn get_asset_active_lease(env: &Env, asset_id: BytesN<32>) -> Option<Lease> {
-    get_active_lease_id(env, &asset_id).and_then(|id| load_lease(env, &id).ok())
+/// Record missed payments and charge late fees up to now. Permissionless.
+/// Returns the number of newly missed payments.
+pub fn assess_late_fees(env: &Env, lease_id: BytesN<32>) -> Result<u32, Error> {
+    let mut lease = load_lease(env, &lease_id)?;
+
+    if !is_live(&lease) {
+        return Err(Error::InvalidLeaseStatus);
+    }
+
+    let now = env.ledger().timestamp();
+    let missed = assess_late_periods(&mut lease, now)?;
+    if missed > 0 {
+        save_lease(env, &lease);
+        env.events().publish(
+            (symbol_short!("rent_late"),),
+            (lease_id, missed, lease.late_fees_owed, now),
+        );
+    }
+
+    Ok(missed)
 }
 
-pub fn get_lessee_leases(env: &Env, lessee: Address) -> Vec<BytesN<32>> {
-    env.storage()
+/// Rent for every period started but not yet paid, plus late fees owed
+pub fn get_rent_due(env: &Env, lease_id: BytesN<32>) -> Result<i128, Error> {
+    let mut lease = load_lease(env, &lease_id)?;
+    let now = env.ledger().timestamp();
+    assess_late_periods(&mut lease, now)?;
+    let unpaid = periods_started(&lease, now).saturating_sub(lease.periods_paid);
+    math::add(
+        math::mul(unpaid as i128, lease.rent_per_period)?,
+        lease.late_fees_owed,
+    )
+}
+
+/// Rent for periods past their grace period and still unpaid, plus late fees owed
+pub fn get_rent_arrears(env: &Env, lease_id: BytesN<32>) -> Result<i128, Error> {
+    let mut lease = load_lease(env, &lease_id)?;
+    arrears(&mut lease, env.ledger().timestamp())
+}
+
+/// Timestamp up to which rent has been paid
+pub fn get_paid_through(env: &Env, lease_id: BytesN<32>) -> Result<u64, Error> {
+    let lease = load_lease(env, &lease_id)?;
+    let paid = (lease.periods_paid as u64).saturating_mul(lease.terms.period_length);
+    Ok(lease
+        .start_timestamp
+        .saturating_add(paid)
+        .min(lease.end_timestamp))
+}
+
+/// File the lessor's condition report for a returned or expired lease and
+/// propose a deduction from the deposit.
+pub fn file_condition_report(
+    env: &Env,
+    lease_id: BytesN<32>,
+    caller: Address,
+    report_hash: BytesN<32>,
+    maintenance_record_hash: Option<BytesN<32>>,
+    deduction: i128,
+) -> Result<(), Error> {
+    let lease = load_lease(env, &lease_id)?;
+
+    if caller != lease.lessor {
+        return Err(Error::Unauthorized);
+    }
+
+    if !has_ended(&lease) {
+        return Err(Error::InvalidLeaseStatus);
+    }
+
+    if lease.deposit_held <= 0 || load_settlement(env, &lease_id).is_some() {
+        return Err(Error::InvalidLeaseStatus);
+    }
+
+    let now = env.ledger().timestamp();
+    if now > lease.ended_at.saturating_add(INSPECTION_PERIOD) {
+        return Err(Error::InvalidTimestamps);
+    }
+
+    if deduction < 0 || deduction > lease.deposit_held {
+        return Err(Error::InvalidPayment);
+    }
+
+    save_settlement(
+        env,
+        &DepositSettlement {
+            lease_id: lease_id.clone(),
+            report_hash: report_hash.clone(),
+            maintenance_record_hash,
+            proposed_deduction: deduction,
+            final_deduction: 0,
+            filed_at: now,
+            respond_by: now.saturating_add(RESPONSE_PERIOD),
+            status: SettlementStatus::Proposed,
+        },
+    );
+
+    env.events().publish(
+        (symbol_short!("dep_claim"),),
+        (lease_id, report_hash, deduction, now),
+    );
+
+    Ok(())
+}
+
+/// Lessee accepts the proposed deductions, settling immediately, or disputes them.
+pub fn respond_to_deductions(
+    env: &Env,
+    lease_id: BytesN<32>,
+    caller: Address,
+    accept: bool,
+) -> Result<(), Error> {
+    let mut lease = load_lease(env, &lease_id)?;
+
+    if caller != lease.lessee {
+        return Err(Error::Unauthorized);
+    }
+
+    let mut settlement = load_settlement(env, &lease_id).ok_or(Error::InvalidLeaseStatus)?;
+    if settlement.status != SettlementStatus::Proposed {
+        return Err(Error::InvalidLeaseStatus);
+    }
+
+    if env.ledger().timestamp() > settlement.respond_by {
+        return Err(Error::InvalidTimestamps);
+    }
+
+    if accept {
+        let deduction = settlement.proposed_deduction;
+        return finish_settlement(env, &mut lease, &mut settlement, deduction);
+    }
+
+    settlement.status = SettlementStatus::Disputed;
+    save_settlement(env, &settlement);
+
+    env.events().publish(
+        (symbol_short!("dep_disp"),),
+        (lease_id, caller, env.ledger().timestamp()),
+    );
+
+    Ok(())
+}
+
+/// Settle a deposit once nobody is left to act. Permissionless.
+/// Unanswered proposals settle at the proposed deduction; if the lessor never
+/// filed a report the full deposit is refunded.
+pub fn settle_deposit(env: &Env, lease_id: BytesN<32>) -> Result<(), Error> {
+    let mut lease = load_lease(env, &lease_id)?;
+
+    if !has_ended(&lease) {
+        return Err(Error::InvalidLeaseStatus);
+    }
+
+    let now = env.ledger().timestamp();
+    match load_settlement(env, &lease_id) {
+        Some(mut settlement) => {
+            if settlement.status != SettlementStatus::Proposed {
+                return Err(Error::InvalidLeaseStatus);
+            }
+            if now <= settlement.respond_by {
+                return Err(Error::InvalidTimestamps);
+            }
+            let deduction = settlement.proposed_deduction;
+            finish_settlement(env, &mut lease, &mut settlement, deduction)
+        }
+        None => {
+            if lease.deposit_held <= 0 {
+                return Err(Error::InvalidLeaseStatus);
+            }
+            if now <= lease.ended_at.saturating_add(INSPECTION_PERIOD) {
+                return Err(Error::InvalidTimestamps);
+            }
+            release_deposit(env, &mut lease, 0)?;
+            save_lease(env, &lease);
+
+            env.events()
+                .publish((symbol_short!("dep_settl"),), (lease_id, 0i128, now));
+
+            Ok(())
+        }
+    }
+}
+
+/// Decide a disputed deduction. Admin auth is checked in lib.rs.
+pub fn resolve_deposit_dispute(
+    env: &Env,
+    lease_id: BytesN<32>,
+    deduction: i128,
+) -> Result<(), Error> {
+    let mut lease = load_lease(env, &lease_id)?;
+
+    let mut settlement = load_settlement(env, &lease_id).ok_or(Error::InvalidLeaseStatus)?;
+    if settlement.status != SettlementStatus::Disputed {
+        return Err(Error::InvalidLeaseStatus);
+    }
+
+    if deduction < 0 || deduction > lease.deposit_held {
+        return Err(Error::InvalidPayment);
+    }
+
+    finish_settlement(env, &mut lease, &mut settlement, deduction)
+}
+
+/// Propose a new end date, rent or renewal option. Lessor only; replaces any
+/// pending proposal.
+pub fn propose_lease_amendment(
+    env: &Env,
+    lease_id: BytesN<32>,
+    caller: Address,
+    new_end: u64,
+    new_rent: i128,
+    renewal: RenewalOption,
+) -> Result<(), Error> {
+    let lease = load_lease(env, &lease_id)?;
+
+    if caller != lease.lessor {
+        return Err(Error::Unauthorized);
+    }
+
+    if !is_live(&lease) {
+        return Err(Error::InvalidLeaseStatus);
+    }
+
+    // The new end cannot cut into the past or into rent already paid
+    let now = env.ledger().timestamp();
+    let paid_through = get_paid_through(env, lease_id.clone())?;
+    if new_end <= lease.start_timestamp || new_end <= now || new_end < paid_through {
+        return Err(Error::InvalidTimestamps);
+    }
+    if new_rent < 0 {
+        return Err(Error::InvalidPayment);
+    }
+    validate_renewal(&renewal)?;
+    if let Some(parent_id) = &lease.parent {
+        validate_sublease_term(env, parent_id, lease.start_timestamp, new_end, &renewal)?;
+    }
+    validate_covers_subleases(env, &lease_id, new_end)?;
+
+    env.storage().persistent().set(
+        &DataKey::PendingAmendment(lease_id.clone()),
+        &LeaseAmendment {
+            new_end_timestamp: new_end,
+            new_rent_per_period: new_rent,
+            renewal,
+            proposed_at: now,
+        },
+    );
+
+    env.events().publish(
+        (symbol_short!("lease_prp"),),
+        (lease_id, new_end, new_rent, now),
+    );
+
+    Ok(())
+}
+
+/// Accept or reject the pending amendment. Lessee only. Accepted changes apply
+/// to every period not yet paid.
+pub fn respond_to_amendment(
+    env: &Env,
+    lease_id: BytesN<32>,
+    caller: Address,
+    accept: bool,
+) -> Result<(), Error> {
+    let mut lease = load_lease(env, &lease_id)?;
+
+    if caller != lease.lessee {
+        return Err(Error::Unauthorized);
+    }
+
+    let key = DataKey::PendingAmendment(lease_id.clone());
+    let amendment: LeaseAmendment = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::InvalidLeaseStatus)?;
+    env.storage().persistent().remove(&key);
+
+    if accept {
+        if !is_live(&lease) {
+            return Err(Error::InvalidLeaseStatus);
+        }
+        if let Some(parent_id) = &lease.parent {
+            validate_sublease_term(
+                env,
+                parent_id,
+                lease.start_timestamp,
+                amendment.new_end_timestamp,
+                &amendment.renewal,
+            )?;
+        }
+        validate_covers_subleases(env, &lease_id, amendment.new_end_timestamp)?;
+        book(
+            env,
+            &lease_calendar(&lease),
+            &lease_id,
+            lease.start_timestamp,
+            amendment.new_end_timestamp,
+        )?;
+        lease.end_timestamp = amendment.new_end_timestamp;
+        lease.rent_per_period = amendment.new_rent_per_period;
+        lease.renewal = amendment.renewal;
+        record_version(env, &mut lease, LeaseChange::Amended);
+        save_lease(env, &lease);
+    }
+
+    env.events().publish(
+        (symbol_short!("lease_amd"),),
+        (lease_id, accept, lease.version, env.ledger().timestamp()),
+    );
+
+    Ok(())
+}
+
+/// Give notice that an auto-renewing lease should end at its current end date.
+/// Lessor or lessee, no later than the notice period before the end.
+pub fn give_renewal_notice(env: &Env, lease_id: BytesN<32>, caller: Address) -> Result<(), Error> {
+    let mut lease = load_lease(env, &lease_id)?;
+
+    if caller != lease.lessor && caller != lease.lessee {
+        return Err(Error::Unauthorized);
+    }
+
+    if !is_live(&lease) {
+        return Err(Error::InvalidLeaseStatus);
+    }
+
+    let renewal = renewal_terms(&lease)?;
+    let deadline = lease.end_timestamp.saturating_sub(renewal.notice_period);
+    if env.ledger().timestamp() > deadline {
+        return Err(Error::InvalidTimestamps);
+    }
+
+    lease.renewal = RenewalOption::Manual;
+    record_version(env, &mut lease, LeaseChange::RenewalCancelled);
+    save_lease(env, &lease);
+
+    env.events().publish(
+        (symbol_short!("lease_ntc"),),
+        (lease_id, caller, env.ledger().timestamp()),
+    );
+
+    Ok(())
+}
+
+/// Extend an auto-renewing lease by its renewal term once the notice deadline
+/// has passed without notice. Permissionless.
+pub fn renew_lease(env: &Env, lease_id: BytesN<32>) -> Result<u64, Error> {
+    let mut lease = load_lease(env, &lease_id)?;
+
+    if !is_live(&lease) {
+        return Err(Error::InvalidLeaseStatus);
+    }
+
+    let renewal = renewal_terms(&lease)?;
+    let deadline = lease.end_timestamp.saturating_sub(renewal.notice_period);
+    if env.ledger().timestamp() <= deadline {
+        return Err(Error::LeaseNotExpired);
+    }
+
+    lease.end_timestamp = lease.end_timestamp.saturating_add(renewal.term);
+    book(
+        env,
+        &lease_calendar(&lease),
+        &lease_id,
+        lease.start_timestamp,
+        lease.end_timestamp,
+    )?;
+    record_version(env, &mut lease, LeaseChange::Renewed);
+    save_lease(env, &lease);
+
+    env.events().publish(
+        (symbol_short!("lease_rnw"),),
+        (lease_id, lease.end_timestamp, env.ledger().timestamp()),
+    );
+
+    Ok(lease.end_timestamp)
+}
+
+/// End the lease before its end date. Lessee only; pays the rent due so far
+/// plus the early-termination fee. Returns the amount paid.
+pub fn terminate_lease_early(
+    env: &Env,
+    lease_id: BytesN<32>,
+    caller: Address,
+) -> Result<i128, Error> {
+    let mut lease = load_lease(env, &lease_id)?;
+
+    if caller != lease.lessee {
+        return Err(Error::Unauthorized);
+    }
+
+    if !is_live(&lease) {
+        return Err(Error::InvalidLeaseStatus);
+    }
+
+    let now = env.ledger().timestamp();
+    if now >= lease.end_timestamp {
+        return Err(Error::InvalidTimestamps);
+    }
+
+    assess_late_periods(&mut lease, now)?;
+    let unpaid = periods_started(&lease, now).saturating_sub(lease.periods_paid);
+    let rent = math::mul(unpaid as i128, lease.rent_per_period)?;
+    let amount = math::add(
+        math::add(rent, lease.late_fees_owed)?,
+        lease.terms.early_termination_fee,
+    )?;
+    pay_lessor(env, &lease, &caller, amount)?;
+
+    lease.periods_paid += unpaid;
+    lease.late_fees_owed = 0;
+    lease.status = LeaseStatus::Terminated;
+    lease.ended_at = now;
+    lease.end_timestamp = now.max(lease.start_timestamp);
+    lease.renewal = RenewalOption::Manual;
+    record_version(env, &mut lease, LeaseChange::Terminated);
+    save_lease(env, &lease);
+    close_lease(env, &lease)?;
+    env.storage()
         .persistent()
-        .get(&DataKey::LesseeLeases(lessee))
+        .remove(&DataKey::PendingAmendment(lease_id.clone()));
+
+    env.events().publish(
+        (symbol_short!("lease_trm"),),
+        (lease_id, caller, amount, now),
+    );
+
+    Ok(amount)
+}
+
+/// Consent to the lessee assigning a live lease to `new_lessee`. Lessor only;
+/// replaces any earlier consent.
+pub fn consent_to_assignment(
+    env: &Env,
+    lease_id: BytesN<32>,
+    caller: Address,
+    new_lessee: Address,
+) -> Result<(), Error> {
+    let lease = load_lease(env, &lease_id)?;
+
+    if caller != lease.lessor {
+        return Err(Error::Unauthorized);
+    }
+
+    if !is_live(&lease) {
+        return Err(Error::InvalidLeaseStatus);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::AssignmentConsent(lease_id.clone()), &new_lessee);
+
+    env.events().publish(
+        (symbol_short!("lease_cns"),),
+        (lease_id, new_lessee, env.ledger().timestamp()),
+    );
+
+    Ok(())
+}
+
+/// Hand the lease over to the new lessee the lessor consented to. Lessee only.
+/// Rent must be up to date and any subleases ended; the new lessee buys out
+/// the escrowed deposit from the outgoing one.
+pub fn assign_lease(
+    env: &Env,
+    lease_id: BytesN<32>,
+    caller: Address,
+    new_lessee: Address,
+) -> Result<(), Error> {
+    let mut lease = load_lease(env, &lease_id)?;
+
+    if caller != lease.lessee {
+        return Err(Error::Unauthorized);
+    }
+
+    if !is_live(&lease) {
+        return Err(Error::InvalidLeaseStatus);
+    }
+
+    let consent_key = DataKey::AssignmentConsent(lease_id.clone());
+    let consented: Option<Address> = env.storage().persistent().get(&consent_key);
+    if consented != Some(new_lessee.clone()) {
+        return Err(Error::Unauthorized);
+    }
+
+    // The outgoing lessee settles arrears before handing over
+    if arrears(&mut lease, env.ledger().timestamp())? > 0 {
+        return Err(Error::InvalidPayment);
+    }
+    if !load_calendar(env, &DataKey::SubleaseCalendar(lease_id.clone())).is_empty() {
+        return Err(Error::InvalidLeaseStatus);
+    }
+
+    if lease.deposit_held > 0 {
+        token::Client::new(env, &lease.terms.payment_token).transfer(
+            &new_lessee,
+            &caller,
+            &lease.deposit_held,
+        );
+    }
+
+    lease.lessee = new_lessee.clone();
+    record_version(env, &mut lease, LeaseChange::Assigned);
+    save_lease(env, &lease);
+    env.storage().persistent().remove(&consent_key);
+    remove_from_index(env, DataKey::LesseeLeases(caller.clone()), &lease_id);
+    append_to_index(env, DataKey::LesseeLeases(new_lessee.clone()), &lease_id);
+
+    env.events().publish(
+        (symbol_short!("lease_asg"),),
+        (lease_id, caller, new_lessee, env.ledger().timestamp()),
+    );
+
+    Ok(())
+}
+
+/// Every sublease created under a lease, oldest first
+pub fn get_subleases(env: &Env, lease_id: BytesN<32>) -> Vec<BytesN<32>> {
+    load_index(env, &DataKey::Subleases(lease_id))
+}
+
+pub fn get_pending_amendment(env: &Env, lease_id: BytesN<32>) -> Option<LeaseAmendment> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PendingAmendment(lease_id))
+}
+
+/// Every version of the lease's terms, oldest first
+pub fn get_lease_versions(env: &Env, lease_id: BytesN<32>) -> Vec<LeaseVersion> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::LeaseVersions(lease_id))
         .unwrap_or_else(|| Vec::new(env))
 }
+
+pub fn get_deposit_settlement(env: &Env, lease_id: BytesN<32>) -> Option<DepositSettlement> {
+    load_settlement(env, &lease_id)
+}
+
+/// Delegate or revoke the right to lease an asset on the owner's behalf.
+/// Owner only; delegations lapse when the asset changes hands.
+pub fn set_lease_manager(
+    env: &Env,
+    asset_id: BytesN<32>,
+    manager: Address,
+    enabled: bool,
+    caller: Address,
+) -> Result<(), Error> {
+    let asset = load_asset(env, &asset_id)?;
+    if caller != asset.owner {
+        return Err(Error::Unauthorized);
+    }
+
+    let key = DataKey::LeaseManager(asset_id.clone(), manager.clone());
+    if enabled {
+        env.storage().persistent().set(&key, &caller);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+
+    env.events().publish(
+        (symbol_short!("lease_mgr"),),
+        (asset_id, manager, enabled, env.ledger().timestamp()),
+    );
+
+    Ok(())
+}
+
+pub fn is_asset_lease_manager(env: &Env, asset_id: BytesN<32>, manager: Address) -> bool {
+    load_asset(env, &asset_id)
+        .map(|asset| is_lease_manager(env, &asset, &manager))
+        .unwrap_or(false)
+}
+
+/// Carry live leases over to a new owner when a leased asset is sold. Leases
+/// signed by the previous owner pass to `new_owner` as lessor; leases signed by
+/// a manager stay with the manager. Ended leases awaiting deposit settlement
+/// remain with whoever signed them.
+pub fn transfer_lessor(
+    env: &Env,
+    asset_id: BytesN<32>,
+    old_owner: Address,
+    new_owner: Address,
+) -> Result<(), Error> {
+    for booking in load_calendar(env, &DataKey::AssetCalendar(asset_id.clone())).iter() {
+        let mut lease = load_lease(env, &booking.lease_id)?;
+        if lease.lessor != old_owner {
+            continue;
+        }
+        lease.lessor = new_owner.clone();
+        record_version(env, &mut lease, LeaseChange::LessorTransferred);
+        save_lease(env, &lease);
+        remove_from_index(
+            env,
+            DataKey::LessorLeases(old_owner.clone()),
+            &lease.lease_id,
+        );
+        append_to_index(
+            env,
+            DataKey::LessorLeases(new_owner.clone()),
+            &lease.lease_id,
+        );
+
+        env.events().publish(
+            (symbol_short!("lease_xfr"),),
+            (booking.lease_id, old_owner.clone(), new_owner.clone()),
+        );
+    }
+    Ok(())
+}
+
+pub fn get_lease(env: &Env, lease_id: BytesN<32>) -> Result<Lease, Error> {
+    load_lease(env, &lease_id)
+}
+
+/// Persist the promotion of a scheduled lease whose start time has arrived.
+/// Permissionless.
+pub fn activate_lease(env: &Env, lease_id: BytesN<32>) -> Result<(), Error> {
+    let stored: Lease = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Lease(lease_id.clone()))
+        .ok_or(Error::LeaseNotFound)?;
+    if stored.status != LeaseStatus::Scheduled {
+        return Err(Error::InvalidLeaseStatus);
+    }
+
+    let lease = load_lease(env, &lease_id)?;
+    if lease.status != LeaseStatus::Active {
+        return Err(Error::InvalidTimestamps);
+    }
+    save_lease(env, &lease);
+
+    env.events().publish(
+        (symbol_short!("lease_act"),),
+        (lease_id, lease.asset_id, env.ledger().timestamp()),
+    );
+
+    Ok(())
+}
+
+/// The lease whose booking covers the current time, if any
+pub fn get_asset_active_lease(env: &Env, asset_id: BytesN<32>) -> Option<Lease> {
+    let now = env.ledger().timestamp();
+    load_calendar(env, &DataKey::AssetCalendar(asset_id.clone()))
+        .iter()
+        .find(|booking| booking.start <= now && now < booking.end)
+        .and_then(|booking| load_lease(env, &booking.lease_id).ok())
+}
+
+/// Every live booking on an asset, ordered by start
+pub fn get_asset_bookings(env: &Env, asset_id: BytesN<32>) -> Vec<Booking> {
+    load_calendar(env, &DataKey::AssetCalendar(asset_id.clone()))
+}
+
+/// Leases whose booking overlaps `[from, to)`
+pub fn get_leases_in_range(
+    env: &Env,
+    asset_id: BytesN<32>,
+    from: u64,
+    to: u64,
+) -> Result<Vec<BytesN<32>>, Error> {
+    if to <= from {
+        return Err(Error::InvalidTimestamps);
+    }
+    let mut lease_ids = Vec::new(env);
+    for booking in load_calendar(env, &DataKey::AssetCalendar(asset_id.clone())).iter() {
+        if booking.start < to && from < booking.end {
+            lease_ids.push_back(booking.lease_id);
+        }
+    }
+    Ok(lease_ids)
+}
+
+/// Earliest start at or after `from` with `duration` seconds free
+pub fn get_next_free_window(
+    env: &Env,
+    asset_id: BytesN<32>,
+    from: u64,
+    duration: u64,
+) -> Result<u64, Error> {
+    if duration == 0 {
+        return Err(Error::InvalidTimestamps);
+    }
+    let mut candidate = from;
+    for booking in load_calendar(env, &DataKey::AssetCalendar(asset_id.clone())).iter() {
+        if booking.end <= candidate {
+            continue;
+        }
+        if candidate.saturating_add(duration) <= booking.start {
+            break;
+        }
+        candidate = booking.end;
+    }
+    Ok(candidate)
+}
+
+pub fn get_lessee_leases(env: &Env, lessee: Address) -> Vec<BytesN<32>> {
+    load_index(env, &DataKey::LesseeLeases(lessee))
+}
+
+/// Page through the scheduled and active leases an address is lessor of,
+/// optionally by status. Ended leases are in their asset's lease history.
+pub fn get_lessor_leases(
+    env: &Env,
+    lessor: Address,
+    status: Option<LeaseStatus>,
+    start: u32,
+    limit: u32,
+) -> Result<Vec<Lease>, Error> {
+    page_leases(env, DataKey::LessorLeases(lessor), status, start, limit)
+}
+
+/// Page through every lease ever created on an asset, optionally by status
+pub fn get_asset_lease_history(
+    env: &Env,
+    asset_id: BytesN<32>,
+    status: Option<LeaseStatus>,
+    start: u32,
+    limit: u32,
+) -> Result<Vec<Lease>, Error> {
+    page_leases(env, DataKey::AssetLeases(asset_id), status, start, limit)
+}
+
+/// Summarise a lessor's live leases, with rent expected for periods falling
+/// due within `[from, to)` and arrears as of now
+pub fn get_lessor_portfolio(
+    env: &Env,
+    lessor: Address,
+    from: u64,
+    to: u64,
+) -> Result<LessorPortfolio, Error> {
+    if to <= from {
+        return Err(Error::InvalidTimestamps);
+    }
+
+    let now = env.ledger().timestamp();
+    let mut portfolio = LessorPortfolio {
+        total_leases: 0,
+        scheduled_leases: 0,
+        active_leases: 0,
+        expected_rent: 0,
+        overdue_leases: 0,
+        arrears: 0,
+    };
+    for id in load_index(env, &DataKey::LessorLeases(lessor)).iter() {
+        let mut lease = load_lease(env, &id)?;
+        portfolio.total_leases += 1;
+        match lease.status {
+            LeaseStatus::Scheduled => portfolio.scheduled_leases += 1,
+            LeaseStatus::Active => portfolio.active_leases += 1,
+            _ => continue,
+        }
+
+        let due = periods_due_between(&lease, from, to);
+        portfolio.expected_rent = math::add(
+            portfolio.expected_rent,
+            math::mul(due as i128, lease.rent_per_period)?,
+        )?;
+
+        let overdue = arrears(&mut lease, now)?;
+        if overdue > 0 {
+            portfolio.overdue_leases += 1;
+            portfolio.arrears = math::add(portfolio.arrears, overdue)?;
+        }
+    }
+
+    Ok(portfolio)
+}
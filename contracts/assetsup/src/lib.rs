@@ -3,20 +3,29 @@
 
 use crate::error::{handle_error, Error};
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env, String, Vec,
+    contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env, String, Symbol, Vec,
 };
 
 pub(crate) mod asset;
+pub(crate) mod attestation;
 pub(crate) mod audit;
 pub(crate) mod branch;
+pub(crate) mod compliance;
 pub(crate) mod detokenization;
 pub(crate) mod dividends;
+pub(crate) mod enforcement;
 pub(crate) mod error;
+pub(crate) mod identity;
 pub(crate) mod insurance;
 pub(crate) mod lease;
+pub(crate) mod marketplace;
+pub(crate) mod math;
+pub(crate) mod offering;
 pub(crate) mod tokenization;
 pub(crate) mod transfer_restrictions;
 pub(crate) mod types;
+pub(crate) mod valuation;
+pub(crate) mod vesting;
 pub(crate) mod voting;
 
 #[cfg(test)]
@@ -314,6 +323,9 @@ impl AssetUpContract {
         asset.status = AssetStatus::Transferred;
         store.set(&key, &asset);
 
+        // Live leases signed by the previous owner carry over to the new one
+        lease::transfer_lessor(&env, asset_id.clone(), old_owner.clone(), new_owner.clone())?;
+
         // Emit event
         env.events().publish(
             (symbol_short!("asset_tx"),),
@@ -387,6 +399,7 @@ impl AssetUpContract {
             category: asset.category,
             owner: asset.owner,
             status: asset.status,
+            leased: lease::get_asset_active_lease(&env, asset_id).is_some(),
         })
     }
 
@@ -542,6 +555,22 @@ impl AssetUpContract {
         )
     }
 
+    /// Link a tokenized asset to the registry asset it represents
+    pub fn link_registry_asset(
+        env: Env,
+        asset_id: u64,
+        registry_id: BytesN<32>,
+        tokenizer: Address,
+    ) -> Result<(), Error> {
+        tokenizer.require_auth();
+        tokenization::link_registry_asset(&env, asset_id, registry_id, tokenizer)
+    }
+
+    /// Get the registry asset a tokenized asset represents, if linked
+    pub fn get_registry_asset(env: Env, asset_id: u64) -> Option<BytesN<32>> {
+        tokenization::get_registry_asset(&env, asset_id)
+    }
+
     /// Mint additional tokens (only tokenizer can call)
     pub fn mint_tokens(
         env: Env,
@@ -564,6 +593,63 @@ impl AssetUpContract {
         tokenization::burn_tokens(&env, asset_id, amount, burner)
     }
 
+    /// Bind a not-yet-voted proposal to a governed mint amount (tokenizer only)
+    pub fn propose_mint(
+        env: Env,
+        asset_id: u64,
+        proposal_id: u64,
+        amount: i128,
+        caller: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        tokenization::propose_mint(&env, asset_id, proposal_id, amount, caller)
+    }
+
+    /// Mint above the governance threshold using a passed holder vote
+    pub fn mint_tokens_with_approval(
+        env: Env,
+        asset_id: u64,
+        amount: i128,
+        proposal_id: u64,
+        minter: Address,
+    ) -> Result<TokenizedAsset, Error> {
+        minter.require_auth();
+        tokenization::mint_tokens_with_approval(&env, asset_id, amount, proposal_id, minter)
+    }
+
+    /// Set the minting policy for an asset (only tokenizer can call)
+    pub fn set_minting_policy(
+        env: Env,
+        asset_id: u64,
+        policy: MintingPolicy,
+        caller: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        tokenization::set_minting_policy(&env, asset_id, policy, caller)
+    }
+
+    pub fn get_minting_policy(env: Env, asset_id: u64) -> MintingPolicy {
+        tokenization::get_minting_policy(&env, asset_id)
+    }
+
+    /// Split or consolidate an asset's tokens so every `ratio_from` tokens
+    /// become `ratio_to` (only tokenizer can call)
+    pub fn redenominate_tokens(
+        env: Env,
+        asset_id: u64,
+        ratio_from: u32,
+        ratio_to: u32,
+        caller: Address,
+    ) -> Result<CorporateAction, Error> {
+        caller.require_auth();
+        tokenization::redenominate_tokens(&env, asset_id, ratio_from, ratio_to, caller)
+    }
+
+    /// Get the split and consolidation history of an asset
+    pub fn get_corporate_actions(env: Env, asset_id: u64) -> Vec<CorporateAction> {
+        tokenization::get_corporate_actions(&env, asset_id)
+    }
+
     /// Transfer tokens from one address to another
     pub fn transfer_tokens(
         env: Env,
@@ -585,11 +671,30 @@ impl AssetUpContract {
         tokenization::get_token_balance(&env, asset_id, holder)
     }
 
+    /// Get the ownership record for a holder
+    pub fn get_ownership_record(
+        env: Env,
+        asset_id: u64,
+        holder: Address,
+    ) -> Result<OwnershipRecord, Error> {
+        tokenization::get_ownership_record(&env, asset_id, holder)
+    }
+
     /// Get all token holders for an asset
     pub fn get_token_holders(env: Env, asset_id: u64) -> Result<Vec<Address>, Error> {
         tokenization::get_token_holders(&env, asset_id)
     }
 
+    /// Get a page of the cap table: holder, balance, ownership bps and unclaimed dividends
+    pub fn get_cap_table(
+        env: Env,
+        asset_id: u64,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<CapTableEntry>, Error> {
+        tokenization::get_cap_table(&env, asset_id, start, limit)
+    }
+
     /// Lock tokens until timestamp (only the asset tokenizer can call this)
     pub fn lock_tokens(
         env: Env,
@@ -612,6 +717,55 @@ impl AssetUpContract {
         tokenization::is_tokens_locked(&env, asset_id, holder)
     }
 
+    /// Lock part of a holder's balance until `release_at` (tokenizer only)
+    pub fn lock_token_amount(
+        env: Env,
+        asset_id: u64,
+        holder: Address,
+        amount: i128,
+        release_at: u64,
+        reason: Symbol,
+        caller: Address,
+    ) -> Result<u32, Error> {
+        caller.require_auth();
+        tokenization::lock_token_amount(&env, asset_id, holder, amount, release_at, reason, caller)
+    }
+
+    /// Release a partial lock early (tokenizer only)
+    pub fn release_token_lock(
+        env: Env,
+        asset_id: u64,
+        holder: Address,
+        lock_id: u32,
+        caller: Address,
+    ) -> Result<i128, Error> {
+        caller.require_auth();
+        tokenization::release_token_lock(&env, asset_id, holder, lock_id, caller)
+    }
+
+    /// Clear a holder's expired partial locks
+    pub fn release_expired_locks(env: Env, asset_id: u64, holder: Address) -> Result<i128, Error> {
+        tokenization::release_expired_locks(&env, asset_id, holder)
+    }
+
+    pub fn get_token_locks(
+        env: Env,
+        asset_id: u64,
+        holder: Address,
+    ) -> Result<Vec<TokenLock>, Error> {
+        tokenization::get_token_locks(&env, asset_id, holder)
+    }
+
+    /// Get the amount of a holder's balance under active partial locks
+    pub fn get_locked_amount(env: Env, asset_id: u64, holder: Address) -> Result<i128, Error> {
+        tokenization::get_locked_amount(&env, asset_id, holder)
+    }
+
+    /// Get the balance a holder can transfer right now
+    pub fn get_spendable_balance(env: Env, asset_id: u64, holder: Address) -> Result<i128, Error> {
+        tokenization::get_spendable_balance(&env, asset_id, holder)
+    }
+
     /// Get ownership percentage for a holder (in basis points)
     pub fn get_ownership_percentage(
         env: Env,
@@ -626,208 +780,934 @@ impl AssetUpContract {
         tokenization::get_tokenized_asset(&env, asset_id)
     }
 
-    /// Update asset valuation
-    pub fn update_valuation(env: Env, asset_id: u64, new_valuation: i128) -> Result<(), Error> {
-        tokenization::update_valuation(&env, asset_id, new_valuation)
+    /// Get token metadata for a tokenized asset
+    pub fn get_token_metadata(env: Env, asset_id: u64) -> Result<TokenMetadata, Error> {
+        tokenization::get_token_metadata(&env, asset_id)
     }
 
     // =====================
-    // Dividend Functions
+    // Vesting
     // =====================
 
-    /// Distribute dividends proportionally to all holders
-    pub fn distribute_dividends(env: Env, asset_id: u64, total_amount: i128) -> Result<(), Error> {
-        dividends::distribute_dividends(&env, asset_id, total_amount)
+    /// Grant tokens to a holder under a cliff + linear vesting schedule (tokenizer only)
+    pub fn create_vesting_schedule(
+        env: Env,
+        asset_id: u64,
+        holder: Address,
+        amount: i128,
+        start: u64,
+        cliff: u64,
+        duration: u64,
+        revocable: bool,
+        caller: Address,
+    ) -> Result<u32, Error> {
+        caller.require_auth();
+        vesting::create_vesting_schedule(
+            &env, asset_id, holder, amount, start, cliff, duration, revocable, caller,
+        )
     }
 
-    /// Claim unclaimed dividends
-    pub fn claim_dividends(env: Env, asset_id: u64, holder: Address) -> Result<i128, Error> {
-        holder.require_auth();
-        dividends::claim_dividends(&env, asset_id, holder)
+    /// Revoke a vesting schedule, returning unvested tokens to the tokenizer
+    pub fn revoke_vesting(
+        env: Env,
+        asset_id: u64,
+        holder: Address,
+        schedule_index: u32,
+        caller: Address,
+    ) -> Result<i128, Error> {
+        caller.require_auth();
+        vesting::revoke_vesting(&env, asset_id, holder, schedule_index, caller)
     }
 
-    /// Get unclaimed dividends for a holder
-    pub fn get_unclaimed_dividends(
+    pub fn get_vesting_schedules(
         env: Env,
         asset_id: u64,
         holder: Address,
-    ) -> Result<i128, Error> {
-        dividends::get_unclaimed_dividends(&env, asset_id, holder)
+    ) -> Result<Vec<vesting::VestingSchedule>, Error> {
+        vesting::get_vesting_schedules(&env, asset_id, holder)
     }
 
-    /// Enable revenue sharing for an asset
-    pub fn enable_revenue_sharing(env: Env, asset_id: u64) -> Result<(), Error> {
-        dividends::enable_revenue_sharing(&env, asset_id)
+    /// Get the amount of a holder's vesting grants that has vested
+    pub fn get_vested_amount(env: Env, asset_id: u64, holder: Address) -> Result<i128, Error> {
+        vesting::get_vested_amount(&env, asset_id, holder)
     }
 
-    /// Disable revenue sharing for an asset
-    pub fn disable_revenue_sharing(env: Env, asset_id: u64) -> Result<(), Error> {
-        dividends::disable_revenue_sharing(&env, asset_id)
+    /// Get the amount of a holder's vesting grants that is still unvested
+    pub fn get_unvested_amount(env: Env, asset_id: u64, holder: Address) -> Result<i128, Error> {
+        vesting::get_unvested_amount(&env, asset_id, holder)
     }
 
     // =====================
-    // Voting Functions
+    // Attestations
     // =====================
 
-    /// Cast a vote on a proposal
-    pub fn cast_vote(
+    /// Register an appraiser or notary signing key (admin only)
+    pub fn register_attestor(
         env: Env,
-        asset_id: u64,
-        proposal_id: u64,
-        voter: Address,
+        public_key: BytesN<32>,
+        role: attestation::AttestorRole,
     ) -> Result<(), Error> {
-        voter.require_auth();
-        voting::cast_vote(&env, asset_id, proposal_id, voter)
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        attestation::register_attestor(&env, public_key, role)
     }
 
-    /// Get vote tally for a proposal
-    pub fn get_vote_tally(env: Env, asset_id: u64, proposal_id: u64) -> Result<i128, Error> {
-        voting::get_vote_tally(&env, asset_id, proposal_id)
+    /// Remove a registered attestor (admin only)
+    pub fn remove_attestor(env: Env, public_key: BytesN<32>) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        attestation::remove_attestor(&env, public_key)
     }
 
-    /// Check if an address has voted
-    pub fn has_voted(
+    /// Get a registered attestor by public key
+    pub fn get_attestor(env: Env, public_key: BytesN<32>) -> Option<attestation::Attestor> {
+        attestation::get_attestor(&env, public_key)
+    }
+
+    /// Update asset valuation with an appraiser-signed attestation
+    pub fn update_valuation(
         env: Env,
         asset_id: u64,
-        proposal_id: u64,
-        voter: Address,
-    ) -> Result<bool, Error> {
-        voting::has_voted(&env, asset_id, proposal_id, voter)
+        new_valuation: i128,
+        method: valuation::ValuationMethod,
+        attestor: BytesN<32>,
+        timestamp: u64,
+        signature: BytesN<64>,
+    ) -> Result<(), Error> {
+        tokenization::update_valuation(
+            &env,
+            asset_id,
+            new_valuation,
+            method,
+            attestor,
+            timestamp,
+            signature,
+        )
     }
 
-    /// Check if proposal passed
-    pub fn proposal_passed(env: Env, asset_id: u64, proposal_id: u64) -> Result<bool, Error> {
-        voting::proposal_passed(&env, asset_id, proposal_id)
+    /// Record an attested legal docs or valuation report hash
+    pub fn attest_document(
+        env: Env,
+        asset_id: u64,
+        field: attestation::AttestationField,
+        doc_hash: BytesN<32>,
+        attestor: BytesN<32>,
+        timestamp: u64,
+        signature: BytesN<64>,
+    ) -> Result<(), Error> {
+        tokenization::set_document_hash(
+            &env, asset_id, field, doc_hash, attestor, timestamp, signature,
+        )
+    }
+
+    /// Get the attestation history for an asset
+    pub fn get_attestations(env: Env, asset_id: u64) -> Vec<attestation::Attestation> {
+        attestation::get_attestations(&env, asset_id)
+    }
+
+    /// Get the latest attestation for a field of an asset
+    pub fn get_latest_attestation(
+        env: Env,
+        asset_id: u64,
+        field: attestation::AttestationField,
+    ) -> Option<attestation::Attestation> {
+        attestation::get_latest_attestation(&env, asset_id, field)
     }
 
     // =====================
-    // Transfer Restrictions
+    // Valuation
     // =====================
 
-    /// Set transfer restrictions
-    pub fn set_transfer_restriction(
+    /// Configure the SEP-40 price oracle for an asset (admin only)
+    pub fn set_price_oracle(
         env: Env,
         asset_id: u64,
-        require_accredited: bool,
+        oracle: Address,
+        oracle_asset: valuation::OracleAsset,
+        max_age: u64,
     ) -> Result<(), Error> {
-        transfer_restrictions::set_transfer_restriction(
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        valuation::set_price_oracle(
             &env,
             asset_id,
-            TransferRestriction {
-                require_accredited,
-                geographic_allowed: Vec::new(&env),
+            valuation::OracleConfig {
+                oracle,
+                asset: oracle_asset,
+                max_age,
             },
         )
     }
 
-    /// Add address to whitelist
-    pub fn add_to_whitelist(env: Env, asset_id: u64, address: Address) -> Result<(), Error> {
-        transfer_restrictions::add_to_whitelist(&env, asset_id, address)
+    /// Get the price oracle configured for an asset
+    pub fn get_price_oracle(env: Env, asset_id: u64) -> Option<valuation::OracleConfig> {
+        valuation::get_price_oracle(&env, asset_id)
     }
 
-    /// Remove address from whitelist
-    pub fn remove_from_whitelist(env: Env, asset_id: u64, address: Address) -> Result<(), Error> {
-        transfer_restrictions::remove_from_whitelist(&env, asset_id, address)
+    /// Pull the latest oracle quote into the asset valuation (permissionless)
+    pub fn update_valuation_from_oracle(env: Env, asset_id: u64) -> Result<i128, Error> {
+        valuation::update_valuation_from_oracle(&env, asset_id)
     }
 
-    /// Check if address is whitelisted
-    pub fn is_whitelisted(env: Env, asset_id: u64, address: Address) -> Result<bool, Error> {
-        transfer_restrictions::is_whitelisted(&env, asset_id, address)
+    /// Get the valuation history for an asset
+    pub fn get_valuation_history(env: Env, asset_id: u64) -> Vec<valuation::ValuationRecord> {
+        valuation::get_valuation_history(&env, asset_id)
     }
 
-    /// Get whitelist
-    pub fn get_whitelist(env: Env, asset_id: u64) -> Result<Vec<Address>, Error> {
-        transfer_restrictions::get_whitelist(&env, asset_id)
+    /// Get the price of one whole token
+    pub fn get_price_per_token(env: Env, asset_id: u64) -> Result<i128, Error> {
+        valuation::get_price_per_token(&env, asset_id)
+    }
+
+    /// Get the net asset value of a holder's position
+    pub fn get_holder_nav(env: Env, asset_id: u64, holder: Address) -> Result<i128, Error> {
+        valuation::get_holder_nav(&env, asset_id, holder)
+    }
+
+    /// Get the time-weighted average valuation over a window
+    pub fn get_time_weighted_valuation(
+        env: Env,
+        asset_id: u64,
+        from: u64,
+        to: u64,
+    ) -> Result<i128, Error> {
+        valuation::get_time_weighted_valuation(&env, asset_id, from, to)
     }
 
     // =====================
-    // Detokenization
+    // Secondary Market
     // =====================
 
-    /// Propose detokenization
-    pub fn propose_detokenization(
+    /// Open a secondary market priced in a SEP-41 payment token (tokenizer only)
+    pub fn open_market(
         env: Env,
         asset_id: u64,
-        proposer: Address,
-    ) -> Result<u64, Error> {
-        proposer.require_auth();
-        detokenization::propose_detokenization(&env, asset_id, proposer)
+        payment_token: Address,
+        caller: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        marketplace::open_market(&env, asset_id, payment_token, caller)
     }
 
-    /// Execute detokenization (if vote passed)
-    pub fn execute_detokenization(env: Env, asset_id: u64, proposal_id: u64) -> Result<(), Error> {
-        detokenization::execute_detokenization(&env, asset_id, proposal_id)
+    /// Get the secondary market for an asset
+    pub fn get_market(env: Env, asset_id: u64) -> Option<marketplace::Market> {
+        marketplace::get_market(&env, asset_id)
     }
 
-    /// Get detokenization proposal status
-    pub fn get_detokenization_proposal(
+    /// Place a limit buy or sell order
+    pub fn place_order(
         env: Env,
         asset_id: u64,
-    ) -> Result<DetokenizationProposal, Error> {
-        detokenization::get_detokenization_proposal(&env, asset_id)
+        maker: Address,
+        side: marketplace::OrderSide,
+        price: i128,
+        amount: i128,
+    ) -> Result<u64, Error> {
+        maker.require_auth();
+        marketplace::place_order(&env, asset_id, maker, side, price, amount)
     }
 
-    /// Check if detokenization is active
-    pub fn is_detokenization_active(env: Env, asset_id: u64) -> Result<bool, Error> {
-        detokenization::is_detokenization_active(&env, asset_id)
+    /// Fill all or part of an open order
+    pub fn fill_order(
+        env: Env,
+        order_id: u64,
+        taker: Address,
+        amount: i128,
+    ) -> Result<i128, Error> {
+        taker.require_auth();
+        marketplace::fill_order(&env, order_id, taker, amount)
+    }
+
+    /// Cancel an open order (maker only)
+    pub fn cancel_order(env: Env, order_id: u64, caller: Address) -> Result<(), Error> {
+        caller.require_auth();
+        marketplace::cancel_order(&env, order_id, caller)
+    }
+
+    /// Get an order by ID
+    pub fn get_order(env: Env, order_id: u64) -> Result<marketplace::Order, Error> {
+        marketplace::get_order(&env, order_id)
+    }
+
+    /// Get all open orders for an asset
+    pub fn get_open_orders(env: Env, asset_id: u64) -> Vec<marketplace::Order> {
+        marketplace::get_open_orders(&env, asset_id)
     }
 
     // =====================
-    // Insurance Policy Management
+    // Primary Offering
     // =====================
 
-    /// Create a new insurance policy
-    pub fn create_insurance_policy(
+    /// Create a primary offering for an asset (tokenizer only)
+    pub fn create_offering(
         env: Env,
-        policy: insurance::InsurancePolicy,
+        asset_id: u64,
+        issuer: Address,
+        terms: offering::OfferingTerms,
     ) -> Result<(), Error> {
-        policy.insurer.require_auth();
-        insurance::create_policy(env, policy)
+        issuer.require_auth();
+        offering::create_offering(&env, asset_id, issuer, terms)
     }
 
-    /// Cancel a policy (holder or insurer)
-    pub fn cancel_insurance_policy(
+    /// Subscribe to an active offering
+    pub fn subscribe(
         env: Env,
-        policy_id: BytesN<32>,
-        caller: Address,
+        asset_id: u64,
+        investor: Address,
+        amount: i128,
     ) -> Result<(), Error> {
-        caller.require_auth();
-        insurance::cancel_policy(env, policy_id, caller)
+        investor.require_auth();
+        offering::subscribe(&env, asset_id, investor, amount)
     }
 
-    /// Suspend a policy (insurer only)
-    pub fn suspend_insurance_policy(
-        env: Env,
-        policy_id: BytesN<32>,
-        insurer: Address,
-    ) -> Result<(), Error> {
-        insurer.require_auth();
-        insurance::suspend_policy(env, policy_id, insurer)
+    /// Close an offering and release proceeds or unsold tokens to the issuer (permissionless)
+    pub fn finalize_offering(env: Env, asset_id: u64) -> Result<offering::OfferingStatus, Error> {
+        offering::finalize_offering(&env, asset_id)
     }
 
-    /// Expire a policy (permissionless)
-    pub fn expire_insurance_policy(env: Env, policy_id: BytesN<32>) -> Result<(), Error> {
-        insurance::expire_policy(env, policy_id)
+    /// Claim the tokens bought in a successful offering
+    pub fn claim_allocation(env: Env, asset_id: u64, investor: Address) -> Result<i128, Error> {
+        investor.require_auth();
+        offering::claim_allocation(&env, asset_id, investor)
     }
 
-    /// Renew a policy (insurer only)
-    pub fn renew_insurance_policy(
-        env: Env,
-        policy_id: BytesN<32>,
-        new_end_date: u64,
-        new_premium: i128,
-        insurer: Address,
-    ) -> Result<(), Error> {
-        insurer.require_auth();
-        insurance::renew_policy(env, policy_id, new_end_date, new_premium, insurer)
+    /// Reclaim the payment subscribed to a failed offering
+    pub fn claim_refund(env: Env, asset_id: u64, investor: Address) -> Result<i128, Error> {
+        investor.require_auth();
+        offering::claim_refund(&env, asset_id, investor)
     }
 
-    /// Get a specific policy
-    pub fn get_insurance_policy(
-        env: Env,
-        policy_id: BytesN<32>,
-    ) -> Option<insurance::InsurancePolicy> {
-        insurance::get_policy(env, policy_id)
+    /// Push allocations or refunds to a page of a closed offering's subscribers (permissionless)
+    pub fn settle_offering(env: Env, asset_id: u64, start: u32, limit: u32) -> Result<u32, Error> {
+        offering::settle_offering(&env, asset_id, start, limit)
+    }
+
+    /// Get the offering for an asset
+    pub fn get_offering(env: Env, asset_id: u64) -> Result<offering::Offering, Error> {
+        offering::get_offering(&env, asset_id)
+    }
+
+    /// Get an investor's subscribed amount
+    pub fn get_subscription(env: Env, asset_id: u64, investor: Address) -> i128 {
+        offering::get_subscription(&env, asset_id, investor)
+    }
+
+    /// Get the number of subscribers to an asset's current offering
+    pub fn get_subscriber_count(env: Env, asset_id: u64) -> u32 {
+        offering::get_subscriber_count(&env, asset_id)
+    }
+
+    // =====================
+    // Dividend Functions
+    // =====================
+
+    /// Distribute dividends proportionally to all holders
+    pub fn distribute_dividends(env: Env, asset_id: u64, total_amount: i128) -> Result<(), Error> {
+        dividends::distribute_dividends(&env, asset_id, total_amount)
+    }
+
+    /// Claim unclaimed dividends
+    pub fn claim_dividends(env: Env, asset_id: u64, holder: Address) -> Result<i128, Error> {
+        holder.require_auth();
+        dividends::claim_dividends(&env, asset_id, holder)
+    }
+
+    /// Get unclaimed dividends for a holder
+    pub fn get_unclaimed_dividends(
+        env: Env,
+        asset_id: u64,
+        holder: Address,
+    ) -> Result<i128, Error> {
+        dividends::get_unclaimed_dividends(&env, asset_id, holder)
+    }
+
+    /// Enable revenue sharing for an asset
+    pub fn enable_revenue_sharing(env: Env, asset_id: u64) -> Result<(), Error> {
+        dividends::enable_revenue_sharing(&env, asset_id)
+    }
+
+    /// Disable revenue sharing for an asset
+    pub fn disable_revenue_sharing(env: Env, asset_id: u64) -> Result<(), Error> {
+        dividends::disable_revenue_sharing(&env, asset_id)
+    }
+
+    /// Set how long holders have to claim future distribution rounds
+    pub fn set_dividend_claim_period(
+        env: Env,
+        asset_id: u64,
+        period: u64,
+        caller: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        dividends::set_claim_period(&env, asset_id, period, caller)
+    }
+
+    /// Get the claim period applied to new distribution rounds
+    pub fn get_dividend_claim_period(env: Env, asset_id: u64) -> u64 {
+        dividends::get_claim_period(&env, asset_id)
+    }
+
+    /// Fund a distribution round in a SEP-41 payment token
+    pub fn create_distribution_round(
+        env: Env,
+        asset_id: u64,
+        payment_token: Address,
+        total_amount: i128,
+        memo_hash: BytesN<32>,
+        caller: Address,
+    ) -> Result<u32, Error> {
+        caller.require_auth();
+        dividends::create_distribution_round(
+            &env,
+            asset_id,
+            payment_token,
+            total_amount,
+            memo_hash,
+            caller,
+        )
+    }
+
+    /// Claim a holder's share of one distribution round
+    pub fn claim_round(
+        env: Env,
+        asset_id: u64,
+        round_id: u32,
+        holder: Address,
+    ) -> Result<dividends::DividendClaim, Error> {
+        holder.require_auth();
+        dividends::claim_round(&env, asset_id, round_id, holder)
+    }
+
+    /// Claim every open distribution round for a holder
+    pub fn claim_all_rounds(
+        env: Env,
+        asset_id: u64,
+        holder: Address,
+    ) -> Result<Vec<dividends::DividendClaim>, Error> {
+        holder.require_auth();
+        dividends::claim_all_rounds(&env, asset_id, holder)
+    }
+
+    /// Return the unclaimed remainder of an expired round to the issuer
+    pub fn reclaim_expired_round(
+        env: Env,
+        asset_id: u64,
+        round_id: u32,
+        caller: Address,
+    ) -> Result<i128, Error> {
+        caller.require_auth();
+        dividends::reclaim_expired_round(&env, asset_id, round_id, caller)
+    }
+
+    /// Get a distribution round
+    pub fn get_distribution_round(
+        env: Env,
+        asset_id: u64,
+        round_id: u32,
+    ) -> Result<dividends::DistributionRound, Error> {
+        dividends::get_distribution_round(&env, asset_id, round_id)
+    }
+
+    /// Get a holder's entitlement in a distribution round
+    pub fn get_round_entitlement(
+        env: Env,
+        asset_id: u64,
+        round_id: u32,
+        holder: Address,
+    ) -> Option<dividends::RoundEntitlement> {
+        dividends::get_round_entitlement(&env, asset_id, round_id, holder)
+    }
+
+    /// Get the rounds a holder can still claim
+    pub fn get_claimable_rounds(env: Env, asset_id: u64, holder: Address) -> Vec<u32> {
+        dividends::get_claimable_rounds(&env, asset_id, holder)
+    }
+
+    /// Get a holder's claim history across distribution rounds
+    pub fn get_dividend_claim_history(
+        env: Env,
+        asset_id: u64,
+        holder: Address,
+    ) -> Vec<dividends::DividendClaim> {
+        dividends::get_claim_history(&env, asset_id, holder)
+    }
+
+    /// Opt in or out of reinvesting distribution rounds into asset tokens
+    pub fn set_dividend_reinvestment(
+        env: Env,
+        asset_id: u64,
+        holder: Address,
+        enabled: bool,
+    ) -> Result<(), Error> {
+        holder.require_auth();
+        dividends::set_reinvestment(&env, asset_id, holder, enabled)
+    }
+
+    pub fn get_dividend_reinvestment(env: Env, asset_id: u64, holder: Address) -> bool {
+        dividends::get_reinvestment(&env, asset_id, holder)
+    }
+
+    /// Choose whether reinvestment mints new tokens or uses the tokenizer's balance
+    pub fn set_reinvestment_source(
+        env: Env,
+        asset_id: u64,
+        source: dividends::ReinvestmentSource,
+        caller: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        dividends::set_reinvestment_source(&env, asset_id, source, caller)
+    }
+
+    pub fn get_reinvestment_source(env: Env, asset_id: u64) -> dividends::ReinvestmentSource {
+        dividends::get_reinvestment_source(&env, asset_id)
+    }
+
+    /// Get a holder's reinvestment remainder carried forward in a payment token
+    pub fn get_reinvestment_carry(
+        env: Env,
+        asset_id: u64,
+        holder: Address,
+        payment_token: Address,
+    ) -> i128 {
+        dividends::get_reinvestment_carry(&env, asset_id, holder, payment_token)
+    }
+
+    /// Withdraw a carried-forward reinvestment remainder in cash
+    pub fn withdraw_reinvestment_carry(
+        env: Env,
+        asset_id: u64,
+        holder: Address,
+        payment_token: Address,
+    ) -> Result<i128, Error> {
+        holder.require_auth();
+        dividends::withdraw_reinvestment_carry(&env, asset_id, holder, payment_token)
+    }
+
+    /// Configure the platform fee on dividend claims and the payout accounts (admin only)
+    pub fn set_dividend_fee_config(
+        env: Env,
+        fee_bps: u32,
+        treasury: Address,
+        tax_authority: Address,
+    ) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        dividends::set_dividend_fee_config(&env, fee_bps, treasury, tax_authority)
+    }
+
+    pub fn get_dividend_fee_config(env: Env) -> Option<dividends::DividendFeeConfig> {
+        dividends::get_dividend_fee_config(&env)
+    }
+
+    /// Set the dividend withholding rate for a jurisdiction (admin only)
+    pub fn set_withholding_rate(
+        env: Env,
+        jurisdiction: String,
+        rate_bps: u32,
+    ) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        dividends::set_withholding_rate(&env, jurisdiction, rate_bps)
+    }
+
+    pub fn get_withholding_rate(env: Env, jurisdiction: String) -> u32 {
+        dividends::get_withholding_rate(&env, jurisdiction)
+    }
+
+    /// Get withheld dividend tax pooled for a payment token
+    pub fn get_withheld_balance(env: Env, payment_token: Address) -> i128 {
+        dividends::get_withheld_balance(&env, payment_token)
+    }
+
+    /// Withdraw pooled withholding (tax authority or treasury only)
+    pub fn withdraw_withheld(
+        env: Env,
+        payment_token: Address,
+        amount: i128,
+        caller: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        dividends::withdraw_withheld(&env, payment_token, amount, caller)
+    }
+
+    // =====================
+    // Voting Functions
+    // =====================
+
+    /// Cast a vote on a proposal
+    pub fn cast_vote(
+        env: Env,
+        asset_id: u64,
+        proposal_id: u64,
+        voter: Address,
+    ) -> Result<(), Error> {
+        voter.require_auth();
+        voting::cast_vote(&env, asset_id, proposal_id, voter)
+    }
+
+    /// Get vote tally for a proposal
+    pub fn get_vote_tally(env: Env, asset_id: u64, proposal_id: u64) -> Result<i128, Error> {
+        voting::get_vote_tally(&env, asset_id, proposal_id)
+    }
+
+    /// Check if an address has voted
+    pub fn has_voted(
+        env: Env,
+        asset_id: u64,
+        proposal_id: u64,
+        voter: Address,
+    ) -> Result<bool, Error> {
+        voting::has_voted(&env, asset_id, proposal_id, voter)
+    }
+
+    /// Check if proposal passed
+    pub fn proposal_passed(env: Env, asset_id: u64, proposal_id: u64) -> Result<bool, Error> {
+        voting::proposal_passed(&env, asset_id, proposal_id)
+    }
+
+    // =====================
+    // Transfer Restrictions
+    // =====================
+
+    /// Set transfer restrictions
+    pub fn set_transfer_restriction(
+        env: Env,
+        asset_id: u64,
+        require_accredited: bool,
+    ) -> Result<(), Error> {
+        transfer_restrictions::set_transfer_restriction(
+            &env,
+            asset_id,
+            TransferRestriction {
+                require_accredited,
+                geographic_allowed: Vec::new(&env),
+            },
+        )
+    }
+
+    /// Add address to whitelist
+    pub fn add_to_whitelist(env: Env, asset_id: u64, address: Address) -> Result<(), Error> {
+        transfer_restrictions::add_to_whitelist(&env, asset_id, address)
+    }
+
+    /// Remove address from whitelist
+    pub fn remove_from_whitelist(env: Env, asset_id: u64, address: Address) -> Result<(), Error> {
+        transfer_restrictions::remove_from_whitelist(&env, asset_id, address)
+    }
+
+    /// Check if address is whitelisted
+    pub fn is_whitelisted(env: Env, asset_id: u64, address: Address) -> Result<bool, Error> {
+        transfer_restrictions::is_whitelisted(&env, asset_id, address)
+    }
+
+    /// Get whitelist
+    pub fn get_whitelist(env: Env, asset_id: u64) -> Result<Vec<Address>, Error> {
+        transfer_restrictions::get_whitelist(&env, asset_id)
+    }
+
+    /// Restrict holders to investors from the given ISO country codes (tokenizer only)
+    pub fn set_allowed_jurisdictions(
+        env: Env,
+        asset_id: u64,
+        countries: Vec<String>,
+    ) -> Result<(), Error> {
+        tokenization::get_tokenized_asset(&env, asset_id)?
+            .tokenizer
+            .require_auth();
+        transfer_restrictions::set_allowed_jurisdictions(&env, asset_id, countries)
+    }
+
+    /// Bar investors from the given ISO country codes (tokenizer only)
+    pub fn set_restricted_jurisdictions(
+        env: Env,
+        asset_id: u64,
+        countries: Vec<String>,
+    ) -> Result<(), Error> {
+        tokenization::get_tokenized_asset(&env, asset_id)?
+            .tokenizer
+            .require_auth();
+        transfer_restrictions::set_restricted_jurisdictions(&env, asset_id, countries)
+    }
+
+    // =====================
+    // Identity Registry
+    // =====================
+
+    /// Trust an address to issue investor identity claims (admin only)
+    pub fn add_claim_issuer(env: Env, issuer: Address) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        identity::add_claim_issuer(&env, issuer)
+    }
+
+    /// Stop trusting a claim issuer (admin only)
+    pub fn remove_claim_issuer(env: Env, issuer: Address) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        identity::remove_claim_issuer(&env, issuer)
+    }
+
+    pub fn is_claim_issuer(env: Env, issuer: Address) -> bool {
+        identity::is_claim_issuer(&env, issuer)
+    }
+
+    /// Record an investor's compliance claims (trusted claim issuer only)
+    pub fn set_identity(
+        env: Env,
+        issuer: Address,
+        investor: Address,
+        accredited: bool,
+        jurisdiction: String,
+        kyc_expiry: u64,
+        investor_type: identity::InvestorType,
+    ) -> Result<(), Error> {
+        issuer.require_auth();
+        identity::set_identity(
+            &env,
+            issuer,
+            investor,
+            accredited,
+            jurisdiction,
+            kyc_expiry,
+            investor_type,
+        )
+    }
+
+    /// Remove an investor's compliance claims (trusted claim issuer only)
+    pub fn revoke_identity(env: Env, issuer: Address, investor: Address) -> Result<(), Error> {
+        issuer.require_auth();
+        identity::revoke_identity(&env, issuer, investor)
+    }
+
+    pub fn get_identity(env: Env, investor: Address) -> Option<identity::IdentityClaims> {
+        identity::get_identity(&env, investor)
+    }
+
+    // =====================
+    // Compliance Modules
+    // =====================
+
+    /// Replace an asset's ordered list of compliance modules (tokenizer only)
+    pub fn set_compliance_modules(
+        env: Env,
+        asset_id: u64,
+        modules: Vec<compliance::ComplianceModule>,
+        caller: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        compliance::set_compliance_modules(&env, asset_id, modules, caller)
+    }
+
+    pub fn get_compliance_modules(env: Env, asset_id: u64) -> Vec<compliance::ComplianceModule> {
+        compliance::get_compliance_modules(&env, asset_id)
+    }
+
+    /// Check the sanctions list, which is set through `set_global_denylisted`
+    pub fn is_sanctioned(env: Env, address: Address) -> bool {
+        compliance::is_sanctioned(&env, address)
+    }
+
+    // =====================
+    // Enforcement
+    // =====================
+
+    /// Appoint a compliance agent who can freeze and denylist (issuer only)
+    pub fn grant_agent(
+        env: Env,
+        asset_id: u64,
+        agent: Address,
+        caller: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        enforcement::grant_agent(&env, asset_id, agent, caller)
+    }
+
+    /// Remove a compliance agent (issuer only)
+    pub fn revoke_agent(
+        env: Env,
+        asset_id: u64,
+        agent: Address,
+        caller: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        enforcement::revoke_agent(&env, asset_id, agent, caller)
+    }
+
+    pub fn is_agent(env: Env, asset_id: u64, address: Address) -> bool {
+        enforcement::is_agent(&env, asset_id, address)
+    }
+
+    /// Freeze a holder's full balance (`None`) or a fixed amount (issuer or agent)
+    pub fn freeze_tokens(
+        env: Env,
+        asset_id: u64,
+        holder: Address,
+        amount: Option<i128>,
+        caller: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        enforcement::freeze_tokens(&env, asset_id, holder, amount, caller)
+    }
+
+    /// Lift a freeze (issuer or agent)
+    pub fn unfreeze_tokens(
+        env: Env,
+        asset_id: u64,
+        holder: Address,
+        caller: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        enforcement::unfreeze_tokens(&env, asset_id, holder, caller)
+    }
+
+    pub fn get_freeze(
+        env: Env,
+        asset_id: u64,
+        holder: Address,
+    ) -> Result<Option<enforcement::FreezeState>, Error> {
+        enforcement::get_freeze(&env, asset_id, holder)
+    }
+
+    /// Add or remove an address on an asset's denylist (issuer or agent)
+    pub fn set_denylisted(
+        env: Env,
+        asset_id: u64,
+        address: Address,
+        denylisted: bool,
+        caller: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        enforcement::set_denylisted(&env, asset_id, address, denylisted, caller)
+    }
+
+    /// Add or remove an address on the contract-wide denylist, which is the
+    /// sanctions list checked by `SanctionsBlock` (admin only)
+    pub fn set_global_denylisted(
+        env: Env,
+        address: Address,
+        denylisted: bool,
+    ) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        enforcement::set_global_denylisted(&env, address, denylisted, admin)
+    }
+
+    /// Check if an address is denylisted for an asset or globally
+    pub fn is_denylisted(env: Env, asset_id: u64, address: Address) -> bool {
+        enforcement::is_denylisted(&env, asset_id, address)
+    }
+
+    /// Move tokens out of a holder's wallet bypassing transfer rules (issuer only)
+    pub fn forced_transfer(
+        env: Env,
+        asset_id: u64,
+        from: Address,
+        to: Address,
+        amount: i128,
+        reason_hash: BytesN<32>,
+        caller: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        enforcement::forced_transfer(&env, asset_id, from, to, amount, reason_hash, caller)
+    }
+
+    pub fn get_enforcement_log(env: Env, asset_id: u64) -> Vec<enforcement::EnforcementRecord> {
+        enforcement::get_enforcement_log(&env, asset_id)
+    }
+
+    /// Get the audit log of contract-wide enforcement actions
+    pub fn get_global_enforcement_log(env: Env) -> Vec<enforcement::EnforcementRecord> {
+        enforcement::get_global_enforcement_log(&env)
+    }
+
+    // =====================
+    // Detokenization
+    // =====================
+
+    /// Propose detokenization
+    pub fn propose_detokenization(
+        env: Env,
+        asset_id: u64,
+        proposer: Address,
+    ) -> Result<u64, Error> {
+        proposer.require_auth();
+        detokenization::propose_detokenization(&env, asset_id, proposer)
+    }
+
+    /// Execute detokenization (if vote passed)
+    pub fn execute_detokenization(env: Env, asset_id: u64, proposal_id: u64) -> Result<(), Error> {
+        detokenization::execute_detokenization(&env, asset_id, proposal_id)
+    }
+
+    /// Get detokenization proposal status
+    pub fn get_detokenization_proposal(
+        env: Env,
+        asset_id: u64,
+    ) -> Result<DetokenizationProposal, Error> {
+        detokenization::get_detokenization_proposal(&env, asset_id)
+    }
+
+    /// Check if detokenization is active
+    pub fn is_detokenization_active(env: Env, asset_id: u64) -> Result<bool, Error> {
+        detokenization::is_detokenization_active(&env, asset_id)
+    }
+
+    // =====================
+    // Insurance Policy Management
+    // =====================
+
+    /// Create a new insurance policy
+    pub fn create_insurance_policy(
+        env: Env,
+        policy: insurance::InsurancePolicy,
+    ) -> Result<(), Error> {
+        policy.insurer.require_auth();
+        insurance::create_policy(env, policy)
+    }
+
+    /// Cancel a policy (holder or insurer)
+    pub fn cancel_insurance_policy(
+        env: Env,
+        policy_id: BytesN<32>,
+        caller: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        insurance::cancel_policy(env, policy_id, caller)
+    }
+
+    /// Suspend a policy (insurer only)
+    pub fn suspend_insurance_policy(
+        env: Env,
+        policy_id: BytesN<32>,
+        insurer: Address,
+    ) -> Result<(), Error> {
+        insurer.require_auth();
+        insurance::suspend_policy(env, policy_id, insurer)
+    }
+
+    /// Expire a policy (permissionless)
+    pub fn expire_insurance_policy(env: Env, policy_id: BytesN<32>) -> Result<(), Error> {
+        insurance::expire_policy(env, policy_id)
+    }
+
+    /// Renew a policy (insurer only)
+    pub fn renew_insurance_policy(
+        env: Env,
+        policy_id: BytesN<32>,
+        new_end_date: u64,
+        new_premium: i128,
+        insurer: Address,
+    ) -> Result<(), Error> {
+        insurer.require_auth();
+        insurance::renew_policy(env, policy_id, new_end_date, new_premium, insurer)
+    }
+
+    /// Get a specific policy
+    pub fn get_insurance_policy(
+        env: Env,
+        policy_id: BytesN<32>,
+    ) -> Option<insurance::InsurancePolicy> {
+        insurance::get_policy(env, policy_id)
     }
 
     /// Get all policies for an asset
@@ -835,7 +1715,159 @@ impl AssetUpContract {
         insurance::get_asset_policies(env, asset_id)
     }
 
-    /// Create a new lease. Lessor authenticates; asset must not already be actively leased.
+    /// Bill a policy's premium in installments of a SEP-41 token (insurer only)
+    pub fn set_premium_schedule(
+        env: Env,
+        policy_id: BytesN<32>,
+        insurer: Address,
+        payment_token: Address,
+        installments: u32,
+        grace_period: u64,
+    ) -> Result<(), Error> {
+        insurer.require_auth();
+        insurance::set_premium_schedule(
+            env,
+            policy_id,
+            insurer,
+            payment_token,
+            installments,
+            grace_period,
+        )
+    }
+
+    /// Pay the next premium installments to the insurer (holder only)
+    pub fn pay_premium(
+        env: Env,
+        policy_id: BytesN<32>,
+        payer: Address,
+        installments: u32,
+    ) -> Result<i128, Error> {
+        payer.require_auth();
+        insurance::pay_premium(env, policy_id, payer, installments)
+    }
+
+    /// Suspend lapsed policies and renew paid-up auto-renew policies (permissionless)
+    pub fn process_renewals(env: Env, start: u32, limit: u32) -> Result<u32, Error> {
+        insurance::process_renewals(env, start, limit)
+    }
+
+    /// Number of live billed policies `process_renewals` pages over
+    pub fn get_billed_policy_count(env: Env) -> u32 {
+        insurance::get_billed_policy_count(env)
+    }
+
+    /// Get a policy's premium schedule
+    pub fn get_premium_schedule(
+        env: Env,
+        policy_id: BytesN<32>,
+    ) -> Option<insurance::PremiumSchedule> {
+        insurance::get_premium_schedule(env, policy_id)
+    }
+
+    /// Get the premium due and unpaid for a policy's current term
+    pub fn get_premium_due(env: Env, policy_id: BytesN<32>) -> Result<i128, Error> {
+        insurance::get_premium_due(env, policy_id)
+    }
+
+    /// Get a policy's premium payment history
+    pub fn get_premium_payments(env: Env, policy_id: BytesN<32>) -> Vec<insurance::PremiumPayment> {
+        insurance::get_premium_payments(env, policy_id)
+    }
+
+    // =====================
+    // Insurance Claims
+    // =====================
+
+    /// File a claim on an active policy (holder only). Returns the claim ID.
+    pub fn file_insurance_claim(
+        env: Env,
+        policy_id: BytesN<32>,
+        claimant: Address,
+        claim_type: insurance::ClaimType,
+        amount: i128,
+    ) -> Result<BytesN<32>, Error> {
+        claimant.require_auth();
+        insurance::file_insurance_claim(env, policy_id, claimant, claim_type, amount)
+    }
+
+    /// Start reviewing a submitted or disputed claim (insurer only)
+    pub fn review_insurance_claim(
+        env: Env,
+        claim_id: BytesN<32>,
+        insurer: Address,
+    ) -> Result<(), Error> {
+        insurer.require_auth();
+        insurance::mark_insurance_claim_under_review(env, claim_id, insurer)
+    }
+
+    /// Approve a claim under review; the payout is the approved loss less the
+    /// deductible, capped at the policy's remaining coverage (insurer only)
+    pub fn approve_insurance_claim(
+        env: Env,
+        claim_id: BytesN<32>,
+        insurer: Address,
+        approved_amount: i128,
+    ) -> Result<(), Error> {
+        insurer.require_auth();
+        insurance::approve_insurance_claim(env, claim_id, insurer, approved_amount)
+    }
+
+    /// Reject a submitted or under-review claim (insurer only)
+    pub fn reject_insurance_claim(
+        env: Env,
+        claim_id: BytesN<32>,
+        insurer: Address,
+    ) -> Result<(), Error> {
+        insurer.require_auth();
+        insurance::reject_insurance_claim(env, claim_id, insurer)
+    }
+
+    /// Dispute a rejected claim (claimant only)
+    pub fn dispute_insurance_claim(
+        env: Env,
+        claim_id: BytesN<32>,
+        claimant: Address,
+    ) -> Result<(), Error> {
+        claimant.require_auth();
+        insurance::dispute_insurance_claim(env, claim_id, claimant)
+    }
+
+    /// Mark an approved claim as paid (insurer only)
+    pub fn pay_insurance_claim(
+        env: Env,
+        claim_id: BytesN<32>,
+        insurer: Address,
+    ) -> Result<(), Error> {
+        insurer.require_auth();
+        insurance::pay_insurance_claim(env, claim_id, insurer)
+    }
+
+    /// Get a specific claim
+    pub fn get_insurance_claim(
+        env: Env,
+        claim_id: BytesN<32>,
+    ) -> Option<insurance::InsuranceClaim> {
+        insurance::get_insurance_claim(env, claim_id)
+    }
+
+    /// Get all claims for an asset
+    pub fn get_asset_insurance_claims(env: Env, asset_id: BytesN<32>) -> Vec<BytesN<32>> {
+        insurance::get_asset_insurance_claims(env, asset_id)
+    }
+
+    /// Get all claims on a policy
+    pub fn get_policy_insurance_claims(env: Env, policy_id: BytesN<32>) -> Vec<BytesN<32>> {
+        insurance::get_policy_claims(env, policy_id)
+    }
+
+    /// Get the payouts approved so far against a policy's coverage
+    pub fn get_policy_claimed_amount(env: Env, policy_id: BytesN<32>) -> i128 {
+        insurance::get_policy_claimed_amount(env, policy_id)
+    }
+
+    /// Create a new lease. Lessor and lessee authenticate; the lessee's deposit is
+    /// escrowed. The lessor must own the registered asset or manage it for the
+    /// owner, and the term must not overlap another booking on the asset.
     pub fn create_lease(
         env: Env,
         asset_id: BytesN<32>,
@@ -846,14 +1878,76 @@ impl AssetUpContract {
         end: u64,
         rent: i128,
         deposit: i128,
+        terms: lease::LeasePaymentTerms,
+        renewal: lease::RenewalOption,
     ) -> Result<(), Error> {
         lessor.require_auth();
+        lessee.require_auth();
         lease::create_lease(
-            &env, asset_id, lease_id, lessor, lessee, start, end, rent, deposit,
+            &env, asset_id, lease_id, lessor, lessee, start, end, rent, deposit, terms, renewal,
+        )
+    }
+
+    /// Sublet a live lease to `sublessee` within the lease's term. The lease's
+    /// lessee and the sublessee authenticate; the sublessee's deposit is escrowed.
+    pub fn create_sublease(
+        env: Env,
+        parent_id: BytesN<32>,
+        sublease_id: BytesN<32>,
+        caller: Address,
+        sublessee: Address,
+        start: u64,
+        end: u64,
+        rent: i128,
+        deposit: i128,
+        terms: lease::LeasePaymentTerms,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        sublessee.require_auth();
+        lease::create_sublease(
+            &env,
+            parent_id,
+            sublease_id,
+            caller,
+            sublessee,
+            start,
+            end,
+            rent,
+            deposit,
+            terms,
         )
     }
 
-    /// Return a leased asset. Callable by lessor or lessee.
+    /// Consent to assigning a lease to `new_lessee`. Lessor only.
+    pub fn consent_to_assignment(
+        env: Env,
+        lease_id: BytesN<32>,
+        caller: Address,
+        new_lessee: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        lease::consent_to_assignment(&env, lease_id, caller, new_lessee)
+    }
+
+    /// Assign a lease to the consented new lessee, who buys out the deposit.
+    /// The current lessee and the new lessee authenticate.
+    pub fn assign_lease(
+        env: Env,
+        lease_id: BytesN<32>,
+        caller: Address,
+        new_lessee: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        new_lessee.require_auth();
+        lease::assign_lease(&env, lease_id, caller, new_lessee)
+    }
+
+    /// Return all sublease IDs created under a lease.
+    pub fn get_subleases(env: Env, lease_id: BytesN<32>) -> Vec<BytesN<32>> {
+        lease::get_subleases(&env, lease_id)
+    }
+
+    /// Return a leased asset. Callable by lessor or lessee. Live subleases end with it.
     pub fn return_leased_asset(
         env: Env,
         lease_id: BytesN<32>,
@@ -874,18 +1968,242 @@ impl AssetUpContract {
         lease::expire_lease(&env, lease_id)
     }
 
+    /// Pay late fees owed plus whole periods of rent. Lessee only.
+    pub fn pay_rent(
+        env: Env,
+        lease_id: BytesN<32>,
+        payer: Address,
+        periods: u32,
+    ) -> Result<i128, Error> {
+        payer.require_auth();
+        lease::pay_rent(&env, lease_id, payer, periods)
+    }
+
+    /// Record missed rent payments and charge late fees permissionlessly.
+    pub fn assess_late_fees(env: Env, lease_id: BytesN<32>) -> Result<u32, Error> {
+        lease::assess_late_fees(&env, lease_id)
+    }
+
+    /// Rent and late fees currently due on a lease.
+    pub fn get_rent_due(env: Env, lease_id: BytesN<32>) -> Result<i128, Error> {
+        lease::get_rent_due(&env, lease_id)
+    }
+
+    /// Rent past its grace period plus late fees owed.
+    pub fn get_rent_arrears(env: Env, lease_id: BytesN<32>) -> Result<i128, Error> {
+        lease::get_rent_arrears(&env, lease_id)
+    }
+
+    /// Timestamp up to which rent has been paid.
+    pub fn get_rent_paid_through(env: Env, lease_id: BytesN<32>) -> Result<u64, Error> {
+        lease::get_paid_through(&env, lease_id)
+    }
+
+    /// Propose a new end date, rent or renewal option. Lessor only.
+    pub fn propose_lease_amendment(
+        env: Env,
+        lease_id: BytesN<32>,
+        caller: Address,
+        new_end: u64,
+        new_rent: i128,
+        renewal: lease::RenewalOption,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        lease::propose_lease_amendment(&env, lease_id, caller, new_end, new_rent, renewal)
+    }
+
+    /// Accept or reject a pending lease amendment. Lessee only.
+    pub fn respond_to_amendment(
+        env: Env,
+        lease_id: BytesN<32>,
+        caller: Address,
+        accept: bool,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        lease::respond_to_amendment(&env, lease_id, caller, accept)
+    }
+
+    /// Give notice not to auto-renew a lease. Lessor or lessee.
+    pub fn give_renewal_notice(
+        env: Env,
+        lease_id: BytesN<32>,
+        caller: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        lease::give_renewal_notice(&env, lease_id, caller)
+    }
+
+    /// Renew an auto-renewing lease once its notice deadline has passed. Permissionless.
+    pub fn renew_lease(env: Env, lease_id: BytesN<32>) -> Result<u64, Error> {
+        lease::renew_lease(&env, lease_id)
+    }
+
+    /// End a lease early, paying rent due and the termination fee. Lessee only.
+    pub fn terminate_lease_early(
+        env: Env,
+        lease_id: BytesN<32>,
+        caller: Address,
+    ) -> Result<i128, Error> {
+        caller.require_auth();
+        lease::terminate_lease_early(&env, lease_id, caller)
+    }
+
+    /// Fetch the pending amendment for a lease, if any.
+    pub fn get_pending_amendment(env: Env, lease_id: BytesN<32>) -> Option<lease::LeaseAmendment> {
+        lease::get_pending_amendment(&env, lease_id)
+    }
+
+    /// Return every version of a lease's terms, oldest first.
+    pub fn get_lease_versions(env: Env, lease_id: BytesN<32>) -> Vec<lease::LeaseVersion> {
+        lease::get_lease_versions(&env, lease_id)
+    }
+
+    /// File a condition report for a returned lease and propose deposit deductions. Lessor only.
+    pub fn file_condition_report(
+        env: Env,
+        lease_id: BytesN<32>,
+        caller: Address,
+        report_hash: BytesN<32>,
+        maintenance_record_hash: Option<BytesN<32>>,
+        deduction: i128,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        lease::file_condition_report(
+            &env,
+            lease_id,
+            caller,
+            report_hash,
+            maintenance_record_hash,
+            deduction,
+        )
+    }
+
+    /// Accept or dispute proposed deposit deductions. Lessee only.
+    pub fn respond_to_deductions(
+        env: Env,
+        lease_id: BytesN<32>,
+        caller: Address,
+        accept: bool,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        lease::respond_to_deductions(&env, lease_id, caller, accept)
+    }
+
+    /// Settle an unanswered deposit proposal or an uninspected return permissionlessly.
+    pub fn settle_deposit(env: Env, lease_id: BytesN<32>) -> Result<(), Error> {
+        lease::settle_deposit(&env, lease_id)
+    }
+
+    /// Resolve a disputed deposit deduction. Admin only.
+    pub fn resolve_deposit_dispute(
+        env: Env,
+        lease_id: BytesN<32>,
+        deduction: i128,
+    ) -> Result<(), Error> {
+        let admin = Self::get_admin(env.clone())?;
+        admin.require_auth();
+        lease::resolve_deposit_dispute(&env, lease_id, deduction)
+    }
+
+    /// Fetch the deposit settlement for a lease, if any.
+    pub fn get_deposit_settlement(
+        env: Env,
+        lease_id: BytesN<32>,
+    ) -> Option<lease::DepositSettlement> {
+        lease::get_deposit_settlement(&env, lease_id)
+    }
+
+    /// Delegate or revoke leasing of an asset to a manager. Asset owner only.
+    pub fn set_lease_manager(
+        env: Env,
+        asset_id: BytesN<32>,
+        manager: Address,
+        enabled: bool,
+        caller: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        lease::set_lease_manager(&env, asset_id, manager, enabled, caller)
+    }
+
+    /// Whether `manager` may lease the asset on its current owner's behalf.
+    pub fn is_lease_manager(env: Env, asset_id: BytesN<32>, manager: Address) -> bool {
+        lease::is_asset_lease_manager(&env, asset_id, manager)
+    }
+
     /// Fetch a lease by ID.
     pub fn get_lease(env: Env, lease_id: BytesN<32>) -> Result<lease::Lease, Error> {
         lease::get_lease(&env, lease_id)
     }
 
-    /// Return the active lease for an asset, or None.
+    /// Mark a scheduled lease active once its start time arrives. Permissionless.
+    pub fn activate_lease(env: Env, lease_id: BytesN<32>) -> Result<(), Error> {
+        lease::activate_lease(&env, lease_id)
+    }
+
+    /// Return the lease whose booking covers the current time, or None.
     pub fn get_asset_active_lease(env: Env, asset_id: BytesN<32>) -> Option<lease::Lease> {
         lease::get_asset_active_lease(&env, asset_id)
     }
 
+    /// Return every live booking on an asset, ordered by start.
+    pub fn get_asset_bookings(env: Env, asset_id: BytesN<32>) -> Vec<lease::Booking> {
+        lease::get_asset_bookings(&env, asset_id)
+    }
+
+    /// Return the IDs of leases booked within `[from, to)`.
+    pub fn get_leases_in_range(
+        env: Env,
+        asset_id: BytesN<32>,
+        from: u64,
+        to: u64,
+    ) -> Result<Vec<BytesN<32>>, Error> {
+        lease::get_leases_in_range(&env, asset_id, from, to)
+    }
+
+    /// Return the earliest start at or after `from` with `duration` seconds free.
+    pub fn get_next_free_window(
+        env: Env,
+        asset_id: BytesN<32>,
+        from: u64,
+        duration: u64,
+    ) -> Result<u64, Error> {
+        lease::get_next_free_window(&env, asset_id, from, duration)
+    }
+
     /// Return all lease IDs for a given lessee.
     pub fn get_lessee_leases(env: Env, lessee: Address) -> Vec<BytesN<32>> {
         lease::get_lessee_leases(&env, lessee)
     }
+
+    /// Page through a lessor's leases, optionally filtered by status.
+    pub fn get_lessor_leases(
+        env: Env,
+        lessor: Address,
+        status: Option<lease::LeaseStatus>,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<lease::Lease>, Error> {
+        lease::get_lessor_leases(&env, lessor, status, start, limit)
+    }
+
+    /// Page through an asset's full lease history, optionally filtered by status.
+    pub fn get_asset_lease_history(
+        env: Env,
+        asset_id: BytesN<32>,
+        status: Option<lease::LeaseStatus>,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<lease::Lease>, Error> {
+        lease::get_asset_lease_history(&env, asset_id, status, start, limit)
+    }
+
+    /// Summarise a lessor's live leases, expected rent for `[from, to)` and arrears.
+    pub fn get_lessor_portfolio(
+        env: Env,
+        lessor: Address,
+        from: u64,
+        to: u64,
+    ) -> Result<lease::LessorPortfolio, Error> {
+        lease::get_lessor_portfolio(&env, lessor, from, to)
+    }
 }
@@ -0,0 +1,418 @@
+use soroban_sdk::{contracttype, symbol_short, token, Address, Env, Vec};
+
+use crate::error::Error;
+use crate::math::{self, Rounding};
+use crate::tokenization;
+use crate::transfer_restrictions;
+use crate::types::{TokenDataKey, TokenizedAsset};
+
+// ─── Types ────────────────────────────────────────────────────────────────────
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OrderSide {
+    Buy,
+    Sell,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OrderStatus {
+    Open,
+    Filled,
+    Cancelled,
+}
+
+/// Secondary market for a tokenized asset
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Market {
+    pub asset_id: u64,
+    /// SEP-41 token orders are priced and settled in
+    pub payment_token: Address,
+    pub opened_at: u64,
+}
+
+/// Limit order. `price` is in payment token units per whole asset token
+/// (10^decimals base units); `amount` and `filled` are in base units.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Order {
+    pub order_id: u64,
+    pub asset_id: u64,
+    pub maker: Address,
+    pub side: OrderSide,
+    pub price: i128,
+    pub amount: i128,
+    pub filled: i128,
+    /// Payment token the order was placed in; fills and refunds always settle in it
+    pub payment_token: Address,
+    /// Payment tokens still held in escrow for a buy order
+    pub escrowed: i128,
+    /// Token lock holding a sell order's unfilled amount (0 for buy orders)
+    pub lock_id: u32,
+    pub status: OrderStatus,
+    pub created_at: u64,
+}
+
+// ─── Storage Keys ─────────────────────────────────────────────────────────────
+
+#[contracttype]
+pub enum DataKey {
+    Market(u64),
+    Order(u64),
+    OrderCounter,
+    AssetOpenOrders(u64),
+}
+
+// ─── Internal helpers ─────────────────────────────────────────────────────────
+
+fn load_order(env: &Env, order_id: u64) -> Result<Order, Error> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Order(order_id))
+        .ok_or(Error::OrderNotFound)
+}
+
+fn save_order(env: &Env, order: &Order) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Order(order.order_id), order);
+}
+
+fn load_market(env: &Env, asset_id: u64) -> Result<Market, Error> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Market(asset_id))
+        .ok_or(Error::AssetNotFound)
+}
+
+fn next_order_id(env: &Env) -> u64 {
+    let id: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::OrderCounter)
+        .unwrap_or(0)
+        + 1;
+    env.storage().persistent().set(&DataKey::OrderCounter, &id);
+    id
+}
+
+fn open_order_ids(env: &Env, asset_id: u64) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AssetOpenOrders(asset_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn remove_open_order(env: &Env, asset_id: u64, order_id: u64) {
+    let mut ids = open_order_ids(env, asset_id);
+    if let Some(index) = ids.iter().position(|id| id == order_id) {
+        ids.remove(index as u32);
+        env.storage()
+            .persistent()
+            .set(&DataKey::AssetOpenOrders(asset_id), &ids);
+    }
+}
+
+/// Payment due for `amount` base units at `price` per whole token, rounded down
+fn order_cost(amount: i128, price: i128, decimals: u32) -> Result<i128, Error> {
+    math::mul_div(amount, price, math::pow10(decimals)?, Rounding::Down)
+}
+
+fn load_tokenized_asset(env: &Env, asset_id: u64) -> Result<TokenizedAsset, Error> {
+    env.storage()
+        .persistent()
+        .get(&TokenDataKey::TokenizedAsset(asset_id))
+        .ok_or(Error::AssetNotTokenized)
+}
+
+/// Move asset tokens between holders, honouring whitelist, accreditation and lock rules
+fn settle_tokens(
+    env: &Env,
+    asset_id: u64,
+    seller: &Address,
+    buyer: &Address,
+    amount: i128,
+    price: i128,
+) -> Result<(), Error> {
+    transfer_restrictions::validate_transfer(env, asset_id, seller.clone(), buyer.clone())?;
+    tokenization::transfer_tokens(env, asset_id, seller.clone(), buyer.clone(), amount)?;
+    tokenization::record_purchase_price(env, asset_id, buyer.clone(), amount, price)
+}
+
+// ─── Public functions (called from lib.rs) ────────────────────────────────────
+
+/// Open a secondary market for an asset. Only the tokenizer can open it.
+pub fn open_market(
+    env: &Env,
+    asset_id: u64,
+    payment_token: Address,
+    caller: Address,
+) -> Result<(), Error> {
+    let tokenized_asset = load_tokenized_asset(env, asset_id)?;
+    if tokenized_asset.tokenizer != caller {
+        return Err(Error::Unauthorized);
+    }
+
+    let market = Market {
+        asset_id,
+        payment_token: payment_token.clone(),
+        opened_at: env.ledger().timestamp(),
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::Market(asset_id), &market);
+
+    // Emit event: (asset_id, payment_token)
+    env.events()
+        .publish(("market", "market_opened"), (asset_id, payment_token));
+
+    Ok(())
+}
+
+pub fn get_market(env: &Env, asset_id: u64) -> Option<Market> {
+    env.storage().persistent().get(&DataKey::Market(asset_id))
+}
+
+/// Release whatever is left of a sell order's token lock
+fn release_order_lock(env: &Env, order: &Order) -> Result<(), Error> {
+    if order.lock_id != 0 {
+        tokenization::reduce_token_lock(
+            env,
+            order.asset_id,
+            order.maker.clone(),
+            order.lock_id,
+            i128::MAX,
+        )?;
+    }
+    Ok(())
+}
+
+/// Place a limit order. Buy orders escrow their full payment up front;
+/// sell orders lock the offered tokens until filled or cancelled.
+pub fn place_order(
+    env: &Env,
+    asset_id: u64,
+    maker: Address,
+    side: OrderSide,
+    price: i128,
+    amount: i128,
+) -> Result<u64, Error> {
+    if price <= 0 || amount <= 0 {
+        return Err(Error::InvalidPayment);
+    }
+
+    let market = load_market(env, asset_id)?;
+    let tokenized_asset = load_tokenized_asset(env, asset_id)?;
+
+    let mut escrowed = 0;
+    let mut lock_id = 0;
+    match side {
+        OrderSide::Sell => {
+            let spendable = tokenization::get_spendable_balance(env, asset_id, maker.clone())?;
+            if spendable < amount {
+                return Err(Error::InsufficientBalance);
+            }
+            lock_id = tokenization::place_token_lock(
+                env,
+                asset_id,
+                maker.clone(),
+                amount,
+                u64::MAX,
+                symbol_short!("order"),
+            )?;
+        }
+        OrderSide::Buy => {
+            escrowed = order_cost(amount, price, tokenized_asset.decimals)?;
+            if escrowed <= 0 {
+                return Err(Error::InvalidPayment);
+            }
+            token::Client::new(env, &market.payment_token).transfer(
+                &maker,
+                &env.current_contract_address(),
+                &escrowed,
+            );
+        }
+    }
+
+    let order_id = next_order_id(env);
+    let order = Order {
+        order_id,
+        asset_id,
+        maker: maker.clone(),
+        side: side.clone(),
+        price,
+        amount,
+        filled: 0,
+        payment_token: market.payment_token,
+        escrowed,
+        lock_id,
+        status: OrderStatus::Open,
+        created_at: env.ledger().timestamp(),
+    };
+    save_order(env, &order);
+
+    let mut ids = open_order_ids(env, asset_id);
+    ids.push_back(order_id);
+    env.storage()
+        .persistent()
+        .set(&DataKey::AssetOpenOrders(asset_id), &ids);
+
+    // Emit event: (order_id, asset_id, maker, side, price, amount)
+    env.events().publish(
+        ("market", "order_placed"),
+        (order_id, asset_id, maker, side, price, amount),
+    );
+
+    Ok(order_id)
+}
+
+/// Take up to the remaining amount of an open order.
+/// Payment and asset tokens move in the same transaction; any failed check reverts both.
+/// Returns the payment amount settled.
+pub fn fill_order(env: &Env, order_id: u64, taker: Address, amount: i128) -> Result<i128, Error> {
+    let mut order = load_order(env, order_id)?;
+    if order.status != OrderStatus::Open {
+        return Err(Error::OrderNotFound);
+    }
+    if taker == order.maker {
+        return Err(Error::Unauthorized);
+    }
+    if amount <= 0 {
+        return Err(Error::InvalidPayment);
+    }
+    if amount > math::sub(order.amount, order.filled)? {
+        return Err(Error::InsufficientBalance);
+    }
+
+    let tokenized_asset = load_tokenized_asset(env, order.asset_id)?;
+    let cost = order_cost(amount, order.price, tokenized_asset.decimals)?;
+    if cost <= 0 {
+        return Err(Error::InvalidPayment);
+    }
+
+    let payment = token::Client::new(env, &order.payment_token);
+    match order.side {
+        OrderSide::Sell => {
+            tokenization::reduce_token_lock(
+                env,
+                order.asset_id,
+                order.maker.clone(),
+                order.lock_id,
+                amount,
+            )?;
+            settle_tokens(
+                env,
+                order.asset_id,
+                &order.maker,
+                &taker,
+                amount,
+                order.price,
+            )?;
+            payment.transfer(&taker, &order.maker, &cost);
+        }
+        OrderSide::Buy => {
+            settle_tokens(
+                env,
+                order.asset_id,
+                &taker,
+                &order.maker,
+                amount,
+                order.price,
+            )?;
+            payment.transfer(&env.current_contract_address(), &taker, &cost);
+            order.escrowed = math::sub(order.escrowed, cost)?;
+        }
+    }
+
+    order.filled = math::add(order.filled, amount)?;
+    if order.filled == order.amount {
+        order.status = OrderStatus::Filled;
+        remove_open_order(env, order.asset_id, order_id);
+        // Rounding after a consolidation can leave part of the lock behind
+        release_order_lock(env, &order)?;
+
+        // Return any rounding remainder left in escrow
+        if order.escrowed > 0 {
+            payment.transfer(
+                &env.current_contract_address(),
+                &order.maker,
+                &order.escrowed,
+            );
+            order.escrowed = 0;
+        }
+    }
+    save_order(env, &order);
+
+    // Emit event: (order_id, taker, amount, cost)
+    env.events()
+        .publish(("market", "order_filled"), (order_id, taker, amount, cost));
+
+    Ok(cost)
+}
+
+/// Cancel an open order, refunding escrowed payment and unlocking unsold tokens. Maker only.
+pub fn cancel_order(env: &Env, order_id: u64, caller: Address) -> Result<(), Error> {
+    let mut order = load_order(env, order_id)?;
+    if caller != order.maker {
+        return Err(Error::Unauthorized);
+    }
+    if order.status != OrderStatus::Open {
+        return Err(Error::OrderNotFound);
+    }
+
+    release_order_lock(env, &order)?;
+    if order.escrowed > 0 {
+        token::Client::new(env, &order.payment_token).transfer(
+            &env.current_contract_address(),
+            &order.maker,
+            &order.escrowed,
+        );
+        order.escrowed = 0;
+    }
+
+    order.status = OrderStatus::Cancelled;
+    save_order(env, &order);
+    remove_open_order(env, order.asset_id, order_id);
+
+    // Emit event: (order_id, maker)
+    env.events()
+        .publish(("market", "order_cancelled"), (order_id, caller));
+
+    Ok(())
+}
+
+pub fn get_order(env: &Env, order_id: u64) -> Result<Order, Error> {
+    load_order(env, order_id)
+}
+
+/// Get all open orders for an asset, oldest first
+pub fn get_open_orders(env: &Env, asset_id: u64) -> Vec<Order> {
+    let mut orders = Vec::new(env);
+    for id in open_order_ids(env, asset_id).iter() {
+        if let Ok(order) = load_order(env, id) {
+            orders.push_back(order);
+        }
+    }
+    orders
+}
+
+/// Re-denominate open orders after a split or consolidation. Token amounts
+/// scale with the ratio and prices per whole token scale inversely; buy-side
+/// escrow is in payment units and stays as is.
+pub fn rescale_open_orders(
+    env: &Env,
+    asset_id: u64,
+    ratio_from: u32,
+    ratio_to: u32,
+) -> Result<(), Error> {
+    for id in open_order_ids(env, asset_id).iter() {
+        let mut order = load_order(env, id)?;
+        order.amount = tokenization::scale_by_ratio(order.amount, ratio_from, ratio_to)?;
+        order.filled = tokenization::scale_by_ratio(order.filled, ratio_from, ratio_to)?;
+        order.price = tokenization::scale_by_ratio(order.price, ratio_to, ratio_from)?;
+        save_order(env, &order);
+    }
+    Ok(())
+}
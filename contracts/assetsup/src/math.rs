@@ -0,0 +1,123 @@
+use crate::error::Error;
+
+/// Basis points in one whole (100%)
+pub const BPS_DENOMINATOR: i128 = 10_000;
+
+// ─── Types ────────────────────────────────────────────────────────────────────
+
+/// How to round the result of a division
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Rounding {
+    /// Toward zero
+    Down,
+    /// Away from zero
+    Up,
+    /// To the nearest integer, halves away from zero
+    Nearest,
+}
+
+// ─── Internal helpers ─────────────────────────────────────────────────────────
+
+/// Full 256-bit product of two `u128` values as `(high, low)` words
+fn wide_mul(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+    let (a_hi, a_lo) = (a >> 64, a & MASK);
+    let (b_hi, b_lo) = (b >> 64, b & MASK);
+
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_hi = a_hi * b_hi;
+
+    let cross = (lo_lo >> 64) + (hi_lo & MASK) + (lo_hi & MASK);
+    let low = (cross << 64) | (lo_lo & MASK);
+    let high = hi_hi + (hi_lo >> 64) + (lo_hi >> 64) + (cross >> 64);
+    (high, low)
+}
+
+/// Divide a 256-bit value by `d`, returning `(quotient, remainder)`.
+/// Requires `high < d` so the quotient fits in 128 bits.
+fn wide_div(high: u128, low: u128, d: u128) -> (u128, u128) {
+    let mut quotient = 0u128;
+    let mut remainder = high;
+    for i in (0..128).rev() {
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | ((low >> i) & 1);
+        if carry == 1 || remainder >= d {
+            remainder = remainder.wrapping_sub(d);
+            quotient |= 1 << i;
+        }
+    }
+    (quotient, remainder)
+}
+
+// ─── Public functions ─────────────────────────────────────────────────────────
+
+/// Compute `a * b / denominator` with a 256-bit intermediate product, so the
+/// result is exact whenever it fits in an `i128`.
+pub fn mul_div(a: i128, b: i128, denominator: i128, rounding: Rounding) -> Result<i128, Error> {
+    if denominator == 0 {
+        return Err(Error::MathOverflow);
+    }
+    let negative = (a < 0) ^ (b < 0) ^ (denominator < 0);
+    let d = denominator.unsigned_abs();
+
+    let (high, low) = wide_mul(a.unsigned_abs(), b.unsigned_abs());
+    if high >= d {
+        return Err(Error::MathOverflow);
+    }
+    let (mut quotient, remainder) = wide_div(high, low, d);
+
+    let round_away = match rounding {
+        Rounding::Down => false,
+        Rounding::Up => remainder > 0,
+        Rounding::Nearest => remainder >= d - remainder,
+    };
+    if round_away {
+        quotient = quotient.checked_add(1).ok_or(Error::MathOverflow)?;
+    }
+
+    if negative {
+        if quotient > i128::MIN.unsigned_abs() {
+            return Err(Error::MathOverflow);
+        }
+        Ok((quotient as i128).wrapping_neg())
+    } else {
+        i128::try_from(quotient).map_err(|_| Error::MathOverflow)
+    }
+}
+
+/// `part` as basis points of `total`, rounded down. Zero when `total` is zero.
+pub fn bps_of(part: i128, total: i128) -> Result<i128, Error> {
+    if total == 0 {
+        return Ok(0);
+    }
+    mul_div(part, BPS_DENOMINATOR, total, Rounding::Down)
+}
+
+/// Apply a basis-point rate to `amount`
+pub fn apply_bps(amount: i128, bps: i128, rounding: Rounding) -> Result<i128, Error> {
+    mul_div(amount, bps, BPS_DENOMINATOR, rounding)
+}
+
+pub fn add(a: i128, b: i128) -> Result<i128, Error> {
+    a.checked_add(b).ok_or(Error::MathOverflow)
+}
+
+pub fn mul(a: i128, b: i128) -> Result<i128, Error> {
+    a.checked_mul(b).ok_or(Error::MathOverflow)
+}
+
+/// Subtract amounts that must not go negative, such as balances and supply
+pub fn sub(a: i128, b: i128) -> Result<i128, Error> {
+    match a.checked_sub(b) {
+        Some(result) if result >= 0 => Ok(result),
+        _ => Err(Error::MathUnderflow),
+    }
+}
+
+/// `10^decimals` as an `i128`
+pub fn pow10(decimals: u32) -> Result<i128, Error> {
+    10i128.checked_pow(decimals).ok_or(Error::MathOverflow)
+}
@@ -0,0 +1,479 @@
+use soroban_sdk::{contracttype, token, Address, Env, Vec};
+
+use crate::error::Error;
+use crate::math::{self, Rounding};
+use crate::tokenization;
+use crate::transfer_restrictions;
+use crate::types::{TokenDataKey, TokenizedAsset};
+
+// ─── Types ────────────────────────────────────────────────────────────────────
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OfferingStatus {
+    Active,
+    /// Soft cap reached; proceeds released and tokens claimable by investors
+    Succeeded,
+    /// Soft cap missed; subscriptions refundable to investors
+    Failed,
+}
+
+/// Terms of a primary offering. Amounts are in payment token units;
+/// `price_per_token` is per whole asset token (10^decimals base units).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OfferingTerms {
+    pub payment_token: Address,
+    pub price_per_token: i128,
+    /// Minimum raise for the offering to succeed
+    pub soft_cap: i128,
+    /// Maximum raise accepted
+    pub hard_cap: i128,
+    pub max_per_investor: i128,
+    pub start: u64,
+    pub end: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Offering {
+    pub asset_id: u64,
+    pub issuer: Address,
+    pub terms: OfferingTerms,
+    /// Asset tokens escrowed by the contract for distribution
+    pub tokens_escrowed: i128,
+    pub raised: i128,
+    pub status: OfferingStatus,
+    /// Investors who have not yet claimed their allocation or refund
+    pub pending_claims: u32,
+}
+
+// ─── Storage Keys ─────────────────────────────────────────────────────────────
+
+#[contracttype]
+pub enum DataKey {
+    Offering(u64),
+    Subscription(u64, Address),
+    /// Page of an asset's current offering subscribers in subscription
+    /// order; see `SUBSCRIBER_PAGE_SIZE`
+    SubscriberPage(u64, u32),
+    /// Number of subscribers to an asset's current offering
+    SubscriberCount(u64),
+}
+
+/// Subscribers are indexed in fixed-size pages so no single entry grows
+/// with the number of investors
+pub const SUBSCRIBER_PAGE_SIZE: u32 = 100;
+
+// ─── Internal helpers ─────────────────────────────────────────────────────────
+
+fn load_offering(env: &Env, asset_id: u64) -> Result<Offering, Error> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Offering(asset_id))
+        .ok_or(Error::OfferingNotActive)
+}
+
+fn save_offering(env: &Env, offering: &Offering) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Offering(offering.asset_id), offering);
+}
+
+fn subscriber_page(env: &Env, asset_id: u64, page: u32) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SubscriberPage(asset_id, page))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Add a first-time subscriber to the end of the offering's index. Pages left
+/// over from an earlier offering are overwritten rather than appended to.
+fn index_subscriber(env: &Env, asset_id: u64, investor: &Address) {
+    let store = env.storage().persistent();
+    let slot = get_subscriber_count(env, asset_id);
+    let mut page = if slot.is_multiple_of(SUBSCRIBER_PAGE_SIZE) {
+        Vec::new(env)
+    } else {
+        subscriber_page(env, asset_id, slot / SUBSCRIBER_PAGE_SIZE)
+    };
+    page.push_back(investor.clone());
+    store.set(
+        &DataKey::SubscriberPage(asset_id, slot / SUBSCRIBER_PAGE_SIZE),
+        &page,
+    );
+    store.set(&DataKey::SubscriberCount(asset_id), &(slot + 1));
+}
+
+/// Asset tokens bought by `payment` at `price` per whole token, rounded down
+fn tokens_for_payment(payment: i128, price: i128, decimals: u32) -> Result<i128, Error> {
+    math::mul_div(payment, math::pow10(decimals)?, price, Rounding::Down)
+}
+
+// ─── Public functions (called from lib.rs) ────────────────────────────────────
+
+/// Create a primary offering. The tokens needed to fill the hard cap are moved
+/// from the issuer into contract escrow until the offering is finalized.
+pub fn create_offering(
+    env: &Env,
+    asset_id: u64,
+    issuer: Address,
+    terms: OfferingTerms,
+) -> Result<(), Error> {
+    let tokenized_asset: TokenizedAsset = env
+        .storage()
+        .persistent()
+        .get(&TokenDataKey::TokenizedAsset(asset_id))
+        .ok_or(Error::AssetNotTokenized)?;
+    if tokenized_asset.tokenizer != issuer {
+        return Err(Error::Unauthorized);
+    }
+
+    // An earlier offering must be closed and fully claimed
+    if let Ok(existing) = load_offering(env, asset_id) {
+        if existing.status == OfferingStatus::Active || existing.pending_claims > 0 {
+            return Err(Error::OfferingNotActive);
+        }
+    }
+
+    if terms.price_per_token <= 0
+        || terms.soft_cap <= 0
+        || terms.soft_cap > terms.hard_cap
+        || terms.max_per_investor <= 0
+    {
+        return Err(Error::InvalidPayment);
+    }
+    if terms.start >= terms.end || terms.end <= env.ledger().timestamp() {
+        return Err(Error::InvalidTimestamps);
+    }
+
+    let tokens = tokens_for_payment(
+        terms.hard_cap,
+        terms.price_per_token,
+        tokenized_asset.decimals,
+    )?;
+    if tokens <= 0 {
+        return Err(Error::InvalidPayment);
+    }
+    tokenization::transfer_tokens(
+        env,
+        asset_id,
+        issuer.clone(),
+        env.current_contract_address(),
+        tokens,
+    )?;
+
+    let offering = Offering {
+        asset_id,
+        issuer: issuer.clone(),
+        terms: terms.clone(),
+        tokens_escrowed: tokens,
+        raised: 0,
+        status: OfferingStatus::Active,
+        pending_claims: 0,
+    };
+    save_offering(env, &offering);
+    env.storage()
+        .persistent()
+        .remove(&DataKey::SubscriberCount(asset_id));
+
+    // Emit event: (asset_id, issuer, hard_cap, tokens_escrowed)
+    env.events().publish(
+        ("offering", "created"),
+        (asset_id, issuer, terms.hard_cap, tokens),
+    );
+
+    Ok(())
+}
+
+/// Subscribe to an active offering. Payment is escrowed until finalization.
+pub fn subscribe(env: &Env, asset_id: u64, investor: Address, amount: i128) -> Result<(), Error> {
+    let mut offering = load_offering(env, asset_id)?;
+    let now = env.ledger().timestamp();
+    if offering.status != OfferingStatus::Active
+        || now < offering.terms.start
+        || now >= offering.terms.end
+    {
+        return Err(Error::OfferingNotActive);
+    }
+
+    if amount <= 0 {
+        return Err(Error::InvalidPayment);
+    }
+
+    // Investors must be eligible to receive the asset tokens
+    transfer_restrictions::validate_transfer(
+        env,
+        asset_id,
+        offering.issuer.clone(),
+        investor.clone(),
+    )?;
+
+    let store = env.storage().persistent();
+    let subscription_key = DataKey::Subscription(asset_id, investor.clone());
+    let contributed: i128 = store.get(&subscription_key).unwrap_or(0);
+
+    let new_contribution = contributed.checked_add(amount).ok_or(Error::MathOverflow)?;
+    let new_raised = offering
+        .raised
+        .checked_add(amount)
+        .ok_or(Error::MathOverflow)?;
+    if new_contribution > offering.terms.max_per_investor || new_raised > offering.terms.hard_cap {
+        return Err(Error::InvalidPayment);
+    }
+
+    token::Client::new(env, &offering.terms.payment_token).transfer(
+        &investor,
+        &env.current_contract_address(),
+        &amount,
+    );
+
+    if contributed == 0 {
+        offering.pending_claims += 1;
+        index_subscriber(env, asset_id, &investor);
+    }
+    store.set(&subscription_key, &new_contribution);
+
+    offering.raised = new_raised;
+    save_offering(env, &offering);
+
+    // Emit event: (asset_id, investor, amount, raised)
+    env.events().publish(
+        ("offering", "subscribed"),
+        (asset_id, investor, amount, new_raised),
+    );
+
+    Ok(())
+}
+
+/// Close an offering once its window has ended or the hard cap is reached.
+/// Permissionless: if the soft cap was met the proceeds go to the issuer and
+/// the tokens not needed for allocations return to it; otherwise everything
+/// returns to the issuer. Investors then claim their allocation or refund, or
+/// anyone settles them in pages with `settle_offering`.
+pub fn finalize_offering(env: &Env, asset_id: u64) -> Result<OfferingStatus, Error> {
+    let mut offering = load_offering(env, asset_id)?;
+    if offering.status != OfferingStatus::Active {
+        return Err(Error::OfferingNotActive);
+    }
+    if env.ledger().timestamp() < offering.terms.end && offering.raised < offering.terms.hard_cap {
+        return Err(Error::OfferingNotActive);
+    }
+
+    let escrow = env.current_contract_address();
+    let succeeded = offering.raised >= offering.terms.soft_cap;
+
+    // Rounding each allocation down never needs more than the whole raise buys
+    let reserved = if succeeded {
+        let tokenized_asset = tokenization::get_tokenized_asset(env, asset_id)?;
+        tokens_for_payment(
+            offering.raised,
+            offering.terms.price_per_token,
+            tokenized_asset.decimals,
+        )?
+        .min(offering.tokens_escrowed)
+    } else {
+        0
+    };
+
+    if succeeded && offering.raised > 0 {
+        token::Client::new(env, &offering.terms.payment_token).transfer(
+            &escrow,
+            &offering.issuer,
+            &offering.raised,
+        );
+    }
+
+    let unsold = math::sub(offering.tokens_escrowed, reserved)?;
+    if unsold > 0 {
+        tokenization::move_tokens(env, asset_id, escrow, offering.issuer.clone(), unsold)?;
+    }
+
+    offering.tokens_escrowed = reserved;
+    offering.status = if succeeded {
+        OfferingStatus::Succeeded
+    } else {
+        OfferingStatus::Failed
+    };
+    release_remainder(env, &mut offering)?;
+
+    // Emit event: (asset_id, succeeded, raised, reserved)
+    env.events().publish(
+        ("offering", "finalized"),
+        (asset_id, succeeded, offering.raised, reserved),
+    );
+
+    Ok(offering.status)
+}
+
+/// Take one investor's subscription off the book once the offering has closed
+fn take_subscription(
+    env: &Env,
+    offering: &mut Offering,
+    investor: &Address,
+    status: OfferingStatus,
+) -> Result<i128, Error> {
+    if offering.status != status {
+        return Err(Error::OfferingNotActive);
+    }
+    let store = env.storage().persistent();
+    let key = DataKey::Subscription(offering.asset_id, investor.clone());
+    let contributed: i128 = store.get(&key).unwrap_or(0);
+    if contributed <= 0 {
+        return Err(Error::InvalidPayment);
+    }
+    store.remove(&key);
+    offering.pending_claims = offering.pending_claims.saturating_sub(1);
+    Ok(contributed)
+}
+
+/// Return rounding dust to the issuer once every allocation has been claimed
+fn release_remainder(env: &Env, offering: &mut Offering) -> Result<(), Error> {
+    if offering.pending_claims == 0 && offering.tokens_escrowed > 0 {
+        tokenization::move_tokens(
+            env,
+            offering.asset_id,
+            env.current_contract_address(),
+            offering.issuer.clone(),
+            offering.tokens_escrowed,
+        )?;
+        offering.tokens_escrowed = 0;
+    }
+    save_offering(env, offering);
+    Ok(())
+}
+
+/// Claim the asset tokens bought in a successful offering. Returns the tokens received.
+pub fn claim_allocation(env: &Env, asset_id: u64, investor: Address) -> Result<i128, Error> {
+    let mut offering = load_offering(env, asset_id)?;
+    let contributed = take_subscription(env, &mut offering, &investor, OfferingStatus::Succeeded)?;
+
+    let tokenized_asset = tokenization::get_tokenized_asset(env, asset_id)?;
+    let tokens = tokens_for_payment(
+        contributed,
+        offering.terms.price_per_token,
+        tokenized_asset.decimals,
+    )?
+    .min(offering.tokens_escrowed);
+    if tokens > 0 {
+        tokenization::transfer_tokens(
+            env,
+            asset_id,
+            env.current_contract_address(),
+            investor.clone(),
+            tokens,
+        )?;
+        tokenization::record_purchase_price(
+            env,
+            asset_id,
+            investor.clone(),
+            tokens,
+            offering.terms.price_per_token,
+        )?;
+        offering.tokens_escrowed = math::sub(offering.tokens_escrowed, tokens)?;
+    }
+    release_remainder(env, &mut offering)?;
+
+    // Emit event: (asset_id, investor, contributed, tokens)
+    env.events().publish(
+        ("offering", "allocation_claimed"),
+        (asset_id, investor, contributed, tokens),
+    );
+
+    Ok(tokens)
+}
+
+/// Reclaim the payment subscribed to a failed offering. Returns the amount refunded.
+pub fn claim_refund(env: &Env, asset_id: u64, investor: Address) -> Result<i128, Error> {
+    let mut offering = load_offering(env, asset_id)?;
+    let contributed = take_subscription(env, &mut offering, &investor, OfferingStatus::Failed)?;
+    save_offering(env, &offering);
+
+    token::Client::new(env, &offering.terms.payment_token).transfer(
+        &env.current_contract_address(),
+        &investor,
+        &contributed,
+    );
+
+    // Emit event: (asset_id, investor, contributed)
+    env.events().publish(
+        ("offering", "refund_claimed"),
+        (asset_id, investor, contributed),
+    );
+
+    Ok(contributed)
+}
+
+/// Push allocations or refunds to a page of a closed offering's subscribers
+/// (permissionless), so investors need not claim them. Subscribers who have
+/// already claimed are skipped. Positions never shift, so a subscriber whose
+/// allocation cannot be delivered can be paged around and left to claim once
+/// eligible. Returns the number of subscriptions settled.
+pub fn settle_offering(env: &Env, asset_id: u64, start: u32, limit: u32) -> Result<u32, Error> {
+    let offering = load_offering(env, asset_id)?;
+    if offering.status == OfferingStatus::Active {
+        return Err(Error::OfferingNotActive);
+    }
+
+    let mut settled = 0;
+    let end = start
+        .saturating_add(limit)
+        .min(get_subscriber_count(env, asset_id));
+    let mut page = Vec::new(env);
+    for index in start..end {
+        if index == start || index % SUBSCRIBER_PAGE_SIZE == 0 {
+            page = subscriber_page(env, asset_id, index / SUBSCRIBER_PAGE_SIZE);
+        }
+        let investor = page.get_unchecked(index % SUBSCRIBER_PAGE_SIZE);
+        if get_subscription(env, asset_id, investor.clone()) <= 0 {
+            continue;
+        }
+        if offering.status == OfferingStatus::Succeeded {
+            claim_allocation(env, asset_id, investor)?;
+        } else {
+            claim_refund(env, asset_id, investor)?;
+        }
+        settled += 1;
+    }
+
+    Ok(settled)
+}
+
+pub fn get_offering(env: &Env, asset_id: u64) -> Result<Offering, Error> {
+    load_offering(env, asset_id)
+}
+
+/// Get the amount an investor has subscribed to an asset's offering
+pub fn get_subscription(env: &Env, asset_id: u64, investor: Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Subscription(asset_id, investor))
+        .unwrap_or(0)
+}
+
+/// Get the number of subscribers to an asset's current offering
+pub fn get_subscriber_count(env: &Env, asset_id: u64) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SubscriberCount(asset_id))
+        .unwrap_or(0)
+}
+
+/// Re-denominate an offering that still holds tokens after a split or consolidation
+pub fn rescale_offering(
+    env: &Env,
+    asset_id: u64,
+    ratio_from: u32,
+    ratio_to: u32,
+) -> Result<(), Error> {
+    let mut offering = match load_offering(env, asset_id) {
+        Ok(offering) if offering.tokens_escrowed > 0 => offering,
+        _ => return Ok(()),
+    };
+    offering.tokens_escrowed =
+        tokenization::scale_by_ratio(offering.tokens_escrowed, ratio_from, ratio_to)?;
+    offering.terms.price_per_token =
+        tokenization::scale_by_ratio(offering.terms.price_per_token, ratio_to, ratio_from)?;
+    save_offering(env, &offering);
+    Ok(())
+}
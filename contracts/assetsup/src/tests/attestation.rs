@@ -0,0 +1,278 @@
+use crate::attestation::{AttestationField, AttestorRole, MAX_ATTESTATION_AGE};
+use crate::tests::helpers::*;
+use crate::types::AssetType;
+use crate::valuation::ValuationMethod;
+use crate::AssetUpContractClient;
+use soroban_sdk::testutils::Ledger;
+use soroban_sdk::{BytesN, Env, String};
+
+fn tokenize(env: &Env, client: &AssetUpContractClient, tokenizer: &soroban_sdk::Address) {
+    client.tokenize_asset(
+        &1u64,
+        &String::from_str(env, "TST"),
+        &1000000i128,
+        &6u32,
+        &100i128,
+        tokenizer,
+        &String::from_str(env, "Test Token"),
+        &String::from_str(env, "A test tokenized asset"),
+        &AssetType::Physical,
+    );
+}
+
+#[test]
+fn test_register_attestor() {
+    let env = create_env();
+    let (admin, _, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+
+    let appraiser = create_attestor_key(1);
+    let appraiser_pk = attestor_public_key(&env, &appraiser);
+    client.register_attestor(&appraiser_pk, &AttestorRole::Appraiser);
+
+    let stored = client.get_attestor(&appraiser_pk).unwrap();
+    assert_eq!(stored.public_key, appraiser_pk);
+    assert_eq!(stored.role, AttestorRole::Appraiser);
+
+    client.remove_attestor(&appraiser_pk);
+    assert!(client.get_attestor(&appraiser_pk).is_none());
+}
+
+#[test]
+fn test_attested_valuation_recorded_in_history() {
+    let env = create_env();
+    let (admin, user1, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    tokenize(&env, &client, &user1);
+
+    let appraiser = create_attestor_key(1);
+    let appraiser_pk = attestor_public_key(&env, &appraiser);
+    client.register_attestor(&appraiser_pk, &AttestorRole::Appraiser);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    let signature = sign_valuation(&env, &appraiser, 1, 5000000, 1000);
+    client.update_valuation(
+        &1u64,
+        &5000000i128,
+        &ValuationMethod::MarketComparison,
+        &appraiser_pk,
+        &1000u64,
+        &signature,
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+    let signature = sign_valuation(&env, &appraiser, 1, 6000000, 1900);
+    client.update_valuation(
+        &1u64,
+        &6000000i128,
+        &ValuationMethod::MarketComparison,
+        &appraiser_pk,
+        &1900u64,
+        &signature,
+    );
+
+    assert_eq!(client.get_tokenized_asset(&1u64).valuation, 6000000);
+
+    let history = client.get_attestations(&1u64);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(0).unwrap().timestamp, 1000);
+    assert_eq!(history.get(1).unwrap().recorded_at, 2000);
+    assert_eq!(
+        history.get(1).unwrap().value_hash,
+        valuation_value_hash(&env, 6000000)
+    );
+
+    let latest = client
+        .get_latest_attestation(&1u64, &AttestationField::Valuation)
+        .unwrap();
+    assert_eq!(latest.timestamp, 1900);
+    assert_eq!(latest.attestor, appraiser_pk);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_valuation_from_unregistered_attestor() {
+    let env = create_env();
+    let (admin, user1, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    tokenize(&env, &client, &user1);
+
+    let stranger = create_attestor_key(9);
+    let stranger_pk = attestor_public_key(&env, &stranger);
+    let signature = sign_valuation(&env, &stranger, 1, 5000000, 0);
+
+    client.update_valuation(
+        &1u64,
+        &5000000i128,
+        &ValuationMethod::MarketComparison,
+        &stranger_pk,
+        &0u64,
+        &signature,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_notary_cannot_attest_valuation() {
+    let env = create_env();
+    let (admin, user1, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    tokenize(&env, &client, &user1);
+
+    let notary = create_attestor_key(2);
+    let notary_pk = attestor_public_key(&env, &notary);
+    client.register_attestor(&notary_pk, &AttestorRole::Notary);
+
+    let signature = sign_valuation(&env, &notary, 1, 5000000, 0);
+    client.update_valuation(
+        &1u64,
+        &5000000i128,
+        &ValuationMethod::MarketComparison,
+        &notary_pk,
+        &0u64,
+        &signature,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Crypto, InvalidInput)")]
+fn test_valuation_signature_mismatch() {
+    let env = create_env();
+    let (admin, user1, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    tokenize(&env, &client, &user1);
+
+    let appraiser = create_attestor_key(1);
+    let appraiser_pk = attestor_public_key(&env, &appraiser);
+    client.register_attestor(&appraiser_pk, &AttestorRole::Appraiser);
+
+    // Signed 5,000,000 but submitted 9,000,000
+    let signature = sign_valuation(&env, &appraiser, 1, 5000000, 0);
+    client.update_valuation(
+        &1u64,
+        &9000000i128,
+        &ValuationMethod::MarketComparison,
+        &appraiser_pk,
+        &0u64,
+        &signature,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #47)")]
+fn test_valuation_attestation_replay_rejected() {
+    let env = create_env();
+    let (admin, user1, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    tokenize(&env, &client, &user1);
+
+    let appraiser = create_attestor_key(1);
+    let appraiser_pk = attestor_public_key(&env, &appraiser);
+    client.register_attestor(&appraiser_pk, &AttestorRole::Appraiser);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    let signature = sign_valuation(&env, &appraiser, 1, 5000000, 1000);
+    client.update_valuation(
+        &1u64,
+        &5000000i128,
+        &ValuationMethod::MarketComparison,
+        &appraiser_pk,
+        &1000u64,
+        &signature,
+    );
+
+    // Same signed payload submitted again
+    client.update_valuation(
+        &1u64,
+        &5000000i128,
+        &ValuationMethod::MarketComparison,
+        &appraiser_pk,
+        &1000u64,
+        &signature,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #47)")]
+fn test_stale_valuation_attestation_rejected() {
+    let env = create_env();
+    let (admin, user1, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    tokenize(&env, &client, &user1);
+
+    let appraiser = create_attestor_key(1);
+    let appraiser_pk = attestor_public_key(&env, &appraiser);
+    client.register_attestor(&appraiser_pk, &AttestorRole::Appraiser);
+
+    let signature = sign_valuation(&env, &appraiser, 1, 5000000, 1000);
+    env.ledger()
+        .with_mut(|li| li.timestamp = 1000 + MAX_ATTESTATION_AGE + 1);
+
+    client.update_valuation(
+        &1u64,
+        &5000000i128,
+        &ValuationMethod::MarketComparison,
+        &appraiser_pk,
+        &1000u64,
+        &signature,
+    );
+}
+
+#[test]
+fn test_attest_legal_docs_sets_metadata_hash() {
+    let env = create_env();
+    let (admin, user1, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    tokenize(&env, &client, &user1);
+
+    let notary = create_attestor_key(2);
+    let notary_pk = attestor_public_key(&env, &notary);
+    client.register_attestor(&notary_pk, &AttestorRole::Notary);
+
+    let doc_hash = BytesN::from_array(&env, &[42u8; 32]);
+    let signature = sign_attestation(&env, &notary, 1, AttestationField::LegalDocs, &doc_hash, 0);
+    client.attest_document(
+        &1u64,
+        &AttestationField::LegalDocs,
+        &doc_hash,
+        &notary_pk,
+        &0u64,
+        &signature,
+    );
+
+    let metadata = client.get_token_metadata(&1u64);
+    assert_eq!(metadata.legal_docs_hash, Some(doc_hash));
+    assert_eq!(metadata.valuation_report_hash, None);
+    assert_eq!(client.get_attestations(&1u64).len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #47)")]
+fn test_attest_document_rejects_valuation_field() {
+    let env = create_env();
+    let (admin, user1, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    tokenize(&env, &client, &user1);
+
+    let appraiser = create_attestor_key(1);
+    let appraiser_pk = attestor_public_key(&env, &appraiser);
+    client.register_attestor(&appraiser_pk, &AttestorRole::Appraiser);
+
+    let doc_hash = BytesN::from_array(&env, &[7u8; 32]);
+    let signature = sign_attestation(
+        &env,
+        &appraiser,
+        1,
+        AttestationField::Valuation,
+        &doc_hash,
+        0,
+    );
+    client.attest_document(
+        &1u64,
+        &AttestationField::Valuation,
+        &doc_hash,
+        &appraiser_pk,
+        &0u64,
+        &signature,
+    );
+}
@@ -0,0 +1,289 @@
+use crate::compliance::ComplianceModule;
+use crate::identity::InvestorType;
+use crate::tests::helpers::*;
+use crate::types::AssetType;
+use crate::AssetUpContractClient;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{contract, contractimpl, symbol_short, vec, Address, Env, String};
+
+/// External module that allows operations up to a configured amount
+#[contract]
+pub struct MockComplianceModule;
+
+#[contractimpl]
+impl MockComplianceModule {
+    pub fn set_limit(env: Env, limit: i128) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("limit"), &limit);
+    }
+
+    pub fn can_transfer(
+        env: Env,
+        _asset_id: u64,
+        _from: Option<Address>,
+        _to: Option<Address>,
+        amount: i128,
+    ) -> bool {
+        let limit: i128 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("limit"))
+            .unwrap_or(0);
+        amount <= limit
+    }
+}
+
+fn setup(env: &Env, client: &AssetUpContractClient, tokenizer: &Address) {
+    client.tokenize_asset(
+        &1u64,
+        &String::from_str(env, "TST"),
+        &1000000i128,
+        &6u32,
+        &100i128,
+        tokenizer,
+        &String::from_str(env, "Test Token"),
+        &String::from_str(env, "A test tokenized asset"),
+        &AssetType::Physical,
+    );
+}
+
+#[test]
+fn test_set_compliance_modules() {
+    let env = create_env();
+    let (admin, tokenizer, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &tokenizer);
+
+    let modules = vec![
+        &env,
+        ComplianceModule::MaxHolders(10),
+        ComplianceModule::SanctionsBlock,
+    ];
+    client.set_compliance_modules(&1u64, &modules, &tokenizer);
+
+    assert_eq!(client.get_compliance_modules(&1u64), modules);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_set_compliance_modules_not_tokenizer() {
+    let env = create_env();
+    let (admin, tokenizer, other, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &tokenizer);
+
+    client.set_compliance_modules(&1u64, &vec![&env, ComplianceModule::MaxHolders(10)], &other);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_max_holders() {
+    let env = create_env();
+    let (admin, tokenizer, investor1, investor2) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &tokenizer);
+    client.set_compliance_modules(
+        &1u64,
+        &vec![&env, ComplianceModule::MaxHolders(1)],
+        &tokenizer,
+    );
+
+    client.transfer_tokens(&1u64, &tokenizer, &investor1, &1000i128);
+    // Topping up an existing investor does not add a holder
+    client.transfer_tokens(&1u64, &tokenizer, &investor1, &1000i128);
+
+    client.transfer_tokens(&1u64, &tokenizer, &investor2, &1000i128);
+}
+
+#[test]
+fn test_max_holders_allows_full_exit() {
+    let env = create_env();
+    let (admin, tokenizer, investor1, investor2) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &tokenizer);
+    client.set_compliance_modules(
+        &1u64,
+        &vec![&env, ComplianceModule::MaxHolders(1)],
+        &tokenizer,
+    );
+
+    client.transfer_tokens(&1u64, &tokenizer, &investor1, &1000i128);
+    client.transfer_tokens(&1u64, &investor1, &investor2, &1000i128);
+    assert_eq!(client.get_token_balance(&1u64, &investor2), 1000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_max_balance() {
+    let env = create_env();
+    let (admin, tokenizer, investor, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &tokenizer);
+    client.set_compliance_modules(
+        &1u64,
+        &vec![&env, ComplianceModule::MaxBalance(5000)],
+        &tokenizer,
+    );
+
+    client.transfer_tokens(&1u64, &tokenizer, &investor, &5000i128);
+    client.transfer_tokens(&1u64, &tokenizer, &investor, &1i128);
+}
+
+#[test]
+fn test_min_holding_period() {
+    let env = create_env();
+    let (admin, tokenizer, investor1, investor2) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &tokenizer);
+    client.set_compliance_modules(
+        &1u64,
+        &vec![&env, ComplianceModule::MinHoldingPeriod(100)],
+        &tokenizer,
+    );
+
+    client.transfer_tokens(&1u64, &tokenizer, &investor1, &1000i128);
+    assert!(client
+        .try_transfer_tokens(&1u64, &investor1, &investor2, &1000i128)
+        .is_err());
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    client.transfer_tokens(&1u64, &investor1, &investor2, &1000i128);
+    assert_eq!(client.get_token_balance(&1u64, &investor2), 1000);
+}
+
+#[test]
+fn test_min_holding_period_overflow_stays_locked() {
+    let env = create_env();
+    let (admin, tokenizer, investor1, investor2) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &tokenizer);
+    client.set_compliance_modules(
+        &1u64,
+        &vec![&env, ComplianceModule::MinHoldingPeriod(u64::MAX)],
+        &tokenizer,
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    client.transfer_tokens(&1u64, &tokenizer, &investor1, &1000i128);
+    assert!(client
+        .try_transfer_tokens(&1u64, &investor1, &investor2, &1000i128)
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_country_holder_cap() {
+    let env = create_env();
+    let (admin, tokenizer, investor1, investor2) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &tokenizer);
+
+    let claim_issuer = Address::generate(&env);
+    client.add_claim_issuer(&claim_issuer);
+    for investor in [&investor1, &investor2] {
+        client.set_identity(
+            &claim_issuer,
+            investor,
+            &false,
+            &String::from_str(&env, "US"),
+            &1000u64,
+            &InvestorType::Retail,
+        );
+    }
+    client.set_compliance_modules(
+        &1u64,
+        &vec![
+            &env,
+            ComplianceModule::CountryHolderCap(String::from_str(&env, "US"), 1),
+        ],
+        &tokenizer,
+    );
+
+    client.transfer_tokens(&1u64, &tokenizer, &investor1, &1000i128);
+    client.transfer_tokens(&1u64, &tokenizer, &investor2, &1000i128);
+}
+
+#[test]
+fn test_daily_volume_limit() {
+    let env = create_env();
+    let (admin, tokenizer, investor1, investor2) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &tokenizer);
+    client.transfer_tokens(&1u64, &tokenizer, &investor1, &10000i128);
+    client.set_compliance_modules(
+        &1u64,
+        &vec![&env, ComplianceModule::DailyVolumeLimit(3000)],
+        &tokenizer,
+    );
+
+    client.transfer_tokens(&1u64, &investor1, &investor2, &2000i128);
+    client.transfer_tokens(&1u64, &investor1, &investor2, &1000i128);
+    assert!(client
+        .try_transfer_tokens(&1u64, &investor1, &investor2, &1i128)
+        .is_err());
+
+    // The day's volume outlives many ledgers within the same day
+    env.ledger().with_mut(|li| {
+        li.sequence_number += 1_000;
+        li.timestamp = 86399;
+    });
+    assert!(client
+        .try_transfer_tokens(&1u64, &investor1, &investor2, &1i128)
+        .is_err());
+
+    // The limit resets the next day
+    env.ledger().with_mut(|li| li.timestamp = 86400);
+    client.transfer_tokens(&1u64, &investor1, &investor2, &3000i128);
+    assert_eq!(client.get_token_balance(&1u64, &investor2), 6000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_sanctioned_receiver_blocked() {
+    let env = create_env();
+    let (admin, tokenizer, investor, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &tokenizer);
+    client.set_compliance_modules(
+        &1u64,
+        &vec![&env, ComplianceModule::SanctionsBlock],
+        &tokenizer,
+    );
+
+    client.set_global_denylisted(&investor, &true);
+    assert!(client.is_sanctioned(&investor));
+
+    client.transfer_tokens(&1u64, &tokenizer, &investor, &1000i128);
+}
+
+#[test]
+fn test_external_module_checks_transfer_mint_and_burn() {
+    let env = create_env();
+    let (admin, tokenizer, investor, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &tokenizer);
+
+    let module_id = env.register(MockComplianceModule, ());
+    MockComplianceModuleClient::new(&env, &module_id).set_limit(&1000i128);
+    client.set_compliance_modules(
+        &1u64,
+        &vec![&env, ComplianceModule::External(module_id)],
+        &tokenizer,
+    );
+
+    client.transfer_tokens(&1u64, &tokenizer, &investor, &1000i128);
+    assert!(client
+        .try_transfer_tokens(&1u64, &tokenizer, &investor, &1001i128)
+        .is_err());
+
+    client.mint_tokens(&1u64, &1000i128, &tokenizer);
+    assert!(client
+        .try_mint_tokens(&1u64, &1001i128, &tokenizer)
+        .is_err());
+
+    client.burn_tokens(&1u64, &1000i128, &tokenizer);
+    assert!(client
+        .try_burn_tokens(&1u64, &1001i128, &tokenizer)
+        .is_err());
+}
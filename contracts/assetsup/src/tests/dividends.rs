@@ -1,6 +1,13 @@
+use crate::attestation::AttestorRole;
+use crate::dividends::ReinvestmentSource;
+use crate::error::Error;
+use crate::identity::InvestorType;
 use crate::tests::helpers::*;
-use crate::types::AssetType;
-use soroban_sdk::String;
+use crate::types::{AssetType, MintingPolicy};
+use crate::valuation::ValuationMethod;
+use crate::AssetUpContractClient;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{Address, BytesN, Env, String};
 
 #[test]
 fn test_enable_revenue_sharing() {
@@ -243,3 +250,403 @@ fn test_multiple_dividend_distributions() {
     assert_eq!(unclaimed1, 7500); // 50% of 15000
     assert_eq!(unclaimed2, 7500); // 50% of 15000
 }
+
+/// Tokenize asset 1 with a 70/30 split between `issuer` and `holder`, enable
+/// revenue sharing and fund the issuer with a payment token
+fn setup_rounds<'a>(
+    env: &'a Env,
+    client: &AssetUpContractClient<'a>,
+    issuer: &Address,
+    holder: &Address,
+) -> Address {
+    client.tokenize_asset(
+        &1u64,
+        &String::from_str(env, "TST"),
+        &1000i128,
+        &0u32,
+        &100i128,
+        issuer,
+        &String::from_str(env, "Test Token"),
+        &String::from_str(env, "A test tokenized asset"),
+        &AssetType::Physical,
+    );
+    client.transfer_tokens(&1u64, issuer, holder, &300i128);
+    client.enable_revenue_sharing(&1u64);
+
+    let token_admin = Address::generate(env);
+    let (token, token_admin_client) = create_payment_token(env, &token_admin);
+    token_admin_client.mint(issuer, &1_000_000i128);
+    token.address
+}
+
+#[test]
+fn test_distribution_round_claims() {
+    let env = create_env();
+    let (admin, issuer, holder, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment_token = setup_rounds(&env, &client, &issuer, &holder);
+    let token = soroban_sdk::token::TokenClient::new(&env, &payment_token);
+    let memo = BytesN::from_array(&env, &[7u8; 32]);
+
+    let round_id =
+        client.create_distribution_round(&1u64, &payment_token, &10_000i128, &memo, &issuer);
+    assert_eq!(round_id, 1);
+    assert_eq!(token.balance(&client.address), 10_000);
+
+    let round = client.get_distribution_round(&1u64, &round_id);
+    assert_eq!(round.payment_token, payment_token);
+    assert_eq!(round.memo_hash, memo);
+    assert_eq!(
+        client
+            .get_round_entitlement(&1u64, &round_id, &holder)
+            .unwrap()
+            .amount,
+        3000
+    );
+
+    // Transfers after the record date do not change entitlements
+    client.transfer_tokens(&1u64, &issuer, &holder, &700i128);
+    let claim = client.claim_round(&1u64, &round_id, &holder);
+    assert_eq!(claim.gross_amount, 3000);
+    assert_eq!(claim.net_amount, 3000);
+    assert_eq!(token.balance(&holder), 3000);
+    assert_eq!(
+        client.try_claim_round(&1u64, &round_id, &holder),
+        Err(Ok(Error::NoDividendsToClaim))
+    );
+
+    let round = client.get_distribution_round(&1u64, &round_id);
+    assert_eq!(round.claimed_amount, 3000);
+    assert_eq!(client.get_dividend_claim_history(&1u64, &holder).len(), 1);
+}
+
+#[test]
+fn test_claim_all_rounds_and_history() {
+    let env = create_env();
+    let (admin, issuer, holder, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment_token = setup_rounds(&env, &client, &issuer, &holder);
+    let memo = BytesN::from_array(&env, &[1u8; 32]);
+
+    client.create_distribution_round(&1u64, &payment_token, &1000i128, &memo, &issuer);
+    env.ledger().with_mut(|li| li.timestamp += 100);
+    client.create_distribution_round(&1u64, &payment_token, &2000i128, &memo, &issuer);
+    assert_eq!(client.get_claimable_rounds(&1u64, &holder).len(), 2);
+
+    let claims = client.claim_all_rounds(&1u64, &holder);
+    assert_eq!(claims.len(), 2);
+    assert_eq!(claims.get(0).unwrap().net_amount, 300);
+    assert_eq!(claims.get(1).unwrap().net_amount, 600);
+    assert!(client.get_claimable_rounds(&1u64, &holder).is_empty());
+
+    let history = client.get_dividend_claim_history(&1u64, &holder);
+    assert_eq!(history, claims);
+    assert_eq!(
+        client.try_claim_all_rounds(&1u64, &holder),
+        Err(Ok(Error::NoDividendsToClaim))
+    );
+}
+
+#[test]
+fn test_reclaim_expired_round() {
+    let env = create_env();
+    let (admin, issuer, holder, outsider) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment_token = setup_rounds(&env, &client, &issuer, &holder);
+    let token = soroban_sdk::token::TokenClient::new(&env, &payment_token);
+    let memo = BytesN::from_array(&env, &[2u8; 32]);
+
+    client.set_dividend_claim_period(&1u64, &1000u64, &issuer);
+    let round_id =
+        client.create_distribution_round(&1u64, &payment_token, &10_000i128, &memo, &issuer);
+    client.claim_round(&1u64, &round_id, &issuer);
+
+    assert_eq!(
+        client.try_reclaim_expired_round(&1u64, &round_id, &issuer),
+        Err(Ok(Error::InvalidTimestamps))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp += 1000);
+    assert_eq!(
+        client.try_claim_round(&1u64, &round_id, &holder),
+        Err(Ok(Error::NoDividendsToClaim))
+    );
+    assert_eq!(
+        client.try_reclaim_expired_round(&1u64, &round_id, &outsider),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    let before = token.balance(&issuer);
+    assert_eq!(
+        client.reclaim_expired_round(&1u64, &round_id, &issuer),
+        3000
+    );
+    assert_eq!(token.balance(&issuer), before + 3000);
+    assert_eq!(token.balance(&client.address), 0);
+    assert_eq!(
+        client.try_reclaim_expired_round(&1u64, &round_id, &issuer),
+        Err(Ok(Error::NoDividendsToClaim))
+    );
+}
+
+#[test]
+fn test_create_round_requires_tokenizer_and_revenue_sharing() {
+    let env = create_env();
+    let (admin, issuer, holder, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment_token = setup_rounds(&env, &client, &issuer, &holder);
+    let memo = BytesN::from_array(&env, &[3u8; 32]);
+
+    assert_eq!(
+        client.try_create_distribution_round(&1u64, &payment_token, &100i128, &memo, &holder),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_create_distribution_round(&1u64, &payment_token, &0i128, &memo, &issuer),
+        Err(Ok(Error::InvalidDividendAmount))
+    );
+
+    client.disable_revenue_sharing(&1u64);
+    assert_eq!(
+        client.try_create_distribution_round(&1u64, &payment_token, &100i128, &memo, &issuer),
+        Err(Ok(Error::InvalidDividendAmount))
+    );
+}
+
+#[test]
+fn test_round_claim_fee_and_withholding() {
+    let env = create_env();
+    let (admin, issuer, holder, treasury) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment_token = setup_rounds(&env, &client, &issuer, &holder);
+    let token = soroban_sdk::token::TokenClient::new(&env, &payment_token);
+    let tax_authority = Address::generate(&env);
+    let memo = BytesN::from_array(&env, &[4u8; 32]);
+
+    let claim_issuer = Address::generate(&env);
+    client.add_claim_issuer(&claim_issuer);
+    client.set_identity(
+        &claim_issuer,
+        &holder,
+        &false,
+        &String::from_str(&env, "US"),
+        &u64::MAX,
+        &InvestorType::Retail,
+    );
+    client.set_dividend_fee_config(&100u32, &treasury, &tax_authority);
+    client.set_withholding_rate(&String::from_str(&env, "US"), &3000u32);
+
+    let round_id =
+        client.create_distribution_round(&1u64, &payment_token, &10_000i128, &memo, &issuer);
+
+    // 1% fee on 3000, then 30% withheld from the remaining 2970
+    let claim = client.claim_round(&1u64, &round_id, &holder);
+    assert_eq!(claim.gross_amount, 3000);
+    assert_eq!(claim.fee_amount, 30);
+    assert_eq!(claim.withheld_amount, 891);
+    assert_eq!(claim.net_amount, 2079);
+    assert_eq!(token.balance(&holder), 2079);
+    assert_eq!(token.balance(&treasury), 30);
+    assert_eq!(client.get_withheld_balance(&payment_token), 891);
+    assert_eq!(
+        client.get_dividend_claim_history(&1u64, &holder).get(0),
+        Some(claim)
+    );
+
+    // No identity claim: only the platform fee applies
+    let issuer_claim = client.claim_round(&1u64, &round_id, &issuer);
+    assert_eq!(issuer_claim.fee_amount, 70);
+    assert_eq!(issuer_claim.withheld_amount, 0);
+    assert_eq!(issuer_claim.net_amount, 6930);
+
+    assert_eq!(
+        client.try_withdraw_withheld(&payment_token, &100i128, &holder),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_withdraw_withheld(&payment_token, &892i128, &tax_authority),
+        Err(Ok(Error::InsufficientBalance))
+    );
+    client.withdraw_withheld(&payment_token, &891i128, &tax_authority);
+    assert_eq!(token.balance(&tax_authority), 891);
+    assert_eq!(client.get_withheld_balance(&payment_token), 0);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+fn test_dividend_fee_config_validation() {
+    let env = create_env();
+    let (admin, _, treasury, tax_authority) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+
+    assert_eq!(
+        client.try_set_dividend_fee_config(&10_001u32, &treasury, &tax_authority),
+        Err(Ok(Error::InvalidDividendAmount))
+    );
+    assert_eq!(
+        client.try_set_withholding_rate(&String::from_str(&env, "US"), &10_001u32),
+        Err(Ok(Error::InvalidDividendAmount))
+    );
+    assert_eq!(client.get_dividend_fee_config(), None);
+}
+
+/// Value asset 1 at `valuation` through a registered appraiser
+fn appraise(env: &Env, client: &AssetUpContractClient, valuation: i128) {
+    let appraiser = create_attestor_key(5);
+    let appraiser_pk = attestor_public_key(env, &appraiser);
+    client.register_attestor(&appraiser_pk, &AttestorRole::Appraiser);
+    let timestamp = env.ledger().timestamp();
+    let signature = sign_valuation(env, &appraiser, 1, valuation, timestamp);
+    client.update_valuation(
+        &1u64,
+        &valuation,
+        &ValuationMethod::IncomeApproach,
+        &appraiser_pk,
+        &timestamp,
+        &signature,
+    );
+}
+
+#[test]
+fn test_reinvestment_from_treasury_carries_remainder() {
+    let env = create_env();
+    let (admin, issuer, holder, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment_token = setup_rounds(&env, &client, &issuer, &holder);
+    let token = soroban_sdk::token::TokenClient::new(&env, &payment_token);
+    let memo = BytesN::from_array(&env, &[5u8; 32]);
+
+    // 7000 over 1000 tokens: 7 per token
+    appraise(&env, &client, 7000);
+    client.set_dividend_reinvestment(&1u64, &holder, &true);
+    assert!(client.get_dividend_reinvestment(&1u64, &holder));
+
+    let issuer_before = token.balance(&issuer);
+    let round_id =
+        client.create_distribution_round(&1u64, &payment_token, &10_000i128, &memo, &issuer);
+
+    // 3000 buys 428 tokens for 2996; 4 carries forward
+    assert_eq!(client.get_token_balance(&1u64, &holder), 728);
+    assert_eq!(client.get_token_balance(&1u64, &issuer), 272);
+    assert_eq!(client.get_tokenized_asset(&1u64).total_supply, 1000);
+    assert_eq!(
+        client.get_reinvestment_carry(&1u64, &holder, &payment_token),
+        4
+    );
+    assert_eq!(token.balance(&issuer), issuer_before - 10_000 + 2996);
+    assert_eq!(token.balance(&holder), 0);
+
+    // (300 * 1 + 428 * 7) / 728
+    let record = client.get_ownership_record(&1u64, &holder);
+    assert_eq!(record.average_purchase_price, 4);
+
+    let claim = client
+        .get_dividend_claim_history(&1u64, &holder)
+        .get(0)
+        .unwrap();
+    assert_eq!(claim.net_amount, 3000);
+    assert_eq!(claim.reinvested_tokens, 428);
+    assert_eq!(
+        client.try_claim_round(&1u64, &round_id, &holder),
+        Err(Ok(Error::NoDividendsToClaim))
+    );
+    assert_eq!(
+        client
+            .get_distribution_round(&1u64, &round_id)
+            .claimed_amount,
+        3000
+    );
+
+    // The carry tops up the next round: 728 + 4 buys 104 tokens for 728
+    client.create_distribution_round(&1u64, &payment_token, &1000i128, &memo, &issuer);
+    assert_eq!(client.get_token_balance(&1u64, &holder), 832);
+    assert_eq!(
+        client.get_reinvestment_carry(&1u64, &holder, &payment_token),
+        4
+    );
+}
+
+#[test]
+fn test_reinvestment_mints_new_tokens() {
+    let env = create_env();
+    let (admin, issuer, holder, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment_token = setup_rounds(&env, &client, &issuer, &holder);
+    let memo = BytesN::from_array(&env, &[6u8; 32]);
+
+    appraise(&env, &client, 7000);
+    client.set_reinvestment_source(&1u64, &ReinvestmentSource::Mint, &issuer);
+    client.set_dividend_reinvestment(&1u64, &holder, &true);
+    client.create_distribution_round(&1u64, &payment_token, &10_000i128, &memo, &issuer);
+
+    assert_eq!(client.get_tokenized_asset(&1u64).total_supply, 1428);
+    assert_eq!(client.get_token_balance(&1u64, &holder), 728);
+    assert_eq!(client.get_token_balance(&1u64, &issuer), 700);
+
+    // Opting out leaves the carry withdrawable in cash
+    client.set_dividend_reinvestment(&1u64, &holder, &false);
+    assert_eq!(
+        client.withdraw_reinvestment_carry(&1u64, &holder, &payment_token),
+        4
+    );
+    assert_eq!(
+        client.try_withdraw_reinvestment_carry(&1u64, &holder, &payment_token),
+        Err(Ok(Error::NoDividendsToClaim))
+    );
+}
+
+#[test]
+fn test_reinvestment_without_valuation_pays_cash() {
+    let env = create_env();
+    let (admin, issuer, holder, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment_token = setup_rounds(&env, &client, &issuer, &holder);
+    let memo = BytesN::from_array(&env, &[8u8; 32]);
+
+    client.set_dividend_reinvestment(&1u64, &holder, &true);
+    let round_id =
+        client.create_distribution_round(&1u64, &payment_token, &10_000i128, &memo, &issuer);
+
+    assert_eq!(client.get_token_balance(&1u64, &holder), 300);
+    assert_eq!(
+        client.claim_round(&1u64, &round_id, &holder).net_amount,
+        3000
+    );
+}
+
+#[test]
+fn test_undeliverable_reinvestment_falls_back_to_cash() {
+    let env = create_env();
+    let (admin, issuer, holder, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment_token = setup_rounds(&env, &client, &issuer, &holder);
+    let token = soroban_sdk::token::TokenClient::new(&env, &payment_token);
+    let memo = BytesN::from_array(&env, &[9u8; 32]);
+
+    // Minting is closed, so the reinvested tokens cannot be issued
+    appraise(&env, &client, 7000);
+    client.set_minting_policy(&1u64, &MintingPolicy::FixedSupply, &issuer);
+    client.set_reinvestment_source(&1u64, &ReinvestmentSource::Mint, &issuer);
+    client.set_dividend_reinvestment(&1u64, &holder, &true);
+    let round_id =
+        client.create_distribution_round(&1u64, &payment_token, &10_000i128, &memo, &issuer);
+
+    // The round is still created and the holder keeps a cash entitlement
+    assert_eq!(client.get_token_balance(&1u64, &holder), 300);
+    assert_eq!(client.get_tokenized_asset(&1u64).total_supply, 1000);
+    assert_eq!(
+        client
+            .get_distribution_round(&1u64, &round_id)
+            .claimed_amount,
+        0
+    );
+    assert_eq!(
+        client.claim_round(&1u64, &round_id, &holder).net_amount,
+        3000
+    );
+    assert_eq!(token.balance(&holder), 3000);
+    assert_eq!(
+        client.get_reinvestment_carry(&1u64, &holder, &payment_token),
+        0
+    );
+}
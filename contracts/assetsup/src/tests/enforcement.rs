@@ -0,0 +1,307 @@
+use crate::enforcement::EnforcementAction;
+use crate::error::Error;
+use crate::identity::InvestorType;
+use crate::tests::helpers::*;
+use crate::types::AssetType;
+use crate::AssetUpContractClient;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{symbol_short, Address, BytesN, Env, String};
+
+fn setup(env: &Env, client: &AssetUpContractClient, issuer: &Address, holder: &Address) {
+    client.tokenize_asset(
+        &1u64,
+        &String::from_str(env, "TST"),
+        &1000000i128,
+        &6u32,
+        &100i128,
+        issuer,
+        &String::from_str(env, "Test Token"),
+        &String::from_str(env, "A test tokenized asset"),
+        &AssetType::Physical,
+    );
+    client.transfer_tokens(&1u64, issuer, holder, &10000i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_full_freeze_blocks_transfers() {
+    let env = create_env();
+    let (admin, issuer, holder, receiver) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &issuer, &holder);
+
+    client.freeze_tokens(&1u64, &holder, &None, &issuer);
+    assert_eq!(client.get_spendable_balance(&1u64, &holder), 0);
+
+    client.transfer_tokens(&1u64, &holder, &receiver, &1i128);
+}
+
+#[test]
+fn test_partial_freeze_and_unfreeze() {
+    let env = create_env();
+    let (admin, issuer, holder, receiver) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &issuer, &holder);
+
+    client.freeze_tokens(&1u64, &holder, &Some(6000i128), &issuer);
+    assert_eq!(client.get_spendable_balance(&1u64, &holder), 4000);
+
+    client.transfer_tokens(&1u64, &holder, &receiver, &4000i128);
+    assert!(client
+        .try_transfer_tokens(&1u64, &holder, &receiver, &1i128)
+        .is_err());
+
+    client.unfreeze_tokens(&1u64, &holder, &issuer);
+    assert!(client.get_freeze(&1u64, &holder).is_none());
+    client.transfer_tokens(&1u64, &holder, &receiver, &6000i128);
+    assert_eq!(client.get_token_balance(&1u64, &receiver), 10000);
+}
+
+#[test]
+fn test_partial_freeze_stacks_with_locks() {
+    let env = create_env();
+    let (admin, issuer, holder, receiver) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &issuer, &holder);
+
+    client.lock_token_amount(
+        &1u64,
+        &holder,
+        &5000i128,
+        &1000u64,
+        &symbol_short!("escrow"),
+        &issuer,
+    );
+    client.freeze_tokens(&1u64, &holder, &Some(5000i128), &issuer);
+    assert_eq!(client.get_spendable_balance(&1u64, &holder), 0);
+
+    assert_eq!(
+        client.try_transfer_tokens(&1u64, &holder, &receiver, &5000i128),
+        Err(Ok(Error::InsufficientLockedTokens))
+    );
+}
+
+#[test]
+fn test_agent_can_freeze() {
+    let env = create_env();
+    let (admin, issuer, holder, agent) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &issuer, &holder);
+
+    client.grant_agent(&1u64, &agent, &issuer);
+    assert!(client.is_agent(&1u64, &agent));
+    client.freeze_tokens(&1u64, &holder, &None, &agent);
+    assert!(client.get_freeze(&1u64, &holder).unwrap().full);
+
+    client.revoke_agent(&1u64, &agent, &issuer);
+    assert!(client.try_unfreeze_tokens(&1u64, &holder, &agent).is_err());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_freeze_unauthorized() {
+    let env = create_env();
+    let (admin, issuer, holder, other) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &issuer, &holder);
+
+    client.freeze_tokens(&1u64, &holder, &None, &other);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_asset_denylist_blocks_receiver() {
+    let env = create_env();
+    let (admin, issuer, holder, receiver) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &issuer, &holder);
+
+    client.set_denylisted(&1u64, &receiver, &true, &issuer);
+    assert!(client.is_denylisted(&1u64, &receiver));
+
+    client.transfer_tokens(&1u64, &holder, &receiver, &1000i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_global_denylist_blocks_sender() {
+    let env = create_env();
+    let (admin, issuer, holder, receiver) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &issuer, &holder);
+
+    client.set_global_denylisted(&holder, &true);
+    assert!(client.is_denylisted(&7u64, &holder));
+
+    client.transfer_tokens(&1u64, &holder, &receiver, &1000i128);
+}
+
+#[test]
+fn test_global_denylist_shares_sanctions_and_is_logged() {
+    let env = create_env();
+    let (admin, _, holder, other) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+
+    client.set_global_denylisted(&holder, &true);
+    assert!(client.is_sanctioned(&holder));
+
+    // Every change to the shared list lands in the same log
+    client.set_global_denylisted(&other, &true);
+    assert!(client.is_denylisted(&1u64, &other));
+    client.set_global_denylisted(&holder, &false);
+    assert!(!client.is_denylisted(&1u64, &holder));
+    assert!(!client.is_sanctioned(&holder));
+
+    let log = client.get_global_enforcement_log();
+    assert_eq!(log.len(), 3);
+    let first = log.get(0).unwrap();
+    assert_eq!(first.action, EnforcementAction::Denylisted);
+    assert_eq!(first.actor, admin);
+    assert_eq!(first.target, holder);
+    assert_eq!(
+        log.get(2).unwrap().action,
+        EnforcementAction::RemovedFromDenylist
+    );
+}
+
+#[test]
+fn test_forced_transfer_recovers_frozen_tokens() {
+    let env = create_env();
+    let (admin, issuer, holder, recovery) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &issuer, &holder);
+
+    client.freeze_tokens(&1u64, &holder, &None, &issuer);
+    let reason = BytesN::from_array(&env, &[7u8; 32]);
+    client.forced_transfer(&1u64, &holder, &recovery, &10000i128, &reason, &issuer);
+
+    assert_eq!(client.get_token_balance(&1u64, &holder), 0);
+    assert_eq!(client.get_token_balance(&1u64, &recovery), 10000);
+
+    let log = client.get_enforcement_log(&1u64);
+    assert_eq!(log.len(), 2);
+    let entry = log.get(1).unwrap();
+    assert_eq!(entry.action, EnforcementAction::ForcedTransfer);
+    assert_eq!(entry.target, holder);
+    assert_eq!(entry.amount, 10000);
+    assert_eq!(entry.reason_hash, Some(reason));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_forced_transfer_agent_not_allowed() {
+    let env = create_env();
+    let (admin, issuer, holder, agent) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &issuer, &holder);
+
+    client.grant_agent(&1u64, &agent, &issuer);
+    client.forced_transfer(
+        &1u64,
+        &holder,
+        &agent,
+        &1000i128,
+        &BytesN::from_array(&env, &[7u8; 32]),
+        &agent,
+    );
+}
+
+#[test]
+fn test_forced_transfer_shrinks_freeze_and_vesting() {
+    let env = create_env();
+    let (admin, issuer, holder, recovery) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &issuer, &holder);
+
+    client.create_vesting_schedule(
+        &1u64, &holder, &8000i128, &1000u64, &0u64, &1000u64, &false, &issuer,
+    );
+    client.freeze_tokens(&1u64, &holder, &Some(5000i128), &issuer);
+
+    client.forced_transfer(
+        &1u64,
+        &holder,
+        &recovery,
+        &15000i128,
+        &BytesN::from_array(&env, &[7u8; 32]),
+        &issuer,
+    );
+
+    assert_eq!(client.get_token_balance(&1u64, &holder), 3000);
+    assert_eq!(client.get_freeze(&1u64, &holder).unwrap().amount, 3000);
+    assert_eq!(client.get_unvested_amount(&1u64, &holder), 3000);
+}
+
+#[test]
+fn test_forced_transfer_checks_receiver() {
+    let env = create_env();
+    let (admin, issuer, holder, recovery) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &issuer, &holder);
+
+    client.add_to_whitelist(&1u64, &holder);
+    assert_eq!(
+        client.try_forced_transfer(
+            &1u64,
+            &holder,
+            &recovery,
+            &1000i128,
+            &BytesN::from_array(&env, &[7u8; 32]),
+            &issuer,
+        ),
+        Err(Ok(Error::TransferRestrictionFailed))
+    );
+}
+
+#[test]
+fn test_forced_transfer_recovers_from_revoked_identity() {
+    let env = create_env();
+    let (admin, issuer, holder, recovery) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &issuer, &holder);
+
+    let claim_issuer = Address::generate(&env);
+    client.add_claim_issuer(&claim_issuer);
+    for investor in [&holder, &recovery] {
+        client.set_identity(
+            &claim_issuer,
+            investor,
+            &true,
+            &String::from_str(&env, "US"),
+            &1000u64,
+            &InvestorType::Retail,
+        );
+    }
+    client.set_transfer_restriction(&1u64, &true);
+
+    // The holder's claims are revoked, so they can no longer send normally
+    client.revoke_identity(&claim_issuer, &holder);
+    assert_eq!(
+        client.try_transfer_tokens(&1u64, &holder, &recovery, &1000i128),
+        Err(Ok(Error::AccreditedInvestorRequired))
+    );
+
+    client.forced_transfer(
+        &1u64,
+        &holder,
+        &recovery,
+        &10000i128,
+        &BytesN::from_array(&env, &[7u8; 32]),
+        &issuer,
+    );
+    assert_eq!(client.get_token_balance(&1u64, &recovery), 10000);
+
+    // The receiver still has to qualify
+    client.revoke_identity(&claim_issuer, &recovery);
+    assert_eq!(
+        client.try_forced_transfer(
+            &1u64,
+            &recovery,
+            &holder,
+            &1000i128,
+            &BytesN::from_array(&env, &[7u8; 32]),
+            &issuer,
+        ),
+        Err(Ok(Error::AccreditedInvestorRequired))
+    );
+}
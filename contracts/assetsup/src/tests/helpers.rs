@@ -1,8 +1,13 @@
 use crate::asset::Asset;
-use crate::insurance::{ClaimStatus, ClaimType, InsuranceClaim, InsurancePolicy, PolicyStatus, PolicyType};
+use crate::attestation::AttestationField;
+use crate::insurance::{
+    ClaimStatus, ClaimType, InsuranceClaim, InsurancePolicy, PolicyStatus, PolicyType,
+};
 use crate::types::{AssetStatus, AssetType, CustomAttribute, TokenMetadata};
 use crate::{AssetUpContract, AssetUpContractClient};
-use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String, Vec};
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{testutils::Address as _, Address, Bytes, BytesN, Env, String, Vec};
 
 /// Create a fresh test environment
 pub fn create_env() -> Env {
@@ -28,6 +33,18 @@ pub fn initialize_contract<'a>(env: &'a Env, admin: &Address) -> AssetUpContract
     client
 }
 
+/// Register a SEP-41 payment token and return its client and admin client
+pub fn create_payment_token<'a>(
+    env: &'a Env,
+    admin: &Address,
+) -> (TokenClient<'a>, StellarAssetClient<'a>) {
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+    (
+        TokenClient::new(env, &sac.address()),
+        StellarAssetClient::new(env, &sac.address()),
+    )
+}
+
 /// Create a test asset
 pub fn create_test_asset(env: &Env, owner: &Address, id: BytesN<32>) -> Asset {
     let timestamp = env.ledger().timestamp();
@@ -151,5 +168,59 @@ pub fn create_test_claim(
         status: ClaimStatus::Submitted,
         filed_at: current_time,
         approved_amount: 0,
+        payout_amount: 0,
     }
 }
+
+/// Create a deterministic ed25519 attestor signing key
+pub fn create_attestor_key(seed: u8) -> SigningKey {
+    SigningKey::from_bytes(&[seed; 32])
+}
+
+/// On-chain public key of an attestor signing key
+pub fn attestor_public_key(env: &Env, key: &SigningKey) -> BytesN<32> {
+    BytesN::from_array(env, &key.verifying_key().to_bytes())
+}
+
+/// Sign `asset_id || field || value_hash || timestamp` as an attestor would off-chain
+pub fn sign_attestation(
+    env: &Env,
+    key: &SigningKey,
+    asset_id: u64,
+    field: AttestationField,
+    value_hash: &BytesN<32>,
+    timestamp: u64,
+) -> BytesN<64> {
+    let mut payload = [0u8; 52];
+    payload[..8].copy_from_slice(&asset_id.to_be_bytes());
+    payload[8..12].copy_from_slice(&(field as u32).to_be_bytes());
+    payload[12..44].copy_from_slice(&value_hash.to_array());
+    payload[44..].copy_from_slice(&timestamp.to_be_bytes());
+    BytesN::from_array(env, &key.sign(&payload).to_bytes())
+}
+
+/// Hash a valuation commits to: sha256 of the big-endian i128
+pub fn valuation_value_hash(env: &Env, valuation: i128) -> BytesN<32> {
+    env.crypto()
+        .sha256(&Bytes::from_array(env, &valuation.to_be_bytes()))
+        .into()
+}
+
+/// Sign a valuation attestation for `asset_id`
+pub fn sign_valuation(
+    env: &Env,
+    key: &SigningKey,
+    asset_id: u64,
+    valuation: i128,
+    timestamp: u64,
+) -> BytesN<64> {
+    let value_hash = valuation_value_hash(env, valuation);
+    sign_attestation(
+        env,
+        key,
+        asset_id,
+        AttestationField::Valuation,
+        &value_hash,
+        timestamp,
+    )
+}
@@ -0,0 +1,180 @@
+use crate::identity::InvestorType;
+use crate::tests::helpers::*;
+use crate::types::AssetType;
+use crate::AssetUpContractClient;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{vec, Address, Env, String};
+
+fn setup(env: &Env, client: &AssetUpContractClient, tokenizer: &Address) -> Address {
+    client.tokenize_asset(
+        &1u64,
+        &String::from_str(env, "TST"),
+        &1000000i128,
+        &6u32,
+        &100i128,
+        tokenizer,
+        &String::from_str(env, "Test Token"),
+        &String::from_str(env, "A test tokenized asset"),
+        &AssetType::Physical,
+    );
+
+    let claim_issuer = Address::generate(env);
+    client.add_claim_issuer(&claim_issuer);
+    claim_issuer
+}
+
+fn register(
+    env: &Env,
+    client: &AssetUpContractClient,
+    claim_issuer: &Address,
+    investor: &Address,
+    accredited: bool,
+    country: &str,
+) {
+    client.set_identity(
+        claim_issuer,
+        investor,
+        &accredited,
+        &String::from_str(env, country),
+        &1000u64,
+        &InvestorType::Retail,
+    );
+}
+
+#[test]
+fn test_set_and_get_identity() {
+    let env = create_env();
+    let (admin, tokenizer, investor, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let claim_issuer = setup(&env, &client, &tokenizer);
+
+    register(&env, &client, &claim_issuer, &investor, true, "DE");
+
+    let claims = client.get_identity(&investor).unwrap();
+    assert!(claims.accredited);
+    assert_eq!(claims.jurisdiction, String::from_str(&env, "DE"));
+    assert_eq!(claims.issuer, claim_issuer);
+    assert!(client.is_claim_issuer(&claim_issuer));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_set_identity_untrusted_issuer() {
+    let env = create_env();
+    let (admin, tokenizer, investor, other) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &tokenizer);
+
+    register(&env, &client, &other, &investor, true, "DE");
+}
+
+#[test]
+fn test_accredited_transfer_with_identity() {
+    let env = create_env();
+    let (admin, tokenizer, investor, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let claim_issuer = setup(&env, &client, &tokenizer);
+
+    client.set_transfer_restriction(&1u64, &true);
+    register(&env, &client, &claim_issuer, &investor, true, "DE");
+
+    client.transfer_tokens(&1u64, &tokenizer, &investor, &1000i128);
+    assert_eq!(client.get_token_balance(&1u64, &investor), 1000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #19)")]
+fn test_accredited_transfer_expired_kyc() {
+    let env = create_env();
+    let (admin, tokenizer, investor, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let claim_issuer = setup(&env, &client, &tokenizer);
+
+    client.set_transfer_restriction(&1u64, &true);
+    register(&env, &client, &claim_issuer, &investor, true, "DE");
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    client.transfer_tokens(&1u64, &tokenizer, &investor, &1000i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #19)")]
+fn test_removed_claim_issuer_invalidates_claims() {
+    let env = create_env();
+    let (admin, tokenizer, investor, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let claim_issuer = setup(&env, &client, &tokenizer);
+
+    client.set_transfer_restriction(&1u64, &true);
+    register(&env, &client, &claim_issuer, &investor, true, "DE");
+    client.remove_claim_issuer(&claim_issuer);
+
+    client.transfer_tokens(&1u64, &tokenizer, &investor, &1000i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #20)")]
+fn test_receiver_outside_allowed_jurisdictions() {
+    let env = create_env();
+    let (admin, tokenizer, investor, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let claim_issuer = setup(&env, &client, &tokenizer);
+
+    client.set_allowed_jurisdictions(
+        &1u64,
+        &vec![
+            &env,
+            String::from_str(&env, "DE"),
+            String::from_str(&env, "FR"),
+        ],
+    );
+    register(&env, &client, &claim_issuer, &investor, false, "US");
+
+    client.transfer_tokens(&1u64, &tokenizer, &investor, &1000i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #20)")]
+fn test_receiver_without_identity_under_geographic_rules() {
+    let env = create_env();
+    let (admin, tokenizer, investor, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &tokenizer);
+
+    client.set_allowed_jurisdictions(&1u64, &vec![&env, String::from_str(&env, "DE")]);
+
+    client.transfer_tokens(&1u64, &tokenizer, &investor, &1000i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #20)")]
+fn test_sender_in_restricted_jurisdiction() {
+    let env = create_env();
+    let (admin, tokenizer, seller, buyer) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let claim_issuer = setup(&env, &client, &tokenizer);
+
+    register(&env, &client, &claim_issuer, &seller, false, "RU");
+    register(&env, &client, &claim_issuer, &buyer, false, "DE");
+    client.transfer_tokens(&1u64, &tokenizer, &seller, &1000i128);
+
+    client.set_restricted_jurisdictions(&1u64, &vec![&env, String::from_str(&env, "RU")]);
+
+    client.transfer_tokens(&1u64, &seller, &buyer, &1000i128);
+}
+
+#[test]
+fn test_transfer_between_allowed_jurisdictions() {
+    let env = create_env();
+    let (admin, tokenizer, seller, buyer) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let claim_issuer = setup(&env, &client, &tokenizer);
+
+    client.set_restricted_jurisdictions(&1u64, &vec![&env, String::from_str(&env, "RU")]);
+    register(&env, &client, &claim_issuer, &seller, false, "FR");
+    register(&env, &client, &claim_issuer, &buyer, false, "DE");
+
+    client.transfer_tokens(&1u64, &tokenizer, &seller, &1000i128);
+    client.transfer_tokens(&1u64, &seller, &buyer, &400i128);
+    assert_eq!(client.get_token_balance(&1u64, &buyer), 400);
+}
@@ -1,6 +1,9 @@
-use crate::insurance::PolicyStatus;
+use crate::error::Error;
+use crate::insurance::{ClaimStatus, ClaimType, PolicyStatus};
 use crate::tests::helpers::*;
+use crate::AssetUpContractClient;
 use soroban_sdk::testutils::Ledger;
+use soroban_sdk::{Address, BytesN, Env};
 
 #[test]
 fn test_create_insurance_policy_success() {
@@ -336,3 +339,353 @@ fn test_policy_lifecycle_full() {
     let stored = client.get_insurance_policy(&policy_id).unwrap();
     assert_eq!(stored.status, PolicyStatus::Cancelled);
 }
+
+const YEAR: u64 = 31536000;
+const DAY: u64 = 86400;
+
+/// Create a policy billed in `installments` with a five-day grace period and
+/// fund the holder with 1000 payment tokens
+fn setup_billed_policy(
+    env: &Env,
+    client: &AssetUpContractClient,
+    seed: u32,
+    holder: &Address,
+    insurer: &Address,
+    token: &Address,
+    installments: u32,
+    auto_renew: bool,
+) -> BytesN<32> {
+    let policy_id = generate_asset_id(env, seed);
+    let mut policy = create_test_policy(
+        env,
+        policy_id.clone(),
+        holder,
+        insurer,
+        generate_asset_id(env, 100),
+    );
+    policy.auto_renew = auto_renew;
+    client.create_insurance_policy(&policy);
+    client.set_premium_schedule(&policy_id, insurer, token, &installments, &(5 * DAY));
+    policy_id
+}
+
+#[test]
+fn test_premium_installments_and_lapse() {
+    let env = create_env();
+    let (admin, holder, insurer, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let (token, token_admin) = create_payment_token(&env, &admin);
+    token_admin.mint(&holder, &1000i128);
+    let policy_id = setup_billed_policy(
+        &env,
+        &client,
+        1,
+        &holder,
+        &insurer,
+        &token.address,
+        4,
+        false,
+    );
+
+    // The first of four installments of 25 is due at the start of the term
+    assert_eq!(client.get_premium_due(&policy_id), 25);
+    assert_eq!(client.pay_premium(&policy_id, &holder, &1u32), 25);
+    assert_eq!(token.balance(&insurer), 25);
+    assert_eq!(client.get_premium_due(&policy_id), 0);
+    assert_eq!(
+        client.try_pay_premium(&policy_id, &insurer, &1u32),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_pay_premium(&policy_id, &holder, &4u32),
+        Err(Ok(Error::InvalidPayment))
+    );
+
+    // Missing the second installment past its grace period suspends the policy
+    let interval = YEAR / 4;
+    env.ledger()
+        .with_mut(|li| li.timestamp = interval + 5 * DAY);
+    assert_eq!(
+        client.get_insurance_policy(&policy_id).unwrap().status,
+        PolicyStatus::Active
+    );
+    env.ledger()
+        .with_mut(|li| li.timestamp = interval + 5 * DAY + 1);
+    assert_eq!(
+        client.get_insurance_policy(&policy_id).unwrap().status,
+        PolicyStatus::Suspended
+    );
+    assert_eq!(client.process_renewals(&0u32, &10u32), 0);
+    assert!(client.get_premium_schedule(&policy_id).unwrap().lapsed);
+
+    // Catching up reinstates it
+    assert_eq!(client.pay_premium(&policy_id, &holder, &1u32), 25);
+    assert_eq!(
+        client.get_insurance_policy(&policy_id).unwrap().status,
+        PolicyStatus::Active
+    );
+    let payments = client.get_premium_payments(&policy_id);
+    assert_eq!(payments.len(), 2);
+    assert_eq!(payments.get(1).unwrap().first_installment, 1);
+    assert_eq!(payments.get(1).unwrap().paid_at, interval + 5 * DAY + 1);
+}
+
+#[test]
+fn test_process_renewals_renews_paid_auto_renew_policies() {
+    let env = create_env();
+    let (admin, holder, insurer, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let (token, token_admin) = create_payment_token(&env, &admin);
+    token_admin.mint(&holder, &1000i128);
+    let paid = setup_billed_policy(&env, &client, 1, &holder, &insurer, &token.address, 2, true);
+    let unpaid = setup_billed_policy(&env, &client, 2, &holder, &insurer, &token.address, 1, true);
+
+    assert_eq!(client.pay_premium(&paid, &holder, &2u32), 100);
+    assert_eq!(
+        client.try_renew_insurance_policy(&unpaid, &(2 * YEAR), &100i128, &insurer),
+        Err(Ok(Error::InvalidPayment))
+    );
+
+    // Not yet at the end of the term
+    env.ledger().with_mut(|li| li.timestamp = YEAR - 1);
+    assert_eq!(client.process_renewals(&0u32, &10u32), 0);
+    assert_eq!(
+        client.get_insurance_policy(&unpaid).unwrap().status,
+        PolicyStatus::Suspended
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = YEAR);
+    assert_eq!(client.process_renewals(&0u32, &10u32), 1);
+    let policy = client.get_insurance_policy(&paid).unwrap();
+    assert_eq!(policy.end_date, 2 * YEAR);
+    assert_eq!(policy.status, PolicyStatus::Active);
+    let schedule = client.get_premium_schedule(&paid).unwrap();
+    assert_eq!(schedule.term_start, YEAR);
+    assert_eq!(schedule.installments_paid, 0);
+    assert_eq!(client.get_premium_due(&paid), 50);
+}
+
+#[test]
+fn test_finished_policies_leave_the_billed_index() {
+    let env = create_env();
+    let (admin, holder, insurer, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let (token, token_admin) = create_payment_token(&env, &admin);
+    token_admin.mint(&holder, &1000i128);
+    let manual = setup_billed_policy(
+        &env,
+        &client,
+        1,
+        &holder,
+        &insurer,
+        &token.address,
+        1,
+        false,
+    );
+    let auto = setup_billed_policy(&env, &client, 2, &holder, &insurer, &token.address, 1, true);
+    let cancelled =
+        setup_billed_policy(&env, &client, 3, &holder, &insurer, &token.address, 1, true);
+    assert_eq!(client.get_billed_policy_count(), 3);
+
+    client.cancel_insurance_policy(&cancelled, &holder);
+    assert_eq!(client.get_billed_policy_count(), 2);
+
+    // The policies that remain are still processed
+    client.pay_premium(&manual, &holder, &1u32);
+    client.pay_premium(&auto, &holder, &1u32);
+    env.ledger().with_mut(|li| li.timestamp = YEAR);
+    assert_eq!(client.process_renewals(&0u32, &10u32), 1);
+
+    // Expiry drops a policy and renewing it brings it back
+    env.ledger().with_mut(|li| li.timestamp = YEAR + 1);
+    client.expire_insurance_policy(&manual);
+    assert_eq!(client.get_billed_policy_count(), 1);
+    client.renew_insurance_policy(&manual, &(2 * YEAR), &100i128, &insurer);
+    assert_eq!(client.get_billed_policy_count(), 2);
+    assert_eq!(client.pay_premium(&manual, &holder, &1u32), 100);
+}
+
+#[test]
+fn test_paid_auto_renew_policy_cannot_be_expired_before_renewal() {
+    let env = create_env();
+    let (admin, holder, insurer, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let (token, token_admin) = create_payment_token(&env, &admin);
+    token_admin.mint(&holder, &1000i128);
+    let paid = setup_billed_policy(&env, &client, 1, &holder, &insurer, &token.address, 1, true);
+    let manual = setup_billed_policy(
+        &env,
+        &client,
+        2,
+        &holder,
+        &insurer,
+        &token.address,
+        1,
+        false,
+    );
+    client.pay_premium(&paid, &holder, &1u32);
+    client.pay_premium(&manual, &holder, &1u32);
+
+    // Anyone could expire the policy once the term is over, before a renewal
+    // run reaches it; a paid auto-renewing policy is left to renew
+    env.ledger().with_mut(|li| li.timestamp = YEAR + 1);
+    assert_eq!(
+        client.try_expire_insurance_policy(&paid),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.expire_insurance_policy(&manual);
+
+    assert_eq!(client.process_renewals(&0u32, &10u32), 1);
+    let policy = client.get_insurance_policy(&paid).unwrap();
+    assert_eq!(policy.status, PolicyStatus::Active);
+    assert_eq!(policy.end_date, 2 * YEAR);
+}
+
+/// Register the insured asset and create a policy on it with coverage 10000
+/// and a 500 deductible
+fn setup_claimable_policy(
+    env: &Env,
+    client: &AssetUpContractClient,
+    admin: &Address,
+    holder: &Address,
+    insurer: &Address,
+) -> BytesN<32> {
+    let asset_id = generate_asset_id(env, 100);
+    client.register_asset(&create_test_asset(env, holder, asset_id.clone()), admin);
+    let policy_id = generate_asset_id(env, 1);
+    client.create_insurance_policy(&create_test_policy(
+        env,
+        policy_id.clone(),
+        holder,
+        insurer,
+        asset_id,
+    ));
+    policy_id
+}
+
+#[test]
+fn test_claim_lifecycle_applies_deductible() {
+    let env = create_env();
+    let (admin, holder, insurer, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let policy_id = setup_claimable_policy(&env, &client, &admin, &holder, &insurer);
+
+    assert_eq!(
+        client.try_file_insurance_claim(&policy_id, &insurer, &ClaimType::Damage, &5000i128),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_file_insurance_claim(&policy_id, &holder, &ClaimType::Damage, &500i128),
+        Err(Ok(Error::InvalidPayment))
+    );
+
+    let claim_id = client.file_insurance_claim(&policy_id, &holder, &ClaimType::Damage, &5000i128);
+    let claim = client.get_insurance_claim(&claim_id).unwrap();
+    assert_eq!(claim.asset_id, generate_asset_id(&env, 100));
+    assert_eq!(claim.status, ClaimStatus::Submitted);
+    assert_eq!(
+        client.get_asset_insurance_claims(&generate_asset_id(&env, 100)),
+        client.get_policy_insurance_claims(&policy_id)
+    );
+
+    // A second claim gets its own ID
+    let other = client.file_insurance_claim(&policy_id, &holder, &ClaimType::Theft, &1000i128);
+    assert_ne!(claim_id, other);
+
+    client.review_insurance_claim(&claim_id, &insurer);
+    assert_eq!(
+        client.try_approve_insurance_claim(&claim_id, &insurer, &6000i128),
+        Err(Ok(Error::InvalidPayment))
+    );
+    // An approved loss within the deductible pays nothing
+    assert_eq!(
+        client.try_approve_insurance_claim(&claim_id, &insurer, &500i128),
+        Err(Ok(Error::InvalidPayment))
+    );
+    client.approve_insurance_claim(&claim_id, &insurer, &4000i128);
+    let claim = client.get_insurance_claim(&claim_id).unwrap();
+    assert_eq!(claim.approved_amount, 4000);
+    assert_eq!(claim.payout_amount, 3500);
+    assert_eq!(client.get_policy_claimed_amount(&policy_id), 3500);
+
+    client.pay_insurance_claim(&claim_id, &insurer);
+    assert_eq!(
+        client.get_insurance_claim(&claim_id).unwrap().status,
+        ClaimStatus::Paid
+    );
+
+    // A rejected claim can be disputed and reviewed again
+    client.reject_insurance_claim(&other, &insurer);
+    assert_eq!(
+        client.try_dispute_insurance_claim(&other, &insurer),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.dispute_insurance_claim(&other, &holder);
+    client.review_insurance_claim(&other, &insurer);
+    assert_eq!(
+        client.get_insurance_claim(&other).unwrap().status,
+        ClaimStatus::UnderReview
+    );
+}
+
+#[test]
+fn test_claim_payouts_capped_at_coverage() {
+    let env = create_env();
+    let (admin, holder, insurer, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let policy_id = setup_claimable_policy(&env, &client, &admin, &holder, &insurer);
+
+    let file_reviewed_claim = || {
+        let claim_id =
+            client.file_insurance_claim(&policy_id, &holder, &ClaimType::Loss, &9000i128);
+        client.review_insurance_claim(&claim_id, &insurer);
+        claim_id
+    };
+    let claims = [
+        file_reviewed_claim(),
+        file_reviewed_claim(),
+        file_reviewed_claim(),
+    ];
+
+    client.approve_insurance_claim(&claims[0], &insurer, &9000i128);
+    assert_eq!(
+        client
+            .get_insurance_claim(&claims[0])
+            .unwrap()
+            .payout_amount,
+        8500
+    );
+    client.approve_insurance_claim(&claims[1], &insurer, &9000i128);
+    assert_eq!(
+        client
+            .get_insurance_claim(&claims[1])
+            .unwrap()
+            .payout_amount,
+        1500
+    );
+    assert_eq!(client.get_policy_claimed_amount(&policy_id), 10000);
+    assert_eq!(
+        client.try_approve_insurance_claim(&claims[2], &insurer, &9000i128),
+        Err(Ok(Error::InvalidPayment))
+    );
+}
+
+#[test]
+fn test_claim_requires_registered_asset() {
+    let env = create_env();
+    let (admin, holder, insurer, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let policy_id = generate_asset_id(&env, 1);
+    client.create_insurance_policy(&create_test_policy(
+        &env,
+        policy_id.clone(),
+        &holder,
+        &insurer,
+        generate_asset_id(&env, 100),
+    ));
+
+    assert_eq!(
+        client.try_file_insurance_claim(&policy_id, &holder, &ClaimType::Damage, &5000i128),
+        Err(Ok(Error::AssetNotFound))
+    );
+}
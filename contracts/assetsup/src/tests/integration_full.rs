@@ -1,6 +1,8 @@
+use crate::identity::InvestorType;
 use crate::tests::helpers::*;
 use crate::types::AssetType;
-use soroban_sdk::String;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{Address, String};
 
 #[test]
 fn test_full_asset_tokenization_workflow() {
@@ -120,7 +122,17 @@ fn test_transfer_restrictions_workflow() {
     // Set transfer restrictions
     client.set_transfer_restriction(&asset_id, &true);
 
-    // Add investor1 to whitelist
+    // Register investor1 as accredited and add to whitelist
+    let claim_issuer = Address::generate(&env);
+    client.add_claim_issuer(&claim_issuer);
+    client.set_identity(
+        &claim_issuer,
+        &investor1,
+        &true,
+        &String::from_str(&env, "US"),
+        &1000u64,
+        &InvestorType::Professional,
+    );
     client.add_to_whitelist(&asset_id, &investor1);
 
     // Transfer to whitelisted address should succeed
@@ -0,0 +1,1172 @@
+use crate::error::Error;
+use crate::lease::{
+    LeaseChange, LeasePaymentTerms, LeaseStatus, RenewalOption, RenewalTerms, SettlementStatus,
+    INSPECTION_PERIOD, RESPONSE_PERIOD,
+};
+use crate::tests::helpers::*;
+use crate::types::AssetType;
+use crate::AssetUpContractClient;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::token::TokenClient;
+use soroban_sdk::{Address, BytesN, Env, String};
+
+const DAY: u64 = 86400;
+const PERIOD: u64 = 30 * DAY;
+
+struct LeaseSetup<'a> {
+    client: AssetUpContractClient<'a>,
+    token: TokenClient<'a>,
+    lessor: Address,
+    lessee: Address,
+    lease_id: BytesN<32>,
+}
+
+fn payment_terms(
+    token: &Address,
+    manager: Option<Address>,
+    management_fee_bps: u32,
+) -> LeasePaymentTerms {
+    LeasePaymentTerms {
+        payment_token: token.clone(),
+        period_length: PERIOD,
+        grace_period: 5 * DAY,
+        late_fee: 50,
+        manager,
+        management_fee_bps,
+        early_termination_fee: 500,
+    }
+}
+
+/// A three-period lease of 1000 per period with a 2000 deposit, starting at `1000`
+fn setup_lease(env: &Env, manager: Option<Address>, management_fee_bps: u32) -> LeaseSetup<'_> {
+    setup_renewing_lease(env, manager, management_fee_bps, RenewalOption::Manual)
+}
+
+fn setup_renewing_lease(
+    env: &Env,
+    manager: Option<Address>,
+    management_fee_bps: u32,
+    renewal: RenewalOption,
+) -> LeaseSetup<'_> {
+    let (admin, lessor, lessee, _) = create_mock_addresses(env);
+    let client = initialize_contract(env, &admin);
+    let (token, token_admin) = create_payment_token(env, &Address::generate(env));
+    token_admin.mint(&lessee, &100_000i128);
+
+    let asset_id = generate_asset_id(env, 100);
+    client.register_asset(&create_test_asset(env, &lessor, asset_id.clone()), &admin);
+
+    let lease_id = generate_asset_id(env, 1);
+    client.create_lease(
+        &asset_id,
+        &lease_id,
+        &lessor,
+        &lessee,
+        &1000u64,
+        &(1000 + 3 * PERIOD),
+        &1000i128,
+        &2000i128,
+        &payment_terms(&token.address, manager, management_fee_bps),
+        &renewal,
+    );
+
+    LeaseSetup {
+        client,
+        token,
+        lessor,
+        lessee,
+        lease_id,
+    }
+}
+
+#[test]
+fn test_create_lease_escrows_deposit() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+
+    assert_eq!(s.token.balance(&s.client.address), 2000);
+    assert_eq!(s.token.balance(&s.lessee), 98_000);
+    assert_eq!(s.client.get_lease(&s.lease_id).deposit_held, 2000);
+
+    // Cancelling before the start refunds the deposit
+    s.client.cancel_lease(&s.lease_id, &s.lessor);
+    assert_eq!(s.token.balance(&s.lessee), 100_000);
+    assert_eq!(s.client.get_lease(&s.lease_id).deposit_held, 0);
+}
+
+#[test]
+fn test_pay_rent_advances_paid_through() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    assert_eq!(s.client.get_rent_due(&s.lease_id), 1000);
+    assert_eq!(s.client.get_rent_arrears(&s.lease_id), 0);
+    assert_eq!(s.client.get_rent_paid_through(&s.lease_id), 1000);
+
+    assert_eq!(s.client.pay_rent(&s.lease_id, &s.lessee, &2u32), 2000);
+    assert_eq!(s.token.balance(&s.lessor), 2000);
+    assert_eq!(
+        s.client.get_rent_paid_through(&s.lease_id),
+        1000 + 2 * PERIOD
+    );
+    assert_eq!(s.client.get_rent_due(&s.lease_id), 0);
+
+    // Cannot prepay beyond the lease term
+    assert_eq!(
+        s.client.try_pay_rent(&s.lease_id, &s.lessee, &2u32),
+        Err(Ok(Error::InvalidPayment))
+    );
+    assert_eq!(
+        s.client.try_pay_rent(&s.lease_id, &s.lessor, &1u32),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_missed_payments_charge_late_fees() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+
+    // Inside the grace period nothing is late yet
+    env.ledger().with_mut(|li| li.timestamp = 1000 + 5 * DAY);
+    assert_eq!(s.client.get_rent_arrears(&s.lease_id), 0);
+    assert_eq!(s.client.assess_late_fees(&s.lease_id), 0);
+
+    // Two periods past grace: both are missed
+    env.ledger()
+        .with_mut(|li| li.timestamp = 1000 + PERIOD + 6 * DAY);
+    assert_eq!(s.client.get_rent_due(&s.lease_id), 2100);
+    assert_eq!(s.client.get_rent_arrears(&s.lease_id), 2100);
+    assert_eq!(s.client.assess_late_fees(&s.lease_id), 2);
+    assert_eq!(s.client.assess_late_fees(&s.lease_id), 0);
+
+    let lease = s.client.get_lease(&s.lease_id);
+    assert_eq!(lease.missed_payments, 2);
+    assert_eq!(lease.late_fees_owed, 100);
+
+    // Paying one period clears the fees but leaves one period in arrears
+    assert_eq!(s.client.pay_rent(&s.lease_id, &s.lessee, &1u32), 1100);
+    assert_eq!(s.client.get_rent_arrears(&s.lease_id), 1000);
+    assert_eq!(s.client.pay_rent(&s.lease_id, &s.lessee, &1u32), 1000);
+    assert_eq!(s.client.get_rent_arrears(&s.lease_id), 0);
+    assert_eq!(s.client.get_lease(&s.lease_id).missed_payments, 2);
+}
+
+#[test]
+fn test_rent_split_with_manager() {
+    let env = create_env();
+    let manager = Address::generate(&env);
+    let s = setup_lease(&env, Some(manager.clone()), 1000);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    s.client.pay_rent(&s.lease_id, &s.lessee, &1u32);
+    assert_eq!(s.token.balance(&manager), 100);
+    assert_eq!(s.token.balance(&s.lessor), 900);
+}
+
+#[test]
+fn test_deposit_refunded_when_not_inspected() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    s.client.pay_rent(&s.lease_id, &s.lessee, &3u32);
+
+    let ended_at = 1001 + 3 * PERIOD;
+    env.ledger().with_mut(|li| li.timestamp = ended_at);
+    s.client.expire_lease(&s.lease_id);
+    assert_eq!(s.client.get_lease(&s.lease_id).status, LeaseStatus::Expired);
+    assert_eq!(s.token.balance(&s.client.address), 2000);
+
+    // The lessor has until the inspection period ends to file a report
+    assert_eq!(
+        s.client.try_settle_deposit(&s.lease_id),
+        Err(Ok(Error::InvalidTimestamps))
+    );
+    env.ledger()
+        .with_mut(|li| li.timestamp = ended_at + INSPECTION_PERIOD + 1);
+    s.client.settle_deposit(&s.lease_id);
+    assert_eq!(s.token.balance(&s.lessee), 97_000);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+    assert_eq!(
+        s.client.try_settle_deposit(&s.lease_id),
+        Err(Ok(Error::InvalidLeaseStatus))
+    );
+    // Nothing is left owing once the lease has ended
+    assert_eq!(
+        s.client.try_pay_rent(&s.lease_id, &s.lessee, &0u32),
+        Err(Ok(Error::InvalidPayment))
+    );
+}
+
+#[test]
+fn test_create_lease_rejects_zero_period() {
+    let env = create_env();
+    let (admin, lessor, lessee, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let (token, _) = create_payment_token(&env, &admin);
+
+    let mut terms = payment_terms(&token.address, None, 0);
+    terms.period_length = 0;
+    assert_eq!(
+        client.try_create_lease(
+            &generate_asset_id(&env, 100),
+            &generate_asset_id(&env, 1),
+            &lessor,
+            &lessee,
+            &1000u64,
+            &2000u64,
+            &1000i128,
+            &0i128,
+            &terms,
+            &RenewalOption::Manual,
+        ),
+        Err(Ok(Error::InvalidTimestamps))
+    );
+}
+
+/// Return the lease at `2000` and file a report proposing `deduction`
+fn return_and_inspect(env: &Env, s: &LeaseSetup, deduction: i128) {
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+    s.client.return_leased_asset(&s.lease_id, &s.lessee);
+    s.client.file_condition_report(
+        &s.lease_id,
+        &s.lessor,
+        &BytesN::from_array(env, &[9u8; 32]),
+        &Some(BytesN::from_array(env, &[7u8; 32])),
+        &deduction,
+    );
+}
+
+#[test]
+fn test_lessee_accepts_deductions() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+    return_and_inspect(&env, &s, 600);
+
+    let settlement = s.client.get_deposit_settlement(&s.lease_id).unwrap();
+    assert_eq!(settlement.status, SettlementStatus::Proposed);
+    assert_eq!(
+        settlement.maintenance_record_hash,
+        Some(BytesN::from_array(&env, &[7u8; 32]))
+    );
+
+    s.client
+        .respond_to_deductions(&s.lease_id, &s.lessee, &true);
+    assert_eq!(s.token.balance(&s.lessor), 600);
+    assert_eq!(s.token.balance(&s.lessee), 99_400);
+
+    let settlement = s.client.get_deposit_settlement(&s.lease_id).unwrap();
+    assert_eq!(settlement.status, SettlementStatus::Settled);
+    assert_eq!(settlement.final_deduction, 600);
+    assert_eq!(s.client.get_lease(&s.lease_id).deposit_held, 0);
+}
+
+#[test]
+fn test_unanswered_deductions_auto_settle() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+    return_and_inspect(&env, &s, 2000);
+
+    assert_eq!(
+        s.client.try_settle_deposit(&s.lease_id),
+        Err(Ok(Error::InvalidTimestamps))
+    );
+    env.ledger()
+        .with_mut(|li| li.timestamp = 2000 + RESPONSE_PERIOD + 1);
+    assert_eq!(
+        s.client
+            .try_respond_to_deductions(&s.lease_id, &s.lessee, &false),
+        Err(Ok(Error::InvalidTimestamps))
+    );
+    s.client.settle_deposit(&s.lease_id);
+    assert_eq!(s.token.balance(&s.lessor), 2000);
+    assert_eq!(s.token.balance(&s.lessee), 98_000);
+}
+
+#[test]
+fn test_disputed_deductions_resolved_by_admin() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+    return_and_inspect(&env, &s, 1500);
+
+    s.client
+        .respond_to_deductions(&s.lease_id, &s.lessee, &false);
+    assert_eq!(
+        s.client.get_deposit_settlement(&s.lease_id).unwrap().status,
+        SettlementStatus::Disputed
+    );
+
+    // A dispute does not auto-settle
+    env.ledger()
+        .with_mut(|li| li.timestamp = 2000 + RESPONSE_PERIOD + 1);
+    assert_eq!(
+        s.client.try_settle_deposit(&s.lease_id),
+        Err(Ok(Error::InvalidLeaseStatus))
+    );
+
+    s.client.resolve_deposit_dispute(&s.lease_id, &500i128);
+    assert_eq!(s.token.balance(&s.lessor), 500);
+    assert_eq!(s.token.balance(&s.lessee), 99_500);
+}
+
+#[test]
+fn test_condition_report_validation() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+    let report = BytesN::from_array(&env, &[9u8; 32]);
+
+    // Not returned yet
+    assert_eq!(
+        s.client
+            .try_file_condition_report(&s.lease_id, &s.lessor, &report, &None, &0i128),
+        Err(Ok(Error::InvalidLeaseStatus))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+    s.client.return_leased_asset(&s.lease_id, &s.lessee);
+    assert_eq!(
+        s.client
+            .try_file_condition_report(&s.lease_id, &s.lessee, &report, &None, &0i128),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        s.client
+            .try_file_condition_report(&s.lease_id, &s.lessor, &report, &None, &2001i128),
+        Err(Ok(Error::InvalidPayment))
+    );
+}
+
+#[test]
+fn test_amendment_requires_lessee_acceptance() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+    let new_end = 1000 + 4 * PERIOD;
+
+    assert_eq!(
+        s.client.try_propose_lease_amendment(
+            &s.lease_id,
+            &s.lessee,
+            &new_end,
+            &1200i128,
+            &RenewalOption::Manual
+        ),
+        Err(Ok(Error::Unauthorized))
+    );
+    s.client.propose_lease_amendment(
+        &s.lease_id,
+        &s.lessor,
+        &new_end,
+        &1200i128,
+        &RenewalOption::Manual,
+    );
+    assert!(s.client.get_pending_amendment(&s.lease_id).is_some());
+
+    // Rejection leaves the lease unchanged
+    s.client
+        .respond_to_amendment(&s.lease_id, &s.lessee, &false);
+    assert_eq!(s.client.get_lease(&s.lease_id).rent_per_period, 1000);
+    assert!(s.client.get_pending_amendment(&s.lease_id).is_none());
+
+    s.client.propose_lease_amendment(
+        &s.lease_id,
+        &s.lessor,
+        &new_end,
+        &1200i128,
+        &RenewalOption::Manual,
+    );
+    s.client.respond_to_amendment(&s.lease_id, &s.lessee, &true);
+    let lease = s.client.get_lease(&s.lease_id);
+    assert_eq!(lease.end_timestamp, new_end);
+    assert_eq!(lease.rent_per_period, 1200);
+    assert_eq!(lease.version, 2);
+
+    let versions = s.client.get_lease_versions(&s.lease_id);
+    assert_eq!(versions.len(), 2);
+    assert_eq!(versions.get(0).unwrap().change, LeaseChange::Created);
+    assert_eq!(versions.get(0).unwrap().rent_per_period, 1000);
+    assert_eq!(versions.get(1).unwrap().change, LeaseChange::Amended);
+    assert_eq!(versions.get(1).unwrap().end_timestamp, new_end);
+}
+
+#[test]
+fn test_amendment_cannot_cut_into_paid_rent() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    s.client.pay_rent(&s.lease_id, &s.lessee, &2u32);
+    assert_eq!(
+        s.client.try_propose_lease_amendment(
+            &s.lease_id,
+            &s.lessor,
+            &(1000 + PERIOD),
+            &1000i128,
+            &RenewalOption::Manual
+        ),
+        Err(Ok(Error::InvalidTimestamps))
+    );
+}
+
+#[test]
+fn test_auto_renewal_and_notice() {
+    let env = create_env();
+    let renewal = RenewalTerms {
+        term: PERIOD,
+        notice_period: 10 * DAY,
+    };
+    let s = setup_renewing_lease(&env, None, 0, RenewalOption::Auto(renewal));
+    let end = 1000 + 3 * PERIOD;
+
+    // Too early to renew while notice can still be given
+    env.ledger().with_mut(|li| li.timestamp = end - 10 * DAY);
+    assert_eq!(
+        s.client.try_renew_lease(&s.lease_id),
+        Err(Ok(Error::LeaseNotExpired))
+    );
+
+    // Past the notice deadline the lease renews and cannot simply expire
+    env.ledger().with_mut(|li| li.timestamp = end + 1);
+    assert_eq!(
+        s.client.try_expire_lease(&s.lease_id),
+        Err(Ok(Error::InvalidLeaseStatus))
+    );
+    assert_eq!(s.client.renew_lease(&s.lease_id), end + PERIOD);
+    // Four periods started, the first three late
+    assert_eq!(s.client.get_rent_due(&s.lease_id), 4150);
+
+    // Notice in time stops the next renewal
+    s.client.give_renewal_notice(&s.lease_id, &s.lessee);
+    assert_eq!(
+        s.client.get_lease(&s.lease_id).renewal,
+        RenewalOption::Manual
+    );
+    env.ledger().with_mut(|li| li.timestamp = end + PERIOD + 1);
+    s.client.expire_lease(&s.lease_id);
+
+    let versions = s.client.get_lease_versions(&s.lease_id);
+    assert_eq!(versions.get(1).unwrap().change, LeaseChange::Renewed);
+    assert_eq!(
+        versions.get(2).unwrap().change,
+        LeaseChange::RenewalCancelled
+    );
+}
+
+#[test]
+fn test_early_termination_pays_rent_due_and_penalty() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    s.client.pay_rent(&s.lease_id, &s.lessee, &1u32);
+
+    // Second period started but unpaid: 1000 rent + 500 penalty
+    env.ledger()
+        .with_mut(|li| li.timestamp = 1000 + PERIOD + DAY);
+    assert_eq!(
+        s.client.try_terminate_lease_early(&s.lease_id, &s.lessor),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(s.client.terminate_lease_early(&s.lease_id, &s.lessee), 1500);
+    assert_eq!(s.token.balance(&s.lessor), 2500);
+
+    let lease = s.client.get_lease(&s.lease_id);
+    assert_eq!(lease.status, LeaseStatus::Terminated);
+    assert_eq!(lease.end_timestamp, 1000 + PERIOD + DAY);
+    assert!(s.client.get_asset_active_lease(&lease.asset_id).is_none());
+
+    // The deposit goes through the usual return inspection
+    s.client.file_condition_report(
+        &s.lease_id,
+        &s.lessor,
+        &BytesN::from_array(&env, &[9u8; 32]),
+        &None,
+        &0i128,
+    );
+    s.client
+        .respond_to_deductions(&s.lease_id, &s.lessee, &true);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+}
+
+type LeaseResult = Result<(), Result<Error, soroban_sdk::InvokeError>>;
+
+/// Book `lease_id` on the setup's asset for `[start, end)`
+fn book_lease(env: &Env, s: &LeaseSetup, seed: u32, start: u64, end: u64) -> LeaseResult {
+    book_lease_as(env, s, &s.lessor, seed, start, end, 1000)
+}
+
+fn book_lease_as(
+    env: &Env,
+    s: &LeaseSetup,
+    lessor: &Address,
+    seed: u32,
+    start: u64,
+    end: u64,
+    rent: i128,
+) -> LeaseResult {
+    let lease = s.client.get_lease(&s.lease_id);
+    s.client
+        .try_create_lease(
+            &lease.asset_id,
+            &generate_asset_id(env, seed),
+            lessor,
+            &s.lessee,
+            &start,
+            &end,
+            &rent,
+            &0i128,
+            &lease.terms,
+            &RenewalOption::Manual,
+        )
+        .map(|_| ())
+}
+
+#[test]
+fn test_calendar_allows_future_bookings_without_overlap() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+    let end = 1000 + 3 * PERIOD;
+
+    // Back-to-back booking after the current lease is allowed
+    assert_eq!(book_lease(&env, &s, 2, end, end + PERIOD), Ok(()));
+    // Overlapping either booking is not
+    assert_eq!(
+        book_lease(&env, &s, 3, end - DAY, end + DAY),
+        Err(Ok(Error::AssetAlreadyLeased))
+    );
+    // A gap before the first lease can still be filled
+    assert_eq!(book_lease(&env, &s, 4, 0, 1000), Ok(()));
+
+    let asset_id = s.client.get_lease(&s.lease_id).asset_id;
+    let bookings = s.client.get_asset_bookings(&asset_id);
+    assert_eq!(bookings.len(), 3);
+    assert_eq!(
+        bookings.get(0).unwrap().lease_id,
+        generate_asset_id(&env, 4)
+    );
+    assert_eq!(bookings.get(1).unwrap().lease_id, s.lease_id);
+    assert_eq!(
+        bookings.get(2).unwrap().lease_id,
+        generate_asset_id(&env, 2)
+    );
+
+    assert_eq!(
+        s.client
+            .get_leases_in_range(&asset_id, &(end - 1), &(end + 1)),
+        soroban_sdk::vec![&env, s.lease_id.clone(), generate_asset_id(&env, 2)]
+    );
+    assert_eq!(
+        s.client.get_next_free_window(&asset_id, &0u64, &DAY),
+        end + PERIOD
+    );
+    assert_eq!(
+        s.client.try_get_leases_in_range(&asset_id, &5u64, &5u64),
+        Err(Ok(Error::InvalidTimestamps))
+    );
+}
+
+#[test]
+fn test_scheduled_lease_promoted_when_due() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+    let asset_id = s.client.get_lease(&s.lease_id).asset_id;
+    let end = 1000 + 3 * PERIOD;
+    book_lease(&env, &s, 2, end, end + PERIOD).unwrap();
+    let next_id = generate_asset_id(&env, 2);
+
+    assert_eq!(
+        s.client.get_lease(&s.lease_id).status,
+        LeaseStatus::Scheduled
+    );
+    assert!(s.client.get_asset_active_lease(&asset_id).is_none());
+    assert_eq!(
+        s.client.try_activate_lease(&s.lease_id),
+        Err(Ok(Error::InvalidTimestamps))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    assert_eq!(
+        s.client.get_asset_active_lease(&asset_id).unwrap().lease_id,
+        s.lease_id
+    );
+    s.client.activate_lease(&s.lease_id);
+    assert_eq!(s.client.get_lease(&s.lease_id).status, LeaseStatus::Active);
+    assert_eq!(
+        s.client.try_activate_lease(&s.lease_id),
+        Err(Ok(Error::InvalidLeaseStatus))
+    );
+
+    // Returning early frees the slot; the next booking takes over at its start
+    s.client.return_leased_asset(&s.lease_id, &s.lessee);
+    assert!(s.client.get_asset_active_lease(&asset_id).is_none());
+    assert_eq!(
+        s.client.get_next_free_window(&asset_id, &1000u64, &DAY),
+        1000
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = end);
+    let active = s.client.get_asset_active_lease(&asset_id).unwrap();
+    assert_eq!(active.lease_id, next_id);
+    assert_eq!(active.status, LeaseStatus::Active);
+}
+
+#[test]
+fn test_extension_cannot_overlap_next_booking() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+    let end = 1000 + 3 * PERIOD;
+    book_lease(&env, &s, 2, end + PERIOD, end + 2 * PERIOD).unwrap();
+
+    s.client.propose_lease_amendment(
+        &s.lease_id,
+        &s.lessor,
+        &(end + 2 * PERIOD),
+        &1000i128,
+        &RenewalOption::Manual,
+    );
+    assert_eq!(
+        s.client
+            .try_respond_to_amendment(&s.lease_id, &s.lessee, &true),
+        Err(Ok(Error::AssetAlreadyLeased))
+    );
+
+    s.client.propose_lease_amendment(
+        &s.lease_id,
+        &s.lessor,
+        &(end + PERIOD),
+        &1000i128,
+        &RenewalOption::Manual,
+    );
+    s.client.respond_to_amendment(&s.lease_id, &s.lessee, &true);
+    let asset_id = s.client.get_lease(&s.lease_id).asset_id;
+    assert_eq!(
+        s.client.get_asset_bookings(&asset_id).get(0).unwrap().end,
+        end + PERIOD
+    );
+}
+
+#[test]
+fn test_only_owner_or_manager_can_lease() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+    let asset_id = s.client.get_lease(&s.lease_id).asset_id;
+    let end = 1000 + 3 * PERIOD;
+    let manager = Address::generate(&env);
+
+    assert_eq!(
+        book_lease_as(&env, &s, &manager, 2, end, end + PERIOD, 1000),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        book_lease_as(&env, &s, &s.lessor, 2, end, end + PERIOD, -1),
+        Err(Ok(Error::InvalidPayment))
+    );
+
+    s.client
+        .set_lease_manager(&asset_id, &manager, &true, &s.lessor);
+    assert!(s.client.is_lease_manager(&asset_id, &manager));
+    book_lease_as(&env, &s, &manager, 2, end, end + PERIOD, 1000).unwrap();
+    assert_eq!(
+        s.client
+            .try_set_lease_manager(&asset_id, &manager, &false, &manager),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    // Delegations lapse once the asset changes hands
+    let buyer = Address::generate(&env);
+    s.client
+        .transfer_asset_ownership(&asset_id, &buyer, &s.lessor);
+    assert!(!s.client.is_lease_manager(&asset_id, &manager));
+    assert_eq!(
+        book_lease_as(&env, &s, &manager, 3, end + PERIOD, end + 2 * PERIOD, 1000),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_cannot_lease_unregistered_or_retired_asset() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+    let asset_id = s.client.get_lease(&s.lease_id).asset_id;
+    let end = 1000 + 3 * PERIOD;
+
+    let terms = s.client.get_lease(&s.lease_id).terms;
+    assert_eq!(
+        s.client.try_create_lease(
+            &generate_asset_id(&env, 200),
+            &generate_asset_id(&env, 2),
+            &s.lessor,
+            &s.lessee,
+            &end,
+            &(end + PERIOD),
+            &1000i128,
+            &0i128,
+            &terms,
+            &RenewalOption::Manual,
+        ),
+        Err(Ok(Error::AssetNotFound))
+    );
+
+    s.client.retire_asset(&asset_id, &s.lessor);
+    assert_eq!(
+        book_lease(&env, &s, 2, end, end + PERIOD),
+        Err(Ok(Error::InvalidLeaseStatus))
+    );
+}
+
+#[test]
+fn test_cannot_lease_detokenized_asset() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+    let asset_id = s.client.get_lease(&s.lease_id).asset_id;
+    let end = 1000 + 3 * PERIOD;
+
+    s.client.tokenize_asset(
+        &1u64,
+        &String::from_str(&env, "LSE"),
+        &1000i128,
+        &0u32,
+        &0i128,
+        &s.lessor,
+        &String::from_str(&env, "Leased Asset"),
+        &String::from_str(&env, "A tokenized leased asset"),
+        &AssetType::Physical,
+    );
+
+    // Only the registry owner's own tokens can represent the asset
+    assert_eq!(
+        s.client
+            .try_link_registry_asset(&1u64, &generate_asset_id(&env, 200), &s.lessor),
+        Err(Ok(Error::AssetNotFound))
+    );
+    s.client.tokenize_asset(
+        &2u64,
+        &String::from_str(&env, "OTH"),
+        &1000i128,
+        &0u32,
+        &0i128,
+        &s.lessee,
+        &String::from_str(&env, "Other Asset"),
+        &String::from_str(&env, "Tokenized by someone else"),
+        &AssetType::Physical,
+    );
+    assert_eq!(
+        s.client
+            .try_link_registry_asset(&2u64, &asset_id, &s.lessee),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    s.client.link_registry_asset(&1u64, &asset_id, &s.lessor);
+    assert_eq!(s.client.get_registry_asset(&1u64), Some(asset_id.clone()));
+    assert_eq!(
+        s.client
+            .try_link_registry_asset(&1u64, &asset_id, &s.lessor),
+        Err(Ok(Error::AssetAlreadyTokenized))
+    );
+
+    // Still tokenized, so leasing continues
+    assert!(book_lease(&env, &s, 2, end, end + PERIOD).is_ok());
+
+    let proposal_id = s.client.propose_detokenization(&1u64, &s.lessor);
+    s.client.cast_vote(&1u64, &proposal_id, &s.lessor);
+    s.client.execute_detokenization(&1u64, &proposal_id);
+
+    assert_eq!(s.client.get_registry_asset(&1u64), None);
+    assert_eq!(
+        book_lease(&env, &s, 3, end + PERIOD, end + 2 * PERIOD),
+        Err(Ok(Error::InvalidLeaseStatus))
+    );
+}
+
+#[test]
+fn test_asset_transfer_carries_lease_to_new_owner() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+    let asset_id = s.client.get_lease(&s.lease_id).asset_id;
+
+    // Booked but not yet started
+    assert!(!s.client.get_asset_info(&asset_id).leased);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    assert!(s.client.get_asset_info(&asset_id).leased);
+
+    let buyer = Address::generate(&env);
+    s.client
+        .transfer_asset_ownership(&asset_id, &buyer, &s.lessor);
+    let lease = s.client.get_lease(&s.lease_id);
+    assert_eq!(lease.lessor, buyer);
+    let versions = s.client.get_lease_versions(&s.lease_id);
+    assert_eq!(
+        versions.get(versions.len() - 1).unwrap().change,
+        LeaseChange::LessorTransferred
+    );
+
+    // Rent now flows to the new owner
+    s.client.pay_rent(&s.lease_id, &s.lessee, &1u32);
+    assert_eq!(s.token.balance(&buyer), 1000);
+    assert_eq!(s.token.balance(&s.lessor), 0);
+
+    s.client.return_leased_asset(&s.lease_id, &s.lessee);
+    assert!(!s.client.get_asset_info(&asset_id).leased);
+}
+
+#[test]
+fn test_lessor_and_asset_lease_indexes() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+    let asset_id = s.client.get_lease(&s.lease_id).asset_id;
+    let end = 1000 + 3 * PERIOD;
+    book_lease(&env, &s, 2, end, end + PERIOD).unwrap();
+    book_lease(&env, &s, 3, end + PERIOD, end + 2 * PERIOD).unwrap();
+    s.client
+        .cancel_lease(&generate_asset_id(&env, 3), &s.lessor);
+
+    // The cancelled lease leaves the lessor's index
+    let all = s.client.get_lessor_leases(&s.lessor, &None, &0u32, &10u32);
+    assert_eq!(all.len(), 2);
+    assert_eq!(all.get(0).unwrap().lease_id, s.lease_id);
+
+    let scheduled =
+        s.client
+            .get_lessor_leases(&s.lessor, &Some(LeaseStatus::Scheduled), &0u32, &10u32);
+    assert_eq!(scheduled.len(), 2);
+    let page = s
+        .client
+        .get_lessor_leases(&s.lessor, &Some(LeaseStatus::Scheduled), &1u32, &1u32);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().lease_id, generate_asset_id(&env, 2));
+    assert_eq!(
+        s.client
+            .get_lessor_leases(&s.lessor, &Some(LeaseStatus::Cancelled), &0u32, &10u32)
+            .len(),
+        0
+    );
+    let cancelled =
+        s.client
+            .get_asset_lease_history(&asset_id, &Some(LeaseStatus::Cancelled), &0u32, &10u32);
+    assert_eq!(
+        cancelled.get(0).unwrap().lease_id,
+        generate_asset_id(&env, 3)
+    );
+
+    // After a sale the live leases move to the buyer; the asset keeps its full history
+    let buyer = Address::generate(&env);
+    s.client
+        .transfer_asset_ownership(&asset_id, &buyer, &s.lessor);
+    assert_eq!(
+        s.client
+            .get_lessor_leases(&s.lessor, &None, &0u32, &10u32)
+            .len(),
+        0
+    );
+    assert_eq!(
+        s.client
+            .get_lessor_leases(&buyer, &None, &0u32, &10u32)
+            .len(),
+        2
+    );
+    assert_eq!(
+        s.client
+            .get_asset_lease_history(&asset_id, &None, &0u32, &10u32)
+            .len(),
+        3
+    );
+}
+
+#[test]
+fn test_lessor_portfolio_summary() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+    let end = 1000 + 3 * PERIOD;
+    book_lease(&env, &s, 2, end, end + PERIOD).unwrap();
+
+    // Two periods of the first lease are past their grace period
+    env.ledger()
+        .with_mut(|li| li.timestamp = 1000 + PERIOD + 6 * DAY);
+    let portfolio = s
+        .client
+        .get_lessor_portfolio(&s.lessor, &(1000 + PERIOD), &(end + PERIOD));
+    assert_eq!(portfolio.total_leases, 2);
+    assert_eq!(portfolio.active_leases, 1);
+    assert_eq!(portfolio.scheduled_leases, 1);
+    // Two remaining periods of the first lease plus the only period of the second
+    assert_eq!(portfolio.expected_rent, 3000);
+    assert_eq!(portfolio.overdue_leases, 1);
+    assert_eq!(portfolio.arrears, 2100);
+
+    s.client.pay_rent(&s.lease_id, &s.lessee, &2u32);
+    let portfolio = s.client.get_lessor_portfolio(&s.lessor, &0u64, &end);
+    assert_eq!(portfolio.expected_rent, 3000);
+    assert_eq!(portfolio.overdue_leases, 0);
+
+    // Ended leases no longer count towards the portfolio
+    s.client.return_leased_asset(&s.lease_id, &s.lessee);
+    let portfolio = s.client.get_lessor_portfolio(&s.lessor, &0u64, &end);
+    assert_eq!(portfolio.total_leases, 1);
+    assert_eq!(portfolio.active_leases, 0);
+
+    assert_eq!(
+        s.client.try_get_lessor_portfolio(&s.lessor, &end, &end),
+        Err(Ok(Error::InvalidTimestamps))
+    );
+}
+
+/// Sublet `[start, end)` of the setup's lease to a new sublessee at 800 per period
+/// with a 500 deposit
+fn sublet(env: &Env, s: &LeaseSetup, seed: u32, start: u64, end: u64) -> (Address, LeaseResult) {
+    let sublessee = Address::generate(env);
+    s.token.transfer(&s.lessee, &sublessee, &5000i128);
+    let terms = s.client.get_lease(&s.lease_id).terms;
+    let result = s
+        .client
+        .try_create_sublease(
+            &s.lease_id,
+            &generate_asset_id(env, seed),
+            &s.lessee,
+            &sublessee,
+            &start,
+            &end,
+            &800i128,
+            &500i128,
+            &terms,
+        )
+        .map(|_| ());
+    (sublessee, result)
+}
+
+#[test]
+fn test_sublease_bounded_by_parent_term() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+    let end = 1000 + 3 * PERIOD;
+    let sublease_id = generate_asset_id(&env, 2);
+
+    let terms = s.client.get_lease(&s.lease_id).terms;
+    assert_eq!(
+        s.client.try_create_sublease(
+            &s.lease_id,
+            &sublease_id,
+            &s.lessor,
+            &Address::generate(&env),
+            &1000u64,
+            &end,
+            &800i128,
+            &0i128,
+            &terms,
+        ),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        sublet(&env, &s, 2, 1000 + PERIOD, end + 1).1,
+        Err(Ok(Error::InvalidTimestamps))
+    );
+
+    let (sublessee, result) = sublet(&env, &s, 2, 1000 + PERIOD, 1000 + 2 * PERIOD);
+    result.unwrap();
+    assert_eq!(
+        sublet(&env, &s, 3, 1000 + PERIOD + DAY, end).1,
+        Err(Ok(Error::AssetAlreadyLeased))
+    );
+    assert_eq!(s.client.get_subleases(&s.lease_id).len(), 1);
+
+    let sublease = s.client.get_lease(&sublease_id);
+    assert_eq!(sublease.parent, Some(s.lease_id.clone()));
+    assert_eq!(sublease.lessor, s.lessee);
+    assert_eq!(s.token.balance(&sublessee), 4500);
+
+    // Only the sublessor acts as lessor on the sublease
+    assert_eq!(
+        s.client.try_cancel_lease(&sublease_id, &s.lessor),
+        Err(Ok(Error::Unauthorized))
+    );
+    env.ledger().with_mut(|li| li.timestamp = 1000 + PERIOD);
+    s.client.pay_rent(&sublease_id, &sublessee, &1u32);
+    assert_eq!(s.token.balance(&sublessee), 3700);
+
+    // The parent cannot be shortened below the sublease
+    assert_eq!(
+        s.client.try_propose_lease_amendment(
+            &s.lease_id,
+            &s.lessor,
+            &(1000 + PERIOD + DAY),
+            &1000i128,
+            &RenewalOption::Manual,
+        ),
+        Err(Ok(Error::InvalidTimestamps))
+    );
+}
+
+#[test]
+fn test_parent_end_cascades_to_subleases() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+    let sublease_id = generate_asset_id(&env, 2);
+    let (sublessee, result) = sublet(&env, &s, 2, 1000, 1000 + 2 * PERIOD);
+    result.unwrap();
+
+    env.ledger().with_mut(|li| li.timestamp = 1000 + DAY);
+    s.client.return_leased_asset(&s.lease_id, &s.lessee);
+
+    let sublease = s.client.get_lease(&sublease_id);
+    assert_eq!(sublease.status, LeaseStatus::Returned);
+    assert_eq!(sublease.ended_at, 1000 + DAY);
+    assert_eq!(sublease.end_timestamp, 1000 + DAY);
+    let versions = s.client.get_lease_versions(&sublease_id);
+    assert_eq!(
+        versions.get(versions.len() - 1).unwrap().change,
+        LeaseChange::ParentEnded
+    );
+    // The sublessor inspects the returned equipment as usual
+    s.client.file_condition_report(
+        &sublease_id,
+        &s.lessee,
+        &generate_asset_id(&env, 50),
+        &None,
+        &100i128,
+    );
+
+    // Cancelling a parent before it starts refunds its subleases' deposits
+    let end = 1000 + 3 * PERIOD;
+    book_lease(&env, &s, 3, end, end + PERIOD).unwrap();
+    let next_id = generate_asset_id(&env, 3);
+    let terms = s.client.get_lease(&next_id).terms;
+    s.client.create_sublease(
+        &next_id,
+        &generate_asset_id(&env, 4),
+        &s.lessee,
+        &sublessee,
+        &end,
+        &(end + PERIOD),
+        &800i128,
+        &500i128,
+        &terms,
+    );
+    let balance = s.token.balance(&sublessee);
+    s.client.cancel_lease(&next_id, &s.lessor);
+    assert_eq!(
+        s.client.get_lease(&generate_asset_id(&env, 4)).status,
+        LeaseStatus::Cancelled
+    );
+    assert_eq!(s.token.balance(&sublessee), balance + 500);
+}
+
+#[test]
+fn test_assign_lease_requires_lessor_consent() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+    let assignee = Address::generate(&env);
+    s.token.transfer(&s.lessee, &assignee, &10_000i128);
+
+    assert_eq!(
+        s.client.try_assign_lease(&s.lease_id, &s.lessee, &assignee),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        s.client
+            .try_consent_to_assignment(&s.lease_id, &s.lessee, &assignee),
+        Err(Ok(Error::Unauthorized))
+    );
+    s.client
+        .consent_to_assignment(&s.lease_id, &s.lessor, &assignee);
+
+    // Live subleases must end first
+    let (_, result) = sublet(&env, &s, 2, 1000, 1000 + PERIOD);
+    result.unwrap();
+    assert_eq!(
+        s.client.try_assign_lease(&s.lease_id, &s.lessee, &assignee),
+        Err(Ok(Error::InvalidLeaseStatus))
+    );
+    s.client
+        .cancel_lease(&generate_asset_id(&env, 2), &s.lessee);
+
+    let before = s.token.balance(&s.lessee);
+    s.client.assign_lease(&s.lease_id, &s.lessee, &assignee);
+    assert_eq!(s.token.balance(&s.lessee), before + 2000);
+    assert_eq!(s.token.balance(&assignee), 8000);
+
+    let lease = s.client.get_lease(&s.lease_id);
+    assert_eq!(lease.lessee, assignee);
+    assert!(s.client.get_lessee_leases(&s.lessee).is_empty());
+    assert_eq!(s.client.get_lessee_leases(&assignee).len(), 1);
+    let versions = s.client.get_lease_versions(&s.lease_id);
+    assert_eq!(
+        versions.get(versions.len() - 1).unwrap().change,
+        LeaseChange::Assigned
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    assert_eq!(
+        s.client.try_pay_rent(&s.lease_id, &s.lessee, &1u32),
+        Err(Ok(Error::Unauthorized))
+    );
+    s.client.pay_rent(&s.lease_id, &assignee, &1u32);
+}
+
+#[test]
+fn test_return_requires_settled_arrears() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+
+    // One period past grace and unpaid
+    env.ledger().with_mut(|li| li.timestamp = 1000 + 6 * DAY);
+    assert_eq!(s.client.get_rent_arrears(&s.lease_id), 1050);
+    assert_eq!(
+        s.client.try_return_leased_asset(&s.lease_id, &s.lessee),
+        Err(Ok(Error::InvalidPayment))
+    );
+
+    s.client.pay_rent(&s.lease_id, &s.lessee, &1u32);
+    s.client.return_leased_asset(&s.lease_id, &s.lessee);
+    assert_eq!(
+        s.client.get_lease(&s.lease_id).status,
+        LeaseStatus::Returned
+    );
+}
+
+#[test]
+fn test_arrears_payable_after_lease_ends() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+
+    // The lease runs out with the last period unpaid
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    s.client.pay_rent(&s.lease_id, &s.lessee, &2u32);
+    env.ledger()
+        .with_mut(|li| li.timestamp = 1000 + 3 * PERIOD + 1);
+    s.client.expire_lease(&s.lease_id);
+    assert_eq!(s.client.get_rent_arrears(&s.lease_id), 1050);
+
+    // Rent for periods that never started cannot be paid
+    assert_eq!(
+        s.client.try_pay_rent(&s.lease_id, &s.lessee, &2u32),
+        Err(Ok(Error::InvalidPayment))
+    );
+    assert_eq!(
+        s.client.try_pay_rent(&s.lease_id, &s.lessee, &u32::MAX),
+        Err(Ok(Error::InvalidPayment))
+    );
+
+    let lessor_before = s.token.balance(&s.lessor);
+    assert_eq!(s.client.pay_rent(&s.lease_id, &s.lessee, &1u32), 1050);
+    assert_eq!(s.token.balance(&s.lessor), lessor_before + 1050);
+    assert_eq!(s.client.get_rent_arrears(&s.lease_id), 0);
+}
+
+#[test]
+fn test_amendment_rejects_negative_rent() {
+    let env = create_env();
+    let s = setup_lease(&env, None, 0);
+    let new_end = 1000 + 4 * PERIOD;
+
+    assert_eq!(
+        s.client.try_propose_lease_amendment(
+            &s.lease_id,
+            &s.lessor,
+            &new_end,
+            &-1i128,
+            &RenewalOption::Manual
+        ),
+        Err(Ok(Error::InvalidPayment))
+    );
+    assert_eq!(s.client.get_lease(&s.lease_id).rent_per_period, 1000);
+}
@@ -0,0 +1,283 @@
+use crate::error::Error;
+use crate::marketplace::{OrderSide, OrderStatus};
+use crate::tests::helpers::*;
+use crate::types::AssetType;
+use crate::AssetUpContractClient;
+use soroban_sdk::testutils::Ledger;
+use soroban_sdk::token::TokenClient;
+use soroban_sdk::{Address, Env, String};
+
+/// Tokenize 10,000 whole tokens (2 decimals) and open a market
+fn setup_market<'a>(
+    env: &'a Env,
+    client: &AssetUpContractClient,
+    admin: &Address,
+    tokenizer: &Address,
+    buyer: &Address,
+) -> TokenClient<'a> {
+    client.tokenize_asset(
+        &1u64,
+        &String::from_str(env, "TST"),
+        &1000000i128,
+        &2u32,
+        &100i128,
+        tokenizer,
+        &String::from_str(env, "Test Token"),
+        &String::from_str(env, "A test tokenized asset"),
+        &AssetType::Physical,
+    );
+
+    let (payment, payment_admin) = create_payment_token(env, admin);
+    payment_admin.mint(buyer, &1000000i128);
+    payment_admin.mint(tokenizer, &1000000i128);
+    client.open_market(&1u64, &payment.address, tokenizer);
+    payment
+}
+
+#[test]
+fn test_open_market() {
+    let env = create_env();
+    let (admin, seller, buyer, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment = setup_market(&env, &client, &admin, &seller, &buyer);
+
+    let market = client.get_market(&1u64).unwrap();
+    assert_eq!(market.payment_token, payment.address);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_open_market_not_tokenizer() {
+    let env = create_env();
+    let (admin, seller, buyer, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment = setup_market(&env, &client, &admin, &seller, &buyer);
+
+    client.open_market(&1u64, &payment.address, &buyer);
+}
+
+#[test]
+fn test_sell_order_full_fill() {
+    let env = create_env();
+    let (admin, seller, buyer, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment = setup_market(&env, &client, &admin, &seller, &buyer);
+
+    // Sell 1,000 whole tokens at 100 per token
+    let order_id = client.place_order(&1u64, &seller, &OrderSide::Sell, &100i128, &100000i128);
+    assert_eq!(client.get_open_orders(&1u64).len(), 1);
+
+    let cost = client.fill_order(&order_id, &buyer, &100000i128);
+    assert_eq!(cost, 100000);
+
+    assert_eq!(client.get_token_balance(&1u64, &buyer), 100000);
+    assert_eq!(client.get_token_balance(&1u64, &seller), 900000);
+    assert_eq!(payment.balance(&buyer), 900000);
+    assert_eq!(payment.balance(&seller), 1100000);
+
+    let order = client.get_order(&order_id);
+    assert_eq!(order.status, OrderStatus::Filled);
+    assert_eq!(order.filled, 100000);
+    assert_eq!(client.get_open_orders(&1u64).len(), 0);
+}
+
+#[test]
+fn test_partial_fills_update_average_price() {
+    let env = create_env();
+    let (admin, seller, buyer, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup_market(&env, &client, &admin, &seller, &buyer);
+
+    let first = client.place_order(&1u64, &seller, &OrderSide::Sell, &100i128, &100000i128);
+    let second = client.place_order(&1u64, &seller, &OrderSide::Sell, &200i128, &100000i128);
+
+    client.fill_order(&first, &buyer, &40000i128);
+    client.fill_order(&second, &buyer, &40000i128);
+
+    let order = client.get_order(&first);
+    assert_eq!(order.status, OrderStatus::Open);
+    assert_eq!(order.filled, 40000);
+    assert_eq!(client.get_open_orders(&1u64).len(), 2);
+
+    assert_eq!(client.get_token_balance(&1u64, &buyer), 80000);
+    // (40,000 * 100 + 40,000 * 200) / 80,000
+    let record = client.get_ownership_record(&1u64, &buyer);
+    assert_eq!(record.average_purchase_price, 150);
+}
+
+#[test]
+fn test_buy_order_escrow_fill_and_cancel() {
+    let env = create_env();
+    let (admin, seller, buyer, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment = setup_market(&env, &client, &admin, &seller, &buyer);
+
+    // Bid for 500 whole tokens at 150 -> 75,000 escrowed
+    let order_id = client.place_order(&1u64, &buyer, &OrderSide::Buy, &150i128, &50000i128);
+    assert_eq!(payment.balance(&buyer), 925000);
+    assert_eq!(payment.balance(&client.address), 75000);
+
+    let cost = client.fill_order(&order_id, &seller, &20000i128);
+    assert_eq!(cost, 30000);
+    assert_eq!(payment.balance(&seller), 1030000);
+    assert_eq!(client.get_token_balance(&1u64, &buyer), 20000);
+
+    client.cancel_order(&order_id, &buyer);
+    assert_eq!(payment.balance(&buyer), 970000);
+    assert_eq!(payment.balance(&client.address), 0);
+    assert_eq!(client.get_order(&order_id).status, OrderStatus::Cancelled);
+    assert_eq!(client.get_open_orders(&1u64).len(), 0);
+}
+
+#[test]
+fn test_sell_order_locks_offered_tokens() {
+    let env = create_env();
+    let (admin, seller, buyer, other) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup_market(&env, &client, &admin, &seller, &buyer);
+
+    let order_id = client.place_order(&1u64, &seller, &OrderSide::Sell, &100i128, &100000i128);
+    assert_eq!(client.get_locked_amount(&1u64, &seller), 100000);
+    assert_eq!(client.get_spendable_balance(&1u64, &seller), 900000);
+
+    // The offered tokens cannot be moved out from under the order
+    assert_eq!(
+        client.try_transfer_tokens(&1u64, &seller, &other, &950000i128),
+        Err(Ok(Error::InsufficientLockedTokens))
+    );
+    assert_eq!(
+        client.try_place_order(&1u64, &seller, &OrderSide::Sell, &100i128, &950000i128),
+        Err(Ok(Error::InsufficientBalance))
+    );
+
+    client.fill_order(&order_id, &buyer, &40000i128);
+    assert_eq!(client.get_locked_amount(&1u64, &seller), 60000);
+
+    client.cancel_order(&order_id, &seller);
+    assert_eq!(client.get_locked_amount(&1u64, &seller), 0);
+    assert_eq!(client.get_spendable_balance(&1u64, &seller), 960000);
+}
+
+#[test]
+fn test_reopened_market_settles_in_order_token() {
+    let env = create_env();
+    let (admin, seller, buyer, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment = setup_market(&env, &client, &admin, &seller, &buyer);
+
+    let order_id = client.place_order(&1u64, &buyer, &OrderSide::Buy, &150i128, &50000i128);
+    assert_eq!(payment.balance(&client.address), 75000);
+
+    // Switching the market's payment token leaves existing orders in theirs
+    let (other_payment, _) = create_payment_token(&env, &admin);
+    client.open_market(&1u64, &other_payment.address, &seller);
+    assert_eq!(client.get_order(&order_id).payment_token, payment.address);
+
+    client.fill_order(&order_id, &seller, &20000i128);
+    assert_eq!(payment.balance(&seller), 1030000);
+    assert_eq!(other_payment.balance(&seller), 0);
+
+    client.cancel_order(&order_id, &buyer);
+    assert_eq!(payment.balance(&buyer), 970000);
+    assert_eq!(payment.balance(&client.address), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_fill_honours_whitelist() {
+    let env = create_env();
+    let (admin, seller, buyer, other) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup_market(&env, &client, &admin, &seller, &buyer);
+
+    client.add_to_whitelist(&1u64, &other);
+    let order_id = client.place_order(&1u64, &seller, &OrderSide::Sell, &100i128, &100000i128);
+
+    client.fill_order(&order_id, &buyer, &100000i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_fill_honours_seller_lock() {
+    let env = create_env();
+    let (admin, seller, buyer, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup_market(&env, &client, &admin, &seller, &buyer);
+
+    let order_id = client.place_order(&1u64, &seller, &OrderSide::Sell, &100i128, &100000i128);
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    client.lock_tokens(&1u64, &seller, &1000u64, &seller);
+
+    client.fill_order(&order_id, &buyer, &100000i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #14)")]
+fn test_fill_more_than_remaining() {
+    let env = create_env();
+    let (admin, seller, buyer, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup_market(&env, &client, &admin, &seller, &buyer);
+
+    let order_id = client.place_order(&1u64, &seller, &OrderSide::Sell, &100i128, &100000i128);
+    client.fill_order(&order_id, &buyer, &100001i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")]
+fn test_place_order_without_market() {
+    let env = create_env();
+    let (admin, seller, buyer, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup_market(&env, &client, &admin, &seller, &buyer);
+
+    client.place_order(&2u64, &seller, &OrderSide::Sell, &100i128, &100000i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_cancel_order_not_maker() {
+    let env = create_env();
+    let (admin, seller, buyer, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup_market(&env, &client, &admin, &seller, &buyer);
+
+    let order_id = client.place_order(&1u64, &seller, &OrderSide::Sell, &100i128, &100000i128);
+    client.cancel_order(&order_id, &buyer);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #48)")]
+fn test_fill_cancelled_order() {
+    let env = create_env();
+    let (admin, seller, buyer, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup_market(&env, &client, &admin, &seller, &buyer);
+
+    let order_id = client.place_order(&1u64, &seller, &OrderSide::Sell, &100i128, &100000i128);
+    client.cancel_order(&order_id, &seller);
+    client.fill_order(&order_id, &buyer, &100000i128);
+}
+
+#[test]
+fn test_split_rescales_open_orders() {
+    let env = create_env();
+    let (admin, seller, buyer, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment = setup_market(&env, &client, &admin, &seller, &buyer);
+
+    // Sell 1,000 whole tokens at 100 per token, then split 1:4
+    let order_id = client.place_order(&1u64, &seller, &OrderSide::Sell, &100i128, &100000i128);
+    client.redenominate_tokens(&1u64, &1u32, &4u32, &seller);
+
+    let order = client.get_order(&order_id);
+    assert_eq!(order.amount, 400000);
+    assert_eq!(order.price, 25);
+
+    // The same payment buys the same economic stake
+    let cost = client.fill_order(&order_id, &buyer, &400000i128);
+    assert_eq!(cost, 100000);
+    assert_eq!(client.get_token_balance(&1u64, &buyer), 400000);
+    assert_eq!(client.get_token_balance(&1u64, &seller), 3600000);
+    assert_eq!(payment.balance(&seller), 1100000);
+}
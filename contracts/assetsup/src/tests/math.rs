@@ -0,0 +1,204 @@
+use crate::error::Error;
+use crate::math::{self, Rounding};
+use crate::tests::helpers::*;
+use crate::types::AssetType;
+use soroban_sdk::String;
+
+/// Deterministic xorshift generator so property runs are reproducible
+struct XorShift(u64);
+
+impl XorShift {
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Non-negative i128 spread across the full bit range
+    fn next_amount(&mut self) -> i128 {
+        let bits = (self.next_u64() % 127) as u32;
+        let raw = ((self.next_u64() as u128) << 64) | self.next_u64() as u128;
+        (raw >> (127 - bits)) as i128
+    }
+}
+
+const EXTREMES: [i128; 8] = [
+    0,
+    1,
+    2,
+    1_000_000_000_000_000_000,
+    u64::MAX as i128,
+    i128::MAX / 2,
+    i128::MAX - 1,
+    i128::MAX,
+];
+
+#[test]
+fn test_mul_div_matches_native_when_product_fits() {
+    let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+    for _ in 0..2000 {
+        let a = (rng.next_u64() >> 1) as i128;
+        let b = (rng.next_u64() >> 1) as i128;
+        let d = (rng.next_u64() >> 1) as i128 + 1;
+        let product = a * b;
+
+        assert_eq!(math::mul_div(a, b, d, Rounding::Down), Ok(product / d));
+        let up = product / d + i128::from(product % d != 0);
+        assert_eq!(math::mul_div(a, b, d, Rounding::Up), Ok(up));
+        let nearest = product / d + i128::from(2 * (product % d) >= d);
+        assert_eq!(math::mul_div(a, b, d, Rounding::Nearest), Ok(nearest));
+    }
+}
+
+#[test]
+fn test_mul_div_rounding_properties_at_full_width() {
+    let mut rng = XorShift(0xdead_beef_cafe_f00d);
+    for _ in 0..2000 {
+        let a = rng.next_amount();
+        let b = rng.next_amount();
+        let d = rng.next_amount().max(1);
+
+        let down = math::mul_div(a, b, d, Rounding::Down);
+        let up = math::mul_div(a, b, d, Rounding::Up);
+        let nearest = math::mul_div(a, b, d, Rounding::Nearest);
+
+        // Operand order never changes the result
+        assert_eq!(down, math::mul_div(b, a, d, Rounding::Down));
+
+        match (down, up, nearest) {
+            (Ok(down), Ok(up), Ok(nearest)) => {
+                assert!(up - down <= 1);
+                assert!(down <= nearest && nearest <= up);
+            }
+            // Rounding up can only overflow when rounding down lands on i128::MAX
+            (Ok(down), Err(Error::MathOverflow), _) => assert_eq!(down, i128::MAX),
+            (Err(e), Err(_), Err(_)) => assert_eq!(e, Error::MathOverflow),
+            other => panic!("inconsistent rounding: {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_mul_div_identities_at_extremes() {
+    for &a in EXTREMES.iter() {
+        for &d in EXTREMES.iter().filter(|d| **d > 0) {
+            // a * d / d == a, even when a * d overflows i128
+            assert_eq!(math::mul_div(a, d, d, Rounding::Down), Ok(a));
+            assert_eq!(math::mul_div(d, a, d, Rounding::Up), Ok(a));
+            // A share of the whole never exceeds the whole
+            if a <= d {
+                assert!(math::mul_div(a, i128::MAX, d, Rounding::Down).is_ok());
+                assert!(math::bps_of(a, d).unwrap() <= 10_000);
+            }
+        }
+    }
+
+    assert_eq!(
+        math::mul_div(i128::MAX, i128::MAX, 1, Rounding::Down),
+        Err(Error::MathOverflow)
+    );
+    assert_eq!(
+        math::mul_div(1, 1, 0, Rounding::Down),
+        Err(Error::MathOverflow)
+    );
+    assert_eq!(math::mul_div(-7, 1, 2, Rounding::Down), Ok(-3));
+    assert_eq!(math::mul_div(-7, 1, 2, Rounding::Up), Ok(-4));
+}
+
+#[test]
+fn test_checked_add_sub_and_pow10() {
+    assert_eq!(math::add(i128::MAX, 1), Err(Error::MathOverflow));
+    assert_eq!(math::sub(5, 6), Err(Error::MathUnderflow));
+    assert_eq!(math::sub(i128::MIN, 1), Err(Error::MathUnderflow));
+    assert_eq!(math::sub(6, 6), Ok(0));
+    assert_eq!(math::mul(i128::MAX, 2), Err(Error::MathOverflow));
+    assert_eq!(math::pow10(18), Ok(1_000_000_000_000_000_000));
+    assert_eq!(math::pow10(38), Ok(10i128.pow(38)));
+    assert_eq!(math::pow10(39), Err(Error::MathOverflow));
+    assert_eq!(math::apply_bps(1001, 2500, Rounding::Nearest), Ok(250));
+    assert_eq!(math::apply_bps(1001, 2500, Rounding::Up), Ok(251));
+    assert_eq!(math::bps_of(1, 0), Ok(0));
+}
+
+#[test]
+fn test_dividends_at_extreme_supply() {
+    let env = create_env();
+    let (admin, tokenizer, holder, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+
+    // 18 decimals with a supply whose product with the payout overflows i128
+    let supply = 4 * 10i128.pow(37);
+    client.tokenize_asset(
+        &1u64,
+        &String::from_str(&env, "BIG"),
+        &supply,
+        &18u32,
+        &1i128,
+        &tokenizer,
+        &String::from_str(&env, "Big Token"),
+        &String::from_str(&env, "Extreme supply"),
+        &AssetType::Digital,
+    );
+    client.transfer_tokens(&1u64, &tokenizer, &holder, &(supply / 4));
+    assert_eq!(client.get_ownership_percentage(&1u64, &holder), 2500);
+
+    client.enable_revenue_sharing(&1u64);
+    let payout = i128::MAX / 2;
+    client.distribute_dividends(&1u64, &payout);
+
+    let holder_share = client.get_unclaimed_dividends(&1u64, &holder);
+    let tokenizer_share = client.get_unclaimed_dividends(&1u64, &tokenizer);
+    assert_eq!(holder_share, payout / 4);
+    assert!(holder_share + tokenizer_share <= payout);
+    assert!(payout - (holder_share + tokenizer_share) <= 2);
+}
+
+#[test]
+fn test_mint_overflow_is_rejected() {
+    let env = create_env();
+    let (admin, tokenizer, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+
+    client.tokenize_asset(
+        &1u64,
+        &String::from_str(&env, "BIG"),
+        &(i128::MAX - 10),
+        &18u32,
+        &1i128,
+        &tokenizer,
+        &String::from_str(&env, "Big Token"),
+        &String::from_str(&env, "Extreme supply"),
+        &AssetType::Digital,
+    );
+
+    assert_eq!(
+        client.try_mint_tokens(&1u64, &11i128, &tokenizer),
+        Err(Ok(Error::MathOverflow))
+    );
+    client.mint_tokens(&1u64, &10i128, &tokenizer);
+    assert_eq!(client.get_tokenized_asset(&1u64).total_supply, i128::MAX);
+}
+
+#[test]
+fn test_vote_threshold_at_extreme_supply() {
+    let env = create_env();
+    let (admin, tokenizer, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+
+    client.tokenize_asset(
+        &1u64,
+        &String::from_str(&env, "BIG"),
+        &i128::MAX,
+        &18u32,
+        &1i128,
+        &tokenizer,
+        &String::from_str(&env, "Big Token"),
+        &String::from_str(&env, "Extreme supply"),
+        &AssetType::Digital,
+    );
+
+    client.cast_vote(&1u64, &1u64, &tokenizer);
+    assert_eq!(client.get_vote_tally(&1u64, &1u64), i128::MAX);
+    assert!(client.proposal_passed(&1u64, &1u64));
+}
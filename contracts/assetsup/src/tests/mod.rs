@@ -7,15 +7,27 @@ mod asset;
 mod initialization;
 
 // Tokenization and ownership tests
+mod attestation;
+mod compliance;
 mod detokenization;
 mod dividends;
+mod enforcement;
+mod identity;
+mod marketplace;
+mod math;
+mod offering;
 mod tokenization;
 mod transfer_restrictions;
+mod valuation;
+mod vesting;
 mod voting;
 
 // Insurance tests
 mod insurance;
 
+// Leasing tests
+mod lease;
+
 // Integration tests
 mod integration_full;
 
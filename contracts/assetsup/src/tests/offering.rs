@@ -0,0 +1,329 @@
+use crate::error::Error;
+use crate::offering::{OfferingStatus, OfferingTerms};
+use crate::tests::helpers::*;
+use crate::types::AssetType;
+use crate::AssetUpContractClient;
+use soroban_sdk::testutils::Ledger;
+use soroban_sdk::token::TokenClient;
+use soroban_sdk::{Address, Env, String};
+
+/// Tokenize 10,000 whole tokens (2 decimals) and fund two investors
+fn setup<'a>(
+    env: &'a Env,
+    client: &AssetUpContractClient,
+    admin: &Address,
+    issuer: &Address,
+    investors: [&Address; 2],
+) -> TokenClient<'a> {
+    client.tokenize_asset(
+        &1u64,
+        &String::from_str(env, "TST"),
+        &1000000i128,
+        &2u32,
+        &100i128,
+        issuer,
+        &String::from_str(env, "Test Token"),
+        &String::from_str(env, "A test tokenized asset"),
+        &AssetType::Physical,
+    );
+
+    let (payment, payment_admin) = create_payment_token(env, admin);
+    for investor in investors {
+        payment_admin.mint(investor, &1000000i128);
+    }
+    payment
+}
+
+/// 100 per whole token, raise between 200,000 and 500,000, 300,000 per investor
+fn terms(payment: &TokenClient) -> OfferingTerms {
+    OfferingTerms {
+        payment_token: payment.address.clone(),
+        price_per_token: 100,
+        soft_cap: 200000,
+        hard_cap: 500000,
+        max_per_investor: 300000,
+        start: 100,
+        end: 1000,
+    }
+}
+
+#[test]
+fn test_create_offering_escrows_tokens() {
+    let env = create_env();
+    let (admin, issuer, investor1, investor2) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment = setup(&env, &client, &admin, &issuer, [&investor1, &investor2]);
+
+    client.create_offering(&1u64, &issuer, &terms(&payment));
+
+    let offering = client.get_offering(&1u64);
+    assert_eq!(offering.status, OfferingStatus::Active);
+    assert_eq!(offering.tokens_escrowed, 500000);
+    assert_eq!(client.get_token_balance(&1u64, &issuer), 500000);
+    assert_eq!(client.get_token_balance(&1u64, &client.address), 500000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_create_offering_not_tokenizer() {
+    let env = create_env();
+    let (admin, issuer, investor1, investor2) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment = setup(&env, &client, &admin, &issuer, [&investor1, &investor2]);
+
+    client.create_offering(&1u64, &investor1, &terms(&payment));
+}
+
+#[test]
+fn test_successful_offering_allocations_are_claimed() {
+    let env = create_env();
+    let (admin, issuer, investor1, investor2) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment = setup(&env, &client, &admin, &issuer, [&investor1, &investor2]);
+    client.create_offering(&1u64, &issuer, &terms(&payment));
+
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    client.subscribe(&1u64, &investor1, &150000i128);
+    client.subscribe(&1u64, &investor2, &100000i128);
+    client.subscribe(&1u64, &investor1, &50000i128);
+    assert_eq!(client.get_subscription(&1u64, &investor1), 200000);
+    assert_eq!(payment.balance(&client.address), 300000);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    let status = client.finalize_offering(&1u64);
+    assert_eq!(status, OfferingStatus::Succeeded);
+
+    // Unsold 200,000 returned to the issuer; allocations wait in escrow
+    assert_eq!(client.get_token_balance(&1u64, &issuer), 700000);
+    assert_eq!(client.get_token_balance(&1u64, &client.address), 300000);
+    assert_eq!(payment.balance(&issuer), 300000);
+
+    assert_eq!(client.claim_allocation(&1u64, &investor1), 200000);
+    assert_eq!(client.claim_allocation(&1u64, &investor2), 100000);
+    assert_eq!(client.get_token_balance(&1u64, &investor1), 200000);
+    assert_eq!(client.get_token_balance(&1u64, &investor2), 100000);
+    assert_eq!(client.get_token_balance(&1u64, &client.address), 0);
+    assert_eq!(client.get_offering(&1u64).pending_claims, 0);
+    assert_eq!(
+        client.try_claim_allocation(&1u64, &investor1),
+        Err(Ok(Error::InvalidPayment))
+    );
+
+    assert_eq!(payment.balance(&client.address), 0);
+    assert_eq!(
+        client
+            .get_ownership_record(&1u64, &investor1)
+            .average_purchase_price,
+        100
+    );
+}
+
+#[test]
+fn test_failed_offering_refunds_investors() {
+    let env = create_env();
+    let (admin, issuer, investor1, investor2) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment = setup(&env, &client, &admin, &issuer, [&investor1, &investor2]);
+    client.create_offering(&1u64, &issuer, &terms(&payment));
+
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    client.subscribe(&1u64, &investor1, &100000i128);
+    client.subscribe(&1u64, &investor2, &50000i128);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    let status = client.finalize_offering(&1u64);
+    assert_eq!(status, OfferingStatus::Failed);
+    assert_eq!(client.get_token_balance(&1u64, &issuer), 1000000);
+    assert_eq!(
+        client.try_claim_allocation(&1u64, &investor1),
+        Err(Ok(Error::OfferingNotActive))
+    );
+
+    assert_eq!(client.claim_refund(&1u64, &investor1), 100000);
+    assert_eq!(client.claim_refund(&1u64, &investor2), 50000);
+    assert_eq!(payment.balance(&investor1), 1000000);
+    assert_eq!(payment.balance(&investor2), 1000000);
+    assert_eq!(payment.balance(&issuer), 0);
+    assert_eq!(client.get_token_balance(&1u64, &investor1), 0);
+    assert_eq!(client.get_token_balance(&1u64, &issuer), 1000000);
+}
+
+#[test]
+fn test_finalize_early_when_hard_cap_reached() {
+    let env = create_env();
+    let (admin, issuer, investor1, investor2) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment = setup(&env, &client, &admin, &issuer, [&investor1, &investor2]);
+    client.create_offering(&1u64, &issuer, &terms(&payment));
+
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    client.subscribe(&1u64, &investor1, &300000i128);
+    client.subscribe(&1u64, &investor2, &200000i128);
+
+    assert_eq!(client.finalize_offering(&1u64), OfferingStatus::Succeeded);
+    assert_eq!(client.get_token_balance(&1u64, &issuer), 500000);
+}
+
+#[test]
+fn test_ineligible_investor_does_not_block_other_claims() {
+    let env = create_env();
+    let (admin, issuer, investor1, investor2) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment = setup(&env, &client, &admin, &issuer, [&investor1, &investor2]);
+    client.create_offering(&1u64, &issuer, &terms(&payment));
+
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    client.subscribe(&1u64, &investor1, &150000i128);
+    client.subscribe(&1u64, &investor2, &100000i128);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    client.finalize_offering(&1u64);
+
+    // investor1 can no longer receive tokens, investor2 still claims
+    client.set_denylisted(&1u64, &investor1, &true, &issuer);
+    assert!(client.try_claim_allocation(&1u64, &investor1).is_err());
+    assert_eq!(client.claim_allocation(&1u64, &investor2), 100000);
+    assert_eq!(client.get_offering(&1u64).pending_claims, 1);
+
+    // A new offering waits for the outstanding claim
+    assert_eq!(
+        client.try_create_offering(&1u64, &issuer, &terms(&payment)),
+        Err(Ok(Error::OfferingNotActive))
+    );
+}
+
+#[test]
+fn test_settle_pushes_allocations_in_pages() {
+    let env = create_env();
+    let (admin, issuer, investor1, investor2) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment = setup(&env, &client, &admin, &issuer, [&investor1, &investor2]);
+    client.create_offering(&1u64, &issuer, &terms(&payment));
+
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    client.subscribe(&1u64, &investor1, &150000i128);
+    client.subscribe(&1u64, &investor2, &100000i128);
+    client.subscribe(&1u64, &investor1, &50000i128);
+    assert_eq!(client.get_subscriber_count(&1u64), 2);
+    assert_eq!(
+        client.try_settle_offering(&1u64, &0u32, &10u32),
+        Err(Ok(Error::OfferingNotActive))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    client.finalize_offering(&1u64);
+
+    // investor1 cannot receive tokens, so the page skips past them
+    client.set_denylisted(&1u64, &investor1, &true, &issuer);
+    assert!(client.try_settle_offering(&1u64, &0u32, &1u32).is_err());
+    assert_eq!(client.settle_offering(&1u64, &1u32, &10u32), 1);
+    assert_eq!(client.get_token_balance(&1u64, &investor2), 100000);
+
+    // Once eligible again, the first page settles and settled entries are skipped
+    client.set_denylisted(&1u64, &investor1, &false, &issuer);
+    assert_eq!(client.settle_offering(&1u64, &0u32, &10u32), 1);
+    assert_eq!(client.get_token_balance(&1u64, &investor1), 200000);
+    assert_eq!(client.get_offering(&1u64).pending_claims, 0);
+    assert_eq!(client.settle_offering(&1u64, &0u32, &10u32), 0);
+
+    // The next offering starts a fresh subscriber index
+    env.ledger().with_mut(|li| li.timestamp = 1100);
+    let mut next = terms(&payment);
+    next.start = 1100;
+    next.end = 2000;
+    client.create_offering(&1u64, &issuer, &next);
+    assert_eq!(client.get_subscriber_count(&1u64), 0);
+    client.subscribe(&1u64, &investor2, &1000i128);
+    assert_eq!(client.get_subscriber_count(&1u64), 1);
+}
+
+#[test]
+fn test_settle_pushes_refunds() {
+    let env = create_env();
+    let (admin, issuer, investor1, investor2) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment = setup(&env, &client, &admin, &issuer, [&investor1, &investor2]);
+    client.create_offering(&1u64, &issuer, &terms(&payment));
+
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    client.subscribe(&1u64, &investor1, &100000i128);
+    client.subscribe(&1u64, &investor2, &50000i128);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    assert_eq!(client.finalize_offering(&1u64), OfferingStatus::Failed);
+    assert_eq!(client.claim_refund(&1u64, &investor2), 50000);
+
+    assert_eq!(client.settle_offering(&1u64, &0u32, &10u32), 1);
+    assert_eq!(payment.balance(&investor1), 1000000);
+    assert_eq!(payment.balance(&investor2), 1000000);
+    assert_eq!(payment.balance(&client.address), 0);
+    assert_eq!(client.get_offering(&1u64).pending_claims, 0);
+}
+
+#[test]
+fn test_escrow_is_not_a_token_holder() {
+    let env = create_env();
+    let (admin, issuer, investor1, investor2) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment = setup(&env, &client, &admin, &issuer, [&investor1, &investor2]);
+    client.create_offering(&1u64, &issuer, &terms(&payment));
+
+    let holders = client.get_token_holders(&1u64);
+    assert_eq!(holders.len(), 1);
+    assert!(!holders.contains(&client.address));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #49)")]
+fn test_finalize_before_end() {
+    let env = create_env();
+    let (admin, issuer, investor1, investor2) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment = setup(&env, &client, &admin, &issuer, [&investor1, &investor2]);
+    client.create_offering(&1u64, &issuer, &terms(&payment));
+
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    client.subscribe(&1u64, &investor1, &250000i128);
+
+    client.finalize_offering(&1u64);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #49)")]
+fn test_subscribe_before_start() {
+    let env = create_env();
+    let (admin, issuer, investor1, investor2) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment = setup(&env, &client, &admin, &issuer, [&investor1, &investor2]);
+    client.create_offering(&1u64, &issuer, &terms(&payment));
+
+    client.subscribe(&1u64, &investor1, &100000i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #9)")]
+fn test_subscribe_over_investor_cap() {
+    let env = create_env();
+    let (admin, issuer, investor1, investor2) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment = setup(&env, &client, &admin, &issuer, [&investor1, &investor2]);
+    client.create_offering(&1u64, &issuer, &terms(&payment));
+
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    client.subscribe(&1u64, &investor1, &200000i128);
+    client.subscribe(&1u64, &investor1, &100001i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #19)")]
+fn test_subscribe_requires_accreditation() {
+    let env = create_env();
+    let (admin, issuer, investor1, investor2) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    let payment = setup(&env, &client, &admin, &issuer, [&investor1, &investor2]);
+    client.create_offering(&1u64, &issuer, &terms(&payment));
+    client.set_transfer_restriction(&1u64, &true);
+
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    client.subscribe(&1u64, &investor1, &100000i128);
+}
@@ -1,6 +1,11 @@
+use crate::attestation::AttestorRole;
+use crate::error::Error;
 use crate::tests::helpers::*;
-use crate::types::AssetType;
-use soroban_sdk::String;
+use crate::types::{AssetType, CorporateActionKind, GovernedMinting, MintingPolicy, TokenDataKey};
+use crate::valuation::ValuationMethod;
+use crate::AssetUpContractClient;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{symbol_short, Address, Env, Map, String, Symbol, Val, Vec};
 
 #[test]
 fn test_tokenize_asset_success() {
@@ -347,6 +352,275 @@ fn test_lock_tokens_unauthorized() {
     client.lock_tokens(&1u64, &user1, &future_time, &user2);
 }
 
+#[test]
+fn test_partial_locks_limit_spendable_balance() {
+    let env = create_env();
+    let (admin, user1, user2, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+
+    env.mock_all_auths();
+
+    client.tokenize_asset(
+        &1u64,
+        &String::from_str(&env, "TST"),
+        &1000000i128,
+        &6u32,
+        &100i128,
+        &user1,
+        &String::from_str(&env, "Test Token"),
+        &String::from_str(&env, "A test tokenized asset"),
+        &AssetType::Physical,
+    );
+
+    client.lock_token_amount(
+        &1u64,
+        &user1,
+        &300000i128,
+        &1000u64,
+        &symbol_short!("escrow"),
+        &user1,
+    );
+    client.lock_token_amount(
+        &1u64,
+        &user1,
+        &200000i128,
+        &2000u64,
+        &symbol_short!("lockup"),
+        &user1,
+    );
+
+    assert_eq!(client.get_token_locks(&1u64, &user1).len(), 2);
+    assert_eq!(client.get_locked_amount(&1u64, &user1), 500000);
+    assert_eq!(client.get_spendable_balance(&1u64, &user1), 500000);
+    assert_eq!(client.get_tokenized_asset(&1u64).locked_tokens, 500000);
+
+    // Unlocked tokens remain transferable
+    client.transfer_tokens(&1u64, &user1, &user2, &500000i128);
+    assert_eq!(client.get_spendable_balance(&1u64, &user1), 0);
+
+    // The first lock expires and is cleared on the next transfer
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    assert_eq!(client.get_spendable_balance(&1u64, &user1), 300000);
+    client.transfer_tokens(&1u64, &user1, &user2, &300000i128);
+    assert_eq!(client.get_token_locks(&1u64, &user1).len(), 1);
+    assert_eq!(client.get_tokenized_asset(&1u64).locked_tokens, 200000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_transfer_exceeds_spendable_balance() {
+    let env = create_env();
+    let (admin, user1, user2, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+
+    env.mock_all_auths();
+
+    client.tokenize_asset(
+        &1u64,
+        &String::from_str(&env, "TST"),
+        &1000000i128,
+        &6u32,
+        &100i128,
+        &user1,
+        &String::from_str(&env, "Test Token"),
+        &String::from_str(&env, "A test tokenized asset"),
+        &AssetType::Physical,
+    );
+
+    client.lock_token_amount(
+        &1u64,
+        &user1,
+        &300000i128,
+        &1000u64,
+        &symbol_short!("escrow"),
+        &user1,
+    );
+
+    client.transfer_tokens(&1u64, &user1, &user2, &700001i128);
+}
+
+#[test]
+fn test_release_partial_locks() {
+    let env = create_env();
+    let (admin, user1, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+
+    env.mock_all_auths();
+
+    client.tokenize_asset(
+        &1u64,
+        &String::from_str(&env, "TST"),
+        &1000000i128,
+        &6u32,
+        &100i128,
+        &user1,
+        &String::from_str(&env, "Test Token"),
+        &String::from_str(&env, "A test tokenized asset"),
+        &AssetType::Physical,
+    );
+
+    let first = client.lock_token_amount(
+        &1u64,
+        &user1,
+        &300000i128,
+        &1000u64,
+        &symbol_short!("escrow"),
+        &user1,
+    );
+    client.lock_token_amount(
+        &1u64,
+        &user1,
+        &200000i128,
+        &2000u64,
+        &symbol_short!("lockup"),
+        &user1,
+    );
+
+    // Early release by the tokenizer
+    assert_eq!(
+        client.release_token_lock(&1u64, &user1, &first, &user1),
+        300000
+    );
+    assert_eq!(client.get_tokenized_asset(&1u64).locked_tokens, 200000);
+
+    // Expired locks can be cleared by anyone
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+    assert_eq!(client.release_expired_locks(&1u64, &user1), 200000);
+    assert_eq!(client.get_token_locks(&1u64, &user1).len(), 0);
+    assert_eq!(client.get_tokenized_asset(&1u64).locked_tokens, 0);
+}
+
+#[test]
+fn test_expired_locks_released_on_transfer() {
+    let env = create_env();
+    let (admin, user1, user2, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+
+    client.tokenize_asset(
+        &1u64,
+        &String::from_str(&env, "TST"),
+        &1000000i128,
+        &6u32,
+        &100i128,
+        &user1,
+        &String::from_str(&env, "Test Token"),
+        &String::from_str(&env, "A test tokenized asset"),
+        &AssetType::Physical,
+    );
+    client.lock_token_amount(
+        &1u64,
+        &user1,
+        &300000i128,
+        &1000u64,
+        &symbol_short!("escrow"),
+        &user1,
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    // Queries skip the expired lock without clearing it
+    assert_eq!(client.get_locked_amount(&1u64, &user1), 0);
+    assert_eq!(client.get_token_balance(&1u64, &user1), 1000000);
+    assert_eq!(client.get_spendable_balance(&1u64, &user1), 1000000);
+    assert_eq!(client.get_token_locks(&1u64, &user1).len(), 1);
+    assert_eq!(client.get_tokenized_asset(&1u64).locked_tokens, 300000);
+
+    client.transfer_tokens(&1u64, &user1, &user2, &1000i128);
+    assert_eq!(client.get_token_locks(&1u64, &user1).len(), 0);
+    assert_eq!(client.get_tokenized_asset(&1u64).locked_tokens, 0);
+}
+
+#[test]
+fn test_forced_transfer_shrinks_partial_locks() {
+    let env = create_env();
+    let (admin, user1, user2, user3) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+
+    client.tokenize_asset(
+        &1u64,
+        &String::from_str(&env, "TST"),
+        &1000000i128,
+        &6u32,
+        &100i128,
+        &user1,
+        &String::from_str(&env, "Test Token"),
+        &String::from_str(&env, "A test tokenized asset"),
+        &AssetType::Physical,
+    );
+    client.transfer_tokens(&1u64, &user1, &user2, &500000i128);
+    client.lock_token_amount(
+        &1u64,
+        &user2,
+        &100000i128,
+        &1000u64,
+        &symbol_short!("escrow"),
+        &user1,
+    );
+    client.lock_token_amount(
+        &1u64,
+        &user2,
+        &300000i128,
+        &2000u64,
+        &symbol_short!("lockup"),
+        &user1,
+    );
+
+    // The newest lock gives way first
+    client.forced_transfer(
+        &1u64,
+        &user2,
+        &user3,
+        &350000i128,
+        &generate_asset_id(&env, 7),
+        &user1,
+    );
+    let locks = client.get_token_locks(&1u64, &user2);
+    assert_eq!(locks.len(), 2);
+    assert_eq!(locks.get(0).unwrap().amount, 100000);
+    assert_eq!(locks.get(1).unwrap().amount, 50000);
+    assert_eq!(client.get_locked_amount(&1u64, &user2), 150000);
+    assert_eq!(client.get_tokenized_asset(&1u64).locked_tokens, 150000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_lock_amount_exceeds_unlocked_balance() {
+    let env = create_env();
+    let (admin, user1, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+
+    env.mock_all_auths();
+
+    client.tokenize_asset(
+        &1u64,
+        &String::from_str(&env, "TST"),
+        &1000000i128,
+        &6u32,
+        &100i128,
+        &user1,
+        &String::from_str(&env, "Test Token"),
+        &String::from_str(&env, "A test tokenized asset"),
+        &AssetType::Physical,
+    );
+
+    client.lock_token_amount(
+        &1u64,
+        &user1,
+        &600000i128,
+        &1000u64,
+        &symbol_short!("escrow"),
+        &user1,
+    );
+    client.lock_token_amount(
+        &1u64,
+        &user1,
+        &400001i128,
+        &1000u64,
+        &symbol_short!("escrow"),
+        &user1,
+    );
+}
+
 #[test]
 fn test_get_ownership_percentage() {
     let env = create_env();
@@ -431,7 +705,20 @@ fn test_update_valuation() {
         &AssetType::Physical,
     );
 
-    client.update_valuation(&1u64, &2000000i128);
+    let appraiser = create_attestor_key(7);
+    let appraiser_pk = attestor_public_key(&env, &appraiser);
+    client.register_attestor(&appraiser_pk, &AttestorRole::Appraiser);
+
+    let timestamp = env.ledger().timestamp();
+    let signature = sign_valuation(&env, &appraiser, 1, 2000000, timestamp);
+    client.update_valuation(
+        &1u64,
+        &2000000i128,
+        &ValuationMethod::MarketComparison,
+        &appraiser_pk,
+        &timestamp,
+        &signature,
+    );
 
     let asset = client.get_tokenized_asset(&1u64);
     assert_eq!(asset.valuation, 2000000);
@@ -458,6 +745,547 @@ fn test_update_valuation_invalid() {
         &AssetType::Physical,
     );
 
+    let appraiser = create_attestor_key(7);
+    let appraiser_pk = attestor_public_key(&env, &appraiser);
+    client.register_attestor(&appraiser_pk, &AttestorRole::Appraiser);
+
+    let timestamp = env.ledger().timestamp();
+    let signature = sign_valuation(&env, &appraiser, 1, 0, timestamp);
+
     // Should panic with InvalidValuation error
-    client.update_valuation(&1u64, &0i128);
+    client.update_valuation(
+        &1u64,
+        &0i128,
+        &ValuationMethod::MarketComparison,
+        &appraiser_pk,
+        &timestamp,
+        &signature,
+    );
+}
+
+#[test]
+fn test_emptied_holders_removed_from_list() {
+    let env = create_env();
+    let (admin, user1, user2, user3) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+
+    env.mock_all_auths();
+
+    client.tokenize_asset(
+        &1u64,
+        &String::from_str(&env, "TST"),
+        &1000000i128,
+        &6u32,
+        &100i128,
+        &user1,
+        &String::from_str(&env, "Test Token"),
+        &String::from_str(&env, "A test tokenized asset"),
+        &AssetType::Physical,
+    );
+
+    client.transfer_tokens(&1u64, &user1, &user2, &300000i128);
+    client.transfer_tokens(&1u64, &user1, &user3, &200000i128);
+    assert_eq!(client.get_tokenized_asset(&1u64).token_holders_count, 3);
+
+    // user2 exits completely
+    client.transfer_tokens(&1u64, &user2, &user3, &300000i128);
+    let holders = client.get_token_holders(&1u64);
+    assert_eq!(holders.len(), 2);
+    assert!(!holders.contains(&user2));
+    assert_eq!(client.get_tokenized_asset(&1u64).token_holders_count, 2);
+
+    // Burning the tokenizer's whole balance removes it too
+    client.burn_tokens(&1u64, &500000i128, &user1);
+    assert_eq!(client.get_token_holders(&1u64).len(), 1);
+    assert_eq!(client.get_tokenized_asset(&1u64).token_holders_count, 1);
+
+    // Minting brings the tokenizer back
+    client.mint_tokens(&1u64, &100i128, &user1);
+    assert_eq!(client.get_tokenized_asset(&1u64).token_holders_count, 2);
+}
+
+#[test]
+fn test_holder_with_unclaimed_dividends_stays_listed() {
+    let env = create_env();
+    let (admin, user1, user2, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+
+    client.tokenize_asset(
+        &1u64,
+        &String::from_str(&env, "TST"),
+        &1000000i128,
+        &6u32,
+        &100i128,
+        &user1,
+        &String::from_str(&env, "Test Token"),
+        &String::from_str(&env, "A test tokenized asset"),
+        &AssetType::Physical,
+    );
+    client.enable_revenue_sharing(&1u64);
+    client.transfer_tokens(&1u64, &user1, &user2, &500000i128);
+    client.distribute_dividends(&1u64, &1000i128);
+
+    // user2 sells out before claiming and keeps a cap-table entry
+    client.transfer_tokens(&1u64, &user2, &user1, &500000i128);
+    assert!(client.get_token_holders(&1u64).contains(&user2));
+    let entry = client.get_cap_table(&1u64, &1u32, &1u32).get(0).unwrap();
+    assert_eq!(entry.holder, user2);
+    assert_eq!(entry.balance, 0);
+    assert_eq!(entry.unclaimed_dividends, 500);
+
+    client.claim_dividends(&1u64, &user2);
+    assert!(!client.get_token_holders(&1u64).contains(&user2));
+    assert_eq!(client.get_tokenized_asset(&1u64).token_holders_count, 1);
+}
+
+#[test]
+fn test_cap_table_pagination() {
+    let env = create_env();
+    let (admin, user1, user2, user3) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+
+    env.mock_all_auths();
+
+    client.tokenize_asset(
+        &1u64,
+        &String::from_str(&env, "TST"),
+        &1000000i128,
+        &6u32,
+        &100i128,
+        &user1,
+        &String::from_str(&env, "Test Token"),
+        &String::from_str(&env, "A test tokenized asset"),
+        &AssetType::Physical,
+    );
+    client.transfer_tokens(&1u64, &user1, &user2, &300000i128);
+    client.transfer_tokens(&1u64, &user1, &user3, &200000i128);
+    client.enable_revenue_sharing(&1u64);
+    client.distribute_dividends(&1u64, &1000i128);
+
+    let first_page = client.get_cap_table(&1u64, &0u32, &2u32);
+    assert_eq!(first_page.len(), 2);
+    assert_eq!(first_page.get(0).unwrap().holder, user1);
+    assert_eq!(first_page.get(0).unwrap().ownership_bps, 5000);
+    assert_eq!(first_page.get(1).unwrap().balance, 300000);
+    assert_eq!(first_page.get(1).unwrap().unclaimed_dividends, 300);
+
+    let second_page = client.get_cap_table(&1u64, &2u32, &2u32);
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(second_page.get(0).unwrap().holder, user3);
+    assert_eq!(second_page.get(0).unwrap().ownership_bps, 2000);
+
+    assert_eq!(client.get_cap_table(&1u64, &5u32, &2u32).len(), 0);
+}
+
+#[test]
+fn test_mint_refreshes_all_ownership_percentages() {
+    let env = create_env();
+    let (admin, user1, user2, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+
+    env.mock_all_auths();
+
+    client.tokenize_asset(
+        &1u64,
+        &String::from_str(&env, "TST"),
+        &1000000i128,
+        &6u32,
+        &100i128,
+        &user1,
+        &String::from_str(&env, "Test Token"),
+        &String::from_str(&env, "A test tokenized asset"),
+        &AssetType::Physical,
+    );
+    client.transfer_tokens(&1u64, &user1, &user2, &500000i128);
+    assert_eq!(
+        client
+            .get_ownership_record(&1u64, &user2)
+            .ownership_percentage,
+        5000
+    );
+
+    // Doubling supply halves user2's share even though their record was not touched
+    client.mint_tokens(&1u64, &1000000i128, &user1);
+    assert_eq!(
+        client
+            .get_ownership_record(&1u64, &user2)
+            .ownership_percentage,
+        2500
+    );
+    assert_eq!(
+        client
+            .get_ownership_record(&1u64, &user1)
+            .ownership_percentage,
+        7500
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #13)")]
+fn test_tokenize_rejects_too_many_decimals() {
+    let env = create_env();
+    let (admin, user1, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+
+    client.tokenize_asset(
+        &1u64,
+        &String::from_str(&env, "TST"),
+        &1000000i128,
+        &19u32,
+        &100i128,
+        &user1,
+        &String::from_str(&env, "Test Token"),
+        &String::from_str(&env, "A test tokenized asset"),
+        &AssetType::Physical,
+    );
+}
+
+fn setup_minting(env: &Env, client: &AssetUpContractClient, tokenizer: &Address) {
+    client.tokenize_asset(
+        &1u64,
+        &String::from_str(env, "TST"),
+        &1000000i128,
+        &6u32,
+        &100i128,
+        tokenizer,
+        &String::from_str(env, "Test Token"),
+        &String::from_str(env, "A test tokenized asset"),
+        &AssetType::Physical,
+    );
+}
+
+#[test]
+fn test_fixed_supply_blocks_minting_permanently() {
+    let env = create_env();
+    let (admin, user1, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup_minting(&env, &client, &user1);
+
+    client.set_minting_policy(&1u64, &MintingPolicy::FixedSupply, &user1);
+    assert_eq!(client.get_minting_policy(&1u64), MintingPolicy::FixedSupply);
+    assert_eq!(
+        client.try_mint_tokens(&1u64, &1i128, &user1),
+        Err(Ok(Error::InvalidTokenSupply))
+    );
+    assert_eq!(
+        client.try_set_minting_policy(&1u64, &MintingPolicy::Capped(2000000), &user1),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    // Burning is still allowed
+    client.burn_tokens(&1u64, &1000i128, &user1);
+}
+
+#[test]
+fn test_capped_minting() {
+    let env = create_env();
+    let (admin, user1, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup_minting(&env, &client, &user1);
+
+    assert_eq!(
+        client.try_set_minting_policy(&1u64, &MintingPolicy::Capped(999999), &user1),
+        Err(Ok(Error::InvalidTokenSupply))
+    );
+    client.set_minting_policy(&1u64, &MintingPolicy::Capped(1500000), &user1);
+
+    client.mint_tokens(&1u64, &500000i128, &user1);
+    assert_eq!(
+        client.try_mint_tokens(&1u64, &1i128, &user1),
+        Err(Ok(Error::InvalidTokenSupply))
+    );
+
+    // A cap can be lowered but never raised
+    assert_eq!(
+        client.try_set_minting_policy(&1u64, &MintingPolicy::Capped(2000000), &user1),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_minting_policy(&1u64, &MintingPolicy::Unrestricted, &user1),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.burn_tokens(&1u64, &100000i128, &user1);
+    client.set_minting_policy(&1u64, &MintingPolicy::Capped(1400000), &user1);
+}
+
+#[test]
+fn test_governed_minting_requires_vote_above_threshold() {
+    let env = create_env();
+    let (admin, user1, user2, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup_minting(&env, &client, &user1);
+    client.transfer_tokens(&1u64, &user1, &user2, &600000i128);
+
+    let policy = MintingPolicy::GovernanceApproved(GovernedMinting {
+        max_supply: 2000000,
+        threshold: 10000,
+    });
+    client.set_minting_policy(&1u64, &policy, &user1);
+
+    // Small mints need no vote until they add up past the threshold
+    client.mint_tokens(&1u64, &6000i128, &user1);
+    client.mint_tokens(&1u64, &4000i128, &user1);
+    assert_eq!(
+        client.try_mint_tokens(&1u64, &1i128, &user1),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    // Only proposals bound to the amount before voting count
+    client.cast_vote(&1u64, &6u64, &user2);
+    assert_eq!(
+        client.try_propose_mint(&1u64, &6u64, &50000i128, &user1),
+        Err(Ok(Error::InvalidProposal))
+    );
+    assert_eq!(
+        client.try_mint_tokens_with_approval(&1u64, &50000i128, &6u64, &user1),
+        Err(Ok(Error::InvalidProposal))
+    );
+    client.propose_mint(&1u64, &7u64, &50000i128, &user1);
+    assert_eq!(
+        client.try_mint_tokens_with_approval(&1u64, &50000i128, &7u64, &user1),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    client.cast_vote(&1u64, &7u64, &user2);
+    assert_eq!(
+        client.try_mint_tokens_with_approval(&1u64, &60000i128, &7u64, &user1),
+        Err(Ok(Error::InvalidTokenSupply))
+    );
+    let result = client.mint_tokens_with_approval(&1u64, &50000i128, &7u64, &user1);
+    assert_eq!(result.total_supply, 1060000);
+
+    // The approval is spent and resets the running total
+    assert_eq!(
+        client.try_mint_tokens_with_approval(&1u64, &50000i128, &7u64, &user1),
+        Err(Ok(Error::InvalidProposal))
+    );
+    client.mint_tokens(&1u64, &10000i128, &user1);
+
+    // Approval does not lift the cap
+    client.propose_mint(&1u64, &8u64, &1000000i128, &user1);
+    client.cast_vote(&1u64, &8u64, &user2);
+    assert_eq!(
+        client.try_mint_tokens_with_approval(&1u64, &1000000i128, &8u64, &user1),
+        Err(Ok(Error::InvalidTokenSupply))
+    );
+}
+
+#[test]
+fn test_mint_votes_use_snapshot_and_exclude_tokenizer() {
+    let env = create_env();
+    let (admin, user1, user2, user3) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup_minting(&env, &client, &user1);
+    client.transfer_tokens(&1u64, &user1, &user2, &60000i128);
+    client.transfer_tokens(&1u64, &user1, &user3, &60000i128);
+    let policy = MintingPolicy::GovernanceApproved(GovernedMinting {
+        max_supply: 2000000,
+        threshold: 0,
+    });
+    client.set_minting_policy(&1u64, &policy, &user1);
+    client.propose_mint(&1u64, &7u64, &50000i128, &user1);
+
+    // The tokenizer holds most of the supply but cannot approve its own mint
+    assert_eq!(
+        client.try_cast_vote(&1u64, &7u64, &user1),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    // Passing needs a majority of the 120000 tokens outside the tokenizer
+    client.cast_vote(&1u64, &7u64, &user2);
+    assert!(!client.proposal_passed(&1u64, &7u64));
+
+    // Tokens moved after the snapshot carry no extra votes
+    client.transfer_tokens(&1u64, &user2, &user3, &60000i128);
+    let late_buyer = Address::generate(&env);
+    client.transfer_tokens(&1u64, &user1, &late_buyer, &500000i128);
+    assert_eq!(
+        client.try_cast_vote(&1u64, &7u64, &late_buyer),
+        Err(Ok(Error::HolderNotFound))
+    );
+    client.cast_vote(&1u64, &7u64, &user3);
+    assert_eq!(client.get_vote_tally(&1u64, &7u64), 120000);
+    assert_eq!(client.get_token_balance(&1u64, &user3), 120000);
+
+    client.mint_tokens_with_approval(&1u64, &50000i128, &7u64, &user1);
+}
+
+#[test]
+fn test_split_rescales_holders_lazily() {
+    let env = create_env();
+    let (admin, user1, user2, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup_minting(&env, &client, &user1);
+    client.transfer_tokens(&1u64, &user1, &user2, &250000i128);
+    client.lock_token_amount(
+        &1u64,
+        &user2,
+        &50000i128,
+        &1000u64,
+        &symbol_short!("lockup"),
+        &user1,
+    );
+
+    let action = client.redenominate_tokens(&1u64, &1u32, &10u32, &user1);
+    assert_eq!(action.kind, CorporateActionKind::Split);
+    assert_eq!(action.supply_before, 1000000);
+    assert_eq!(action.supply_after, 10000000);
+
+    let asset = client.get_tokenized_asset(&1u64);
+    assert_eq!(asset.total_supply, 10000000);
+    assert_eq!(asset.locked_tokens, 500000);
+    assert_eq!(asset.min_voting_threshold, 1000);
+
+    assert_eq!(client.get_token_balance(&1u64, &user1), 7500000);
+    assert_eq!(client.get_token_balance(&1u64, &user2), 2500000);
+    assert_eq!(client.get_locked_amount(&1u64, &user2), 500000);
+    assert_eq!(client.get_ownership_percentage(&1u64, &user2), 2500);
+
+    // The locked slice stays locked in the new denomination
+    assert!(client
+        .try_transfer_tokens(&1u64, &user2, &user1, &2000001i128)
+        .is_err());
+    client.transfer_tokens(&1u64, &user2, &user1, &2000000i128);
+    assert_eq!(client.get_token_balance(&1u64, &user1), 9500000);
+    assert_eq!(client.get_ownership_record(&1u64, &user2).scale_epoch, 1);
+
+    assert_eq!(client.get_corporate_actions(&1u64).len(), 1);
+}
+
+#[test]
+fn test_consolidation_rounds_down_and_rescales_votes() {
+    let env = create_env();
+    let (admin, user1, user2, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup_minting(&env, &client, &user1);
+    client.transfer_tokens(&1u64, &user1, &user2, &600005i128);
+
+    // user2 votes before the consolidation
+    client.cast_vote(&1u64, &1u64, &user2);
+    assert_eq!(client.get_vote_tally(&1u64, &1u64), 600005);
+
+    let action = client.redenominate_tokens(&1u64, &10u32, &1u32, &user1);
+    assert_eq!(action.kind, CorporateActionKind::Consolidation);
+
+    // Fractional tokens are rounded down per holder; the supply is scaled
+    // once, so the rounding dust stays at asset level
+    assert_eq!(client.get_token_balance(&1u64, &user2), 60000);
+    assert_eq!(client.get_token_balance(&1u64, &user1), 39999);
+    assert_eq!(action.supply_after, 100000);
+    let asset = client.get_tokenized_asset(&1u64);
+    assert_eq!(asset.total_supply, 100000);
+    assert_eq!(asset.tokens_in_circulation, 100000);
+
+    // The earlier vote keeps its weight relative to the new supply
+    assert_eq!(client.get_vote_tally(&1u64, &1u64), 60000);
+    assert!(client.proposal_passed(&1u64, &1u64));
+}
+
+#[test]
+fn test_consolidation_keeps_lock_dust_at_asset_level() {
+    let env = create_env();
+    let (admin, user1, user2, user3) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup_minting(&env, &client, &user1);
+    client.transfer_tokens(&1u64, &user1, &user2, &1000i128);
+    client.transfer_tokens(&1u64, &user1, &user3, &1000i128);
+    for holder in [&user2, &user3] {
+        client.lock_token_amount(
+            &1u64,
+            holder,
+            &15i128,
+            &1000u64,
+            &symbol_short!("lockup"),
+            &user1,
+        );
+    }
+
+    client.redenominate_tokens(&1u64, &10u32, &1u32, &user1);
+    assert_eq!(client.get_locked_amount(&1u64, &user2), 1);
+    assert_eq!(client.get_tokenized_asset(&1u64).locked_tokens, 3);
+
+    // Releasing both rounded locks leaves the dust in the asset-level total
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    client.transfer_tokens(&1u64, &user2, &user1, &100i128);
+    client.transfer_tokens(&1u64, &user3, &user1, &100i128);
+    assert_eq!(client.get_locked_amount(&1u64, &user2), 0);
+    assert_eq!(client.get_tokenized_asset(&1u64).locked_tokens, 1);
+}
+
+#[test]
+fn test_records_without_scale_epoch_are_migrated() {
+    let env = create_env();
+    let (admin, user1, user2, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup_minting(&env, &client, &user1);
+    client.transfer_tokens(&1u64, &user1, &user2, &250000i128);
+    client.lock_token_amount(
+        &1u64,
+        &user2,
+        &50000i128,
+        &1000u64,
+        &symbol_short!("lockup"),
+        &user1,
+    );
+    client.cast_vote(&1u64, &1u64, &user2);
+
+    // Rewrite the records in the shape they had before corporate actions
+    env.as_contract(&client.address, || {
+        let store = env.storage().persistent();
+        let epoch = Symbol::new(&env, "scale_epoch");
+        let holder_key = TokenDataKey::TokenHolder(1, user2.clone());
+        let mut record: Map<Symbol, Val> = store.get(&holder_key).unwrap();
+        record.remove(epoch.clone());
+        store.set(&holder_key, &record);
+
+        let locks_key = TokenDataKey::TokenLocks(1, user2.clone());
+        let locks: Vec<Map<Symbol, Val>> = store.get(&locks_key).unwrap();
+        let mut legacy_locks = Vec::<Map<Symbol, Val>>::new(&env);
+        for mut lock in locks.iter() {
+            lock.remove(epoch.clone());
+            legacy_locks.push_back(lock);
+        }
+        store.set(&locks_key, &legacy_locks);
+
+        store.set(&TokenDataKey::VoteTally(1, 1), &250000i128);
+    });
+
+    assert_eq!(client.get_token_balance(&1u64, &user2), 250000);
+    assert_eq!(client.get_locked_amount(&1u64, &user2), 50000);
+    assert_eq!(client.get_vote_tally(&1u64, &1u64), 250000);
+
+    // Legacy records predate every corporate action and scale from epoch 0
+    client.redenominate_tokens(&1u64, &1u32, &2u32, &user1);
+    assert_eq!(client.get_token_balance(&1u64, &user2), 500000);
+    assert_eq!(client.get_locked_amount(&1u64, &user2), 100000);
+    assert_eq!(client.get_vote_tally(&1u64, &1u64), 500000);
+    client.transfer_tokens(&1u64, &user2, &user1, &400000i128);
+    assert_eq!(client.get_ownership_record(&1u64, &user2).scale_epoch, 1);
+}
+
+#[test]
+fn test_redenominate_validation() {
+    let env = create_env();
+    let (admin, user1, user2, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup_minting(&env, &client, &user1);
+
+    assert_eq!(
+        client.try_redenominate_tokens(&1u64, &1u32, &10u32, &user2),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_redenominate_tokens(&1u64, &0u32, &10u32, &user1),
+        Err(Ok(Error::InvalidTokenSupply))
+    );
+    assert_eq!(
+        client.try_redenominate_tokens(&1u64, &5u32, &5u32, &user1),
+        Err(Ok(Error::InvalidTokenSupply))
+    );
+    // Consolidating the whole supply away is rejected
+    assert_eq!(
+        client.try_redenominate_tokens(&1u64, &2000000u32, &1u32, &user1),
+        Err(Ok(Error::InvalidTokenSupply))
+    );
+    assert!(client.get_corporate_actions(&1u64).is_empty());
 }
@@ -5,6 +5,7 @@ extern crate std;
 use soroban_sdk::testutils::Address as _;
 use soroban_sdk::{Address, Env, String};
 
+use crate::identity::{self, InvestorType};
 use crate::tokenization;
 use crate::transfer_restrictions;
 use crate::types::{AssetType, TransferRestriction};
@@ -230,10 +231,11 @@ fn test_validate_transfer_empty_whitelist_allows_all() {
 }
 
 #[test]
-fn test_validate_transfer_accredited_required_uses_whitelist() {
+fn test_validate_transfer_accredited_required_uses_identity_registry() {
     let env = Env::default();
     let contract_id = env.register(AssetUpContract, ());
     let tokenizer = Address::generate(&env);
+    let claim_issuer = Address::generate(&env);
     let accredited = Address::generate(&env);
     let non_accredited = Address::generate(&env);
     let asset_id = 903u64;
@@ -241,13 +243,23 @@ fn test_validate_transfer_accredited_required_uses_whitelist() {
     let (ok_result, err_result) = env.as_contract(&contract_id, || {
         setup_tokenized_asset(&env, asset_id, &tokenizer);
 
-        // Set accredited requirement; whitelist acts as the accredited registry
+        // Accreditation comes from claims issued by a trusted claim issuer
         let restriction = TransferRestriction {
             require_accredited: true,
             geographic_allowed: soroban_sdk::Vec::new(&env),
         };
         transfer_restrictions::set_transfer_restriction(&env, asset_id, restriction).unwrap();
-        transfer_restrictions::add_to_whitelist(&env, asset_id, accredited.clone()).unwrap();
+        identity::add_claim_issuer(&env, claim_issuer.clone()).unwrap();
+        identity::set_identity(
+            &env,
+            claim_issuer.clone(),
+            accredited.clone(),
+            true,
+            String::from_str(&env, "US"),
+            1000,
+            InvestorType::Professional,
+        )
+        .unwrap();
 
         let ok = transfer_restrictions::validate_transfer(
             &env,
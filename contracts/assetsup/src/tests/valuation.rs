@@ -0,0 +1,246 @@
+use crate::attestation::AttestorRole;
+use crate::tests::helpers::*;
+use crate::types::AssetType;
+use crate::valuation::{OracleAsset, PriceData, ValuationMethod, ValuationSource};
+use crate::AssetUpContractClient;
+use soroban_sdk::testutils::Ledger;
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String};
+
+#[contracttype]
+enum MockOracleKey {
+    Price,
+    Decimals,
+}
+
+/// Minimal SEP-40 style oracle returning a settable quote
+#[contract]
+pub struct MockOracle;
+
+#[contractimpl]
+impl MockOracle {
+    pub fn set_price(env: Env, price: i128, timestamp: u64, decimals: u32) {
+        env.storage()
+            .instance()
+            .set(&MockOracleKey::Price, &PriceData { price, timestamp });
+        env.storage()
+            .instance()
+            .set(&MockOracleKey::Decimals, &decimals);
+    }
+
+    pub fn lastprice(env: Env, _asset: OracleAsset) -> Option<PriceData> {
+        env.storage().instance().get(&MockOracleKey::Price)
+    }
+
+    pub fn decimals(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&MockOracleKey::Decimals)
+            .unwrap_or(7)
+    }
+}
+
+fn tokenize(env: &Env, client: &AssetUpContractClient, tokenizer: &Address) {
+    client.tokenize_asset(
+        &1u64,
+        &String::from_str(env, "TST"),
+        &1000000i128,
+        &2u32,
+        &100i128,
+        tokenizer,
+        &String::from_str(env, "Test Token"),
+        &String::from_str(env, "A test tokenized asset"),
+        &AssetType::Physical,
+    );
+}
+
+fn setup_oracle<'a>(env: &'a Env, client: &AssetUpContractClient) -> MockOracleClient<'a> {
+    let oracle_id = env.register(MockOracle, ());
+    client.set_price_oracle(
+        &1u64,
+        &oracle_id,
+        &OracleAsset::Other(symbol_short!("BLDG1")),
+        &3600u64,
+    );
+    MockOracleClient::new(env, &oracle_id)
+}
+
+#[test]
+fn test_valuation_starts_empty() {
+    let env = create_env();
+    let (admin, user1, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    tokenize(&env, &client, &user1);
+
+    assert_eq!(client.get_tokenized_asset(&1u64).valuation, 0);
+    assert_eq!(client.get_valuation_history(&1u64).len(), 0);
+}
+
+#[test]
+fn test_attested_valuation_appends_history() {
+    let env = create_env();
+    let (admin, user1, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    tokenize(&env, &client, &user1);
+
+    let appraiser = create_attestor_key(3);
+    let appraiser_pk = attestor_public_key(&env, &appraiser);
+    client.register_attestor(&appraiser_pk, &AttestorRole::Appraiser);
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    let signature = sign_valuation(&env, &appraiser, 1, 4000000, 500);
+    client.update_valuation(
+        &1u64,
+        &4000000i128,
+        &ValuationMethod::IncomeApproach,
+        &appraiser_pk,
+        &500u64,
+        &signature,
+    );
+
+    let history = client.get_valuation_history(&1u64);
+    assert_eq!(history.len(), 1);
+    let record = history.get(0).unwrap();
+    assert_eq!(record.valuation, 4000000);
+    assert_eq!(record.timestamp, 500);
+    assert_eq!(record.source, ValuationSource::Attestor(appraiser_pk));
+    assert_eq!(record.method, ValuationMethod::IncomeApproach);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #30)")]
+fn test_attested_valuation_rejects_oracle_method() {
+    let env = create_env();
+    let (admin, user1, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    tokenize(&env, &client, &user1);
+
+    let appraiser = create_attestor_key(3);
+    let appraiser_pk = attestor_public_key(&env, &appraiser);
+    client.register_attestor(&appraiser_pk, &AttestorRole::Appraiser);
+
+    let signature = sign_valuation(&env, &appraiser, 1, 4000000, 0);
+    client.update_valuation(
+        &1u64,
+        &4000000i128,
+        &ValuationMethod::OraclePrice,
+        &appraiser_pk,
+        &0u64,
+        &signature,
+    );
+}
+
+#[test]
+fn test_update_valuation_from_oracle() {
+    let env = create_env();
+    let (admin, user1, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    tokenize(&env, &client, &user1);
+    let oracle = setup_oracle(&env, &client);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    // 1.5 units quoted with 8 decimals -> 15,000,000 stroops
+    oracle.set_price(&150000000i128, &990u64, &8u32);
+
+    let valuation = client.update_valuation_from_oracle(&1u64);
+    assert_eq!(valuation, 15000000);
+    assert_eq!(client.get_tokenized_asset(&1u64).valuation, 15000000);
+
+    let record = client.get_valuation_history(&1u64).get(0).unwrap();
+    assert_eq!(
+        record.source,
+        ValuationSource::Oracle(oracle.address.clone())
+    );
+    assert_eq!(record.method, ValuationMethod::OraclePrice);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #30)")]
+fn test_update_valuation_from_stale_oracle() {
+    let env = create_env();
+    let (admin, user1, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    tokenize(&env, &client, &user1);
+    let oracle = setup_oracle(&env, &client);
+
+    oracle.set_price(&50000000i128, &1000u64, &7u32);
+    env.ledger().with_mut(|li| li.timestamp = 1000 + 3601);
+
+    client.update_valuation_from_oracle(&1u64);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #30)")]
+fn test_update_valuation_without_oracle() {
+    let env = create_env();
+    let (admin, user1, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    tokenize(&env, &client, &user1);
+
+    client.update_valuation_from_oracle(&1u64);
+}
+
+#[test]
+fn test_price_per_token_and_holder_nav() {
+    let env = create_env();
+    let (admin, user1, user2, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    tokenize(&env, &client, &user1);
+    let oracle = setup_oracle(&env, &client);
+
+    oracle.set_price(&5000000i128, &0u64, &7u32);
+    client.update_valuation_from_oracle(&1u64);
+    client.transfer_tokens(&1u64, &user1, &user2, &250000i128);
+
+    // 1,000,000 base units at 2 decimals = 10,000 whole tokens
+    assert_eq!(client.get_price_per_token(&1u64), 500);
+    assert_eq!(client.get_holder_nav(&1u64, &user2), 1250000);
+    assert_eq!(client.get_holder_nav(&1u64, &user1), 3750000);
+}
+
+#[test]
+fn test_time_weighted_valuation() {
+    let env = create_env();
+    let (admin, user1, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    tokenize(&env, &client, &user1);
+    let oracle = setup_oracle(&env, &client);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    oracle.set_price(&1000000i128, &100u64, &7u32);
+    client.update_valuation_from_oracle(&1u64);
+
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    oracle.set_price(&3000000i128, &200u64, &7u32);
+    client.update_valuation_from_oracle(&1u64);
+
+    assert_eq!(
+        client.get_time_weighted_valuation(&1u64, &100, &300),
+        2000000
+    );
+    // Time before the first valuation is excluded
+    assert_eq!(client.get_time_weighted_valuation(&1u64, &0, &300), 2000000);
+    assert_eq!(
+        client.get_time_weighted_valuation(&1u64, &150, &250),
+        2000000
+    );
+    assert_eq!(
+        client.get_time_weighted_valuation(&1u64, &100, &200),
+        1000000
+    );
+    // Zero-length window returns the valuation in effect
+    assert_eq!(
+        client.get_time_weighted_valuation(&1u64, &250, &250),
+        3000000
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #46)")]
+fn test_time_weighted_valuation_invalid_window() {
+    let env = create_env();
+    let (admin, user1, _, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    tokenize(&env, &client, &user1);
+
+    client.get_time_weighted_valuation(&1u64, &300, &100);
+}
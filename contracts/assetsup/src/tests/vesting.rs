@@ -0,0 +1,208 @@
+use crate::tests::helpers::*;
+use crate::types::AssetType;
+use crate::AssetUpContractClient;
+use soroban_sdk::testutils::Ledger;
+use soroban_sdk::{Address, Env, String};
+
+fn setup(env: &Env, client: &AssetUpContractClient, tokenizer: &Address) {
+    client.tokenize_asset(
+        &1u64,
+        &String::from_str(env, "TST"),
+        &1000000i128,
+        &2u32,
+        &100i128,
+        tokenizer,
+        &String::from_str(env, "Test Token"),
+        &String::from_str(env, "A test tokenized asset"),
+        &AssetType::Physical,
+    );
+}
+
+/// 100,000 tokens vesting from t=1000 over 1000 seconds with a 250 second cliff
+fn grant(client: &AssetUpContractClient, tokenizer: &Address, holder: &Address, revocable: bool) {
+    client.create_vesting_schedule(
+        &1u64,
+        holder,
+        &100000i128,
+        &1000u64,
+        &250u64,
+        &1000u64,
+        &revocable,
+        tokenizer,
+    );
+}
+
+#[test]
+fn test_create_vesting_schedule() {
+    let env = create_env();
+    let (admin, tokenizer, holder, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &tokenizer);
+
+    grant(&client, &tokenizer, &holder, true);
+
+    assert_eq!(client.get_token_balance(&1u64, &holder), 100000);
+    assert_eq!(client.get_token_balance(&1u64, &tokenizer), 900000);
+    let schedules = client.get_vesting_schedules(&1u64, &holder);
+    assert_eq!(schedules.len(), 1);
+    assert_eq!(schedules.get(0).unwrap().total_amount, 100000);
+    assert_eq!(client.get_vested_amount(&1u64, &holder), 0);
+    assert_eq!(client.get_unvested_amount(&1u64, &holder), 100000);
+}
+
+#[test]
+fn test_vesting_cliff_and_linear_release() {
+    let env = create_env();
+    let (admin, tokenizer, holder, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &tokenizer);
+    grant(&client, &tokenizer, &holder, false);
+
+    // Just before the cliff nothing has vested
+    env.ledger().with_mut(|li| li.timestamp = 1249);
+    assert_eq!(client.get_vested_amount(&1u64, &holder), 0);
+
+    // At the cliff the linear amount since start becomes available
+    env.ledger().with_mut(|li| li.timestamp = 1250);
+    assert_eq!(client.get_vested_amount(&1u64, &holder), 25000);
+
+    env.ledger().with_mut(|li| li.timestamp = 1600);
+    assert_eq!(client.get_vested_amount(&1u64, &holder), 60000);
+    assert_eq!(client.get_unvested_amount(&1u64, &holder), 40000);
+
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+    assert_eq!(client.get_vested_amount(&1u64, &holder), 100000);
+    assert_eq!(client.get_unvested_amount(&1u64, &holder), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_transfer_blocked_before_cliff() {
+    let env = create_env();
+    let (admin, tokenizer, holder, receiver) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &tokenizer);
+    grant(&client, &tokenizer, &holder, false);
+
+    env.ledger().with_mut(|li| li.timestamp = 1100);
+    client.transfer_tokens(&1u64, &holder, &receiver, &1i128);
+}
+
+#[test]
+fn test_transfer_limited_to_vested_amount() {
+    let env = create_env();
+    let (admin, tokenizer, holder, receiver) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &tokenizer);
+    grant(&client, &tokenizer, &holder, false);
+
+    env.ledger().with_mut(|li| li.timestamp = 1500);
+    client.transfer_tokens(&1u64, &holder, &receiver, &50000i128);
+
+    assert_eq!(client.get_token_balance(&1u64, &holder), 50000);
+    assert_eq!(client.get_token_balance(&1u64, &receiver), 50000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_transfer_above_vested_amount() {
+    let env = create_env();
+    let (admin, tokenizer, holder, receiver) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &tokenizer);
+    grant(&client, &tokenizer, &holder, false);
+
+    env.ledger().with_mut(|li| li.timestamp = 1500);
+    client.transfer_tokens(&1u64, &holder, &receiver, &50001i128);
+}
+
+#[test]
+fn test_revoke_returns_unvested_tokens() {
+    let env = create_env();
+    let (admin, tokenizer, holder, receiver) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &tokenizer);
+    grant(&client, &tokenizer, &holder, true);
+
+    env.ledger().with_mut(|li| li.timestamp = 1300);
+    let returned = client.revoke_vesting(&1u64, &holder, &0u32, &tokenizer);
+    assert_eq!(returned, 70000);
+
+    assert_eq!(client.get_token_balance(&1u64, &holder), 30000);
+    assert_eq!(client.get_token_balance(&1u64, &tokenizer), 970000);
+    assert_eq!(client.get_unvested_amount(&1u64, &holder), 0);
+    assert!(
+        client
+            .get_vesting_schedules(&1u64, &holder)
+            .get(0)
+            .unwrap()
+            .revoked
+    );
+
+    // The vested remainder is freely transferable
+    client.transfer_tokens(&1u64, &holder, &receiver, &30000i128);
+    assert_eq!(client.get_token_balance(&1u64, &receiver), 30000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_revoke_non_revocable_schedule() {
+    let env = create_env();
+    let (admin, tokenizer, holder, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &tokenizer);
+    grant(&client, &tokenizer, &holder, false);
+
+    client.revoke_vesting(&1u64, &holder, &0u32, &tokenizer);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_create_vesting_not_tokenizer() {
+    let env = create_env();
+    let (admin, tokenizer, holder, other) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &tokenizer);
+
+    grant(&client, &other, &holder, false);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #46)")]
+fn test_create_vesting_cliff_after_end() {
+    let env = create_env();
+    let (admin, tokenizer, holder, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &tokenizer);
+
+    client.create_vesting_schedule(
+        &1u64,
+        &holder,
+        &100000i128,
+        &1000u64,
+        &2000u64,
+        &1000u64,
+        &false,
+        &tokenizer,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #46)")]
+fn test_create_vesting_end_overflows() {
+    let env = create_env();
+    let (admin, tokenizer, holder, _) = create_mock_addresses(&env);
+    let client = initialize_contract(&env, &admin);
+    setup(&env, &client, &tokenizer);
+
+    client.create_vesting_schedule(
+        &1u64,
+        &holder,
+        &100000i128,
+        &(u64::MAX - 10),
+        &0u64,
+        &1000u64,
+        &false,
+        &tokenizer,
+    );
+}
@@ -1,6 +1,23 @@
+use crate::asset;
+use crate::attestation::{self, AttestationField};
+use crate::compliance;
+use crate::enforcement;
 use crate::error::Error;
-use crate::types::{OwnershipRecord, TokenDataKey, TokenMetadata, TokenizedAsset};
-use soroban_sdk::{Address, Env, String, Vec};
+use crate::marketplace;
+use crate::math::{self, Rounding};
+use crate::offering;
+use crate::types::{
+    BalanceSnapshot, CapTableEntry, CorporateAction, CorporateActionKind, GovernedMinting,
+    MintApproval, MintingPolicy, OwnershipRecord, TokenDataKey, TokenLock, TokenMetadata,
+    TokenizedAsset, UnapprovedMints,
+};
+use crate::valuation::{self, ValuationMethod, ValuationSource};
+use crate::vesting;
+use crate::voting;
+use soroban_sdk::{Address, BytesN, Env, IntoVal, Map, String, Symbol, TryFromVal, Val, Vec};
+
+/// Highest number of decimals a token may use
+pub const MAX_TOKEN_DECIMALS: u32 = 18;
 
 /// Initialize tokenization by creating tokenized asset
 /// Only contract admin or asset owner can tokenize
@@ -19,6 +36,9 @@ pub fn tokenize_asset(
     if total_supply <= 0 {
         return Err(Error::InvalidTokenSupply);
     }
+    if decimals > MAX_TOKEN_DECIMALS {
+        return Err(Error::InvalidTokenDecimals);
+    }
 
     // Check if asset is already tokenized
     let store = env.storage().persistent();
@@ -36,7 +56,7 @@ pub fn tokenize_asset(
         decimals,
         locked_tokens: 0,
         tokenizer: tokenizer.clone(),
-        valuation: total_supply,
+        valuation: 0,
         token_holders_count: 1,
         tokens_in_circulation: total_supply,
         min_voting_threshold,
@@ -62,6 +82,7 @@ pub fn tokenize_asset(
         dividend_entitlement: total_supply,
         unclaimed_dividends: 0,
         ownership_percentage: 10000, // 100% in basis points
+        scale_epoch: current_scale_epoch(env, asset_id),
     };
 
     let holder_key = TokenDataKey::TokenHolder(asset_id, tokenizer.clone());
@@ -82,13 +103,237 @@ pub fn tokenize_asset(
     Ok(tokenized_asset)
 }
 
+/// Record which registry asset a tokenized asset represents
+/// Only the tokenizer can link, and only to a registry asset it owns
+pub fn link_registry_asset(
+    env: &Env,
+    asset_id: u64,
+    registry_id: BytesN<32>,
+    tokenizer: Address,
+) -> Result<(), Error> {
+    let store = env.storage().persistent();
+    let tokenized_asset: TokenizedAsset = store
+        .get(&TokenDataKey::TokenizedAsset(asset_id))
+        .ok_or(Error::AssetNotTokenized)?;
+    let registered: asset::Asset = store
+        .get(&asset::DataKey::Asset(registry_id.clone()))
+        .ok_or(Error::AssetNotFound)?;
+    if tokenized_asset.tokenizer != tokenizer || registered.owner != tokenizer {
+        return Err(Error::Unauthorized);
+    }
+
+    // Each side links at most once per tokenization
+    let link_key = TokenDataKey::RegistryAsset(asset_id);
+    let reverse_key = asset::DataKey::TokenizedAs(registry_id.clone());
+    if store.has(&link_key) || store.has(&reverse_key) {
+        return Err(Error::AssetAlreadyTokenized);
+    }
+
+    store.set(&link_key, &registry_id);
+    store.set(&reverse_key, &asset_id);
+    // Tokenizing again lifts an earlier detokenization
+    store.remove(&asset::DataKey::Detokenized(registry_id.clone()));
+
+    env.events()
+        .publish(("token", "registry_linked"), (asset_id, registry_id));
+
+    Ok(())
+}
+
+/// Get the registry asset a tokenized asset represents, if linked
+pub fn get_registry_asset(env: &Env, asset_id: u64) -> Option<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&TokenDataKey::RegistryAsset(asset_id))
+}
+
 /// Mint additional tokens
-/// Only tokenizer can mint
+/// Only tokenizer can mint, within the asset's minting policy
 pub fn mint_tokens(
     env: &Env,
     asset_id: u64,
     amount: i128,
     minter: Address,
+) -> Result<TokenizedAsset, Error> {
+    mint_supply(env, asset_id, amount, minter, false)
+}
+
+/// Load the mint a proposal is bound to, with the amount in the current denomination
+pub fn load_mint_approval(
+    env: &Env,
+    asset_id: u64,
+    proposal_id: u64,
+) -> Result<Option<MintApproval>, Error> {
+    let stored: Option<MintApproval> = env
+        .storage()
+        .persistent()
+        .get(&TokenDataKey::MintApproval(asset_id, proposal_id));
+    let Some(mut approval) = stored else {
+        return Ok(None);
+    };
+    let actions = get_corporate_actions(env, asset_id);
+    approval.amount = scale_amount(&actions, approval.scale_epoch, approval.amount)?;
+    approval.eligible_supply =
+        scale_amount(&actions, approval.scale_epoch, approval.eligible_supply)?;
+    approval.scale_epoch = actions.len();
+    Ok(Some(approval))
+}
+
+/// Take a new balance snapshot of an asset. Holders are not iterated: each
+/// holder's balance is recorded the first time it changes afterwards.
+fn take_snapshot(env: &Env, asset_id: u64) -> u32 {
+    let key = TokenDataKey::SnapshotCounter(asset_id);
+    let snapshot_id = env.storage().persistent().get(&key).unwrap_or(0u32) + 1;
+    env.storage().persistent().set(&key, &snapshot_id);
+    snapshot_id
+}
+
+/// Before a holder's balance changes, record what it was at the latest
+/// snapshot if nothing has been recorded since that snapshot was taken
+fn checkpoint_balance(
+    env: &Env,
+    asset_id: u64,
+    holder: &Address,
+    balance: i128,
+) -> Result<(), Error> {
+    let store = env.storage().persistent();
+    let snapshot_id: u32 = store
+        .get(&TokenDataKey::SnapshotCounter(asset_id))
+        .unwrap_or(0);
+    if snapshot_id == 0 {
+        return Ok(());
+    }
+    let key = TokenDataKey::BalanceSnapshots(asset_id, holder.clone());
+    let mut snapshots: Vec<BalanceSnapshot> = store.get(&key).unwrap_or_else(|| Vec::new(env));
+    if snapshots
+        .last()
+        .is_some_and(|snapshot| snapshot.snapshot_id >= snapshot_id)
+    {
+        return Ok(());
+    }
+    snapshots.push_back(BalanceSnapshot {
+        snapshot_id,
+        balance,
+        scale_epoch: current_scale_epoch(env, asset_id),
+    });
+    store.set(&key, &snapshots);
+    Ok(())
+}
+
+/// A holder's balance when `snapshot_id` was taken, in the current denomination
+pub fn balance_at_snapshot(
+    env: &Env,
+    asset_id: u64,
+    holder: &Address,
+    snapshot_id: u32,
+) -> Result<i128, Error> {
+    let snapshots: Vec<BalanceSnapshot> = env
+        .storage()
+        .persistent()
+        .get(&TokenDataKey::BalanceSnapshots(asset_id, holder.clone()))
+        .unwrap_or_else(|| Vec::new(env));
+    // The first record at or after the snapshot holds the balance it saw;
+    // without one the balance has not changed since
+    for snapshot in snapshots.iter() {
+        if snapshot.snapshot_id >= snapshot_id {
+            return scale_amount(
+                &get_corporate_actions(env, asset_id),
+                snapshot.scale_epoch,
+                snapshot.balance,
+            );
+        }
+    }
+    get_token_balance(env, asset_id, holder.clone())
+}
+
+/// Bind a proposal to a governed mint of `amount` (tokenizer only). The
+/// proposal must not have any votes yet, so holders vote on a known amount.
+/// Votes are weighted by balances at a snapshot taken now, and the tokenizer
+/// and contract escrow cannot vote on their own dilution.
+pub fn propose_mint(
+    env: &Env,
+    asset_id: u64,
+    proposal_id: u64,
+    amount: i128,
+    caller: Address,
+) -> Result<(), Error> {
+    let tokenized_asset = get_tokenized_asset(env, asset_id)?;
+    if tokenized_asset.tokenizer != caller {
+        return Err(Error::Unauthorized);
+    }
+    if amount <= 0 {
+        return Err(Error::InvalidTokenSupply);
+    }
+
+    // Detokenization votes use the asset ID as their proposal ID
+    let key = TokenDataKey::MintApproval(asset_id, proposal_id);
+    if proposal_id == asset_id
+        || env.storage().persistent().has(&key)
+        || voting::get_vote_tally(env, asset_id, proposal_id)? > 0
+    {
+        return Err(Error::InvalidProposal);
+    }
+
+    let eligible_supply = math::sub(
+        math::sub(
+            tokenized_asset.total_supply,
+            get_token_balance(env, asset_id, caller.clone())?,
+        )?,
+        get_token_balance(env, asset_id, env.current_contract_address())?,
+    )?;
+    env.storage().persistent().set(
+        &key,
+        &MintApproval {
+            amount,
+            used: false,
+            snapshot_id: take_snapshot(env, asset_id),
+            eligible_supply,
+            scale_epoch: current_scale_epoch(env, asset_id),
+        },
+    );
+
+    // Emit event: (asset_id, proposal_id, amount)
+    env.events()
+        .publish(("token", "mint_proposed"), (asset_id, proposal_id, amount));
+
+    Ok(())
+}
+
+/// Mint above the governance threshold, backed by a passed holder vote on a
+/// proposal bound to exactly this amount. Each proposal authorizes one mint.
+pub fn mint_tokens_with_approval(
+    env: &Env,
+    asset_id: u64,
+    amount: i128,
+    proposal_id: u64,
+    minter: Address,
+) -> Result<TokenizedAsset, Error> {
+    let mut approval =
+        load_mint_approval(env, asset_id, proposal_id)?.ok_or(Error::InvalidProposal)?;
+    if approval.used {
+        return Err(Error::InvalidProposal);
+    }
+    if amount != approval.amount {
+        return Err(Error::InvalidTokenSupply);
+    }
+    if !voting::proposal_passed(env, asset_id, proposal_id)? {
+        return Err(Error::Unauthorized);
+    }
+    approval.used = true;
+    env.storage().persistent().set(
+        &TokenDataKey::MintApproval(asset_id, proposal_id),
+        &approval,
+    );
+
+    mint_supply(env, asset_id, amount, minter, true)
+}
+
+fn mint_supply(
+    env: &Env,
+    asset_id: u64,
+    amount: i128,
+    minter: Address,
+    approved: bool,
 ) -> Result<TokenizedAsset, Error> {
     if amount <= 0 {
         return Err(Error::InvalidTokenSupply);
@@ -105,22 +350,29 @@ pub fn mint_tokens(
         return Err(Error::Unauthorized);
     }
 
+    let unapproved = check_minting_policy(env, &tokenized_asset, amount, approved)?;
+    compliance::check_transfer(env, asset_id, None, Some(minter.clone()), amount)?;
+    if let Some(minted) = unapproved {
+        set_unapproved_mints(env, asset_id, minted);
+    }
+
     // Update total supply
-    tokenized_asset.total_supply += amount;
-    tokenized_asset.tokens_in_circulation += amount;
+    let supply_before = tokenized_asset.total_supply;
+    tokenized_asset.total_supply = math::add(tokenized_asset.total_supply, amount)?;
+    tokenized_asset.tokens_in_circulation =
+        math::add(tokenized_asset.tokens_in_circulation, amount)?;
 
     // Update tokenizer's ownership
     let holder_key = TokenDataKey::TokenHolder(asset_id, minter.clone());
-    let mut ownership: OwnershipRecord = store.get(&holder_key).ok_or(Error::HolderNotFound)?;
+    let mut ownership = load_ownership(env, asset_id, &minter)?.ok_or(Error::HolderNotFound)?;
 
-    ownership.balance += amount;
+    checkpoint_balance(env, asset_id, &minter, ownership.balance)?;
+    ownership.balance = math::add(ownership.balance, amount)?;
     ownership.voting_power = ownership.balance;
     ownership.dividend_entitlement = ownership.balance;
 
-    // Recalculate ownership percentage
-    ownership.ownership_percentage = (ownership.balance * 10000) / tokenized_asset.total_supply;
-
     store.set(&holder_key, &ownership);
+    update_holders_list(env, &mut tokenized_asset, &minter, Some(&ownership))?;
     store.set(&key, &tokenized_asset.clone());
 
     // Emit event: (asset_id, amount, new_supply)
@@ -128,6 +380,7 @@ pub fn mint_tokens(
         ("token", "tokens_minted"),
         (asset_id, amount, tokenized_asset.total_supply),
     );
+    emit_supply_changed(env, asset_id, supply_before, tokenized_asset.total_supply);
 
     Ok(tokenized_asset)
 }
@@ -157,24 +410,31 @@ pub fn burn_tokens(
 
     // Get burner's balance
     let holder_key = TokenDataKey::TokenHolder(asset_id, burner.clone());
-    let mut ownership: OwnershipRecord = store.get(&holder_key).ok_or(Error::HolderNotFound)?;
+    let mut ownership = load_ownership(env, asset_id, &burner)?.ok_or(Error::HolderNotFound)?;
 
     if ownership.balance < amount {
         return Err(Error::InsufficientBalance);
     }
+    release_expired_locks(env, asset_id, burner.clone())?;
+    if math::sub(ownership.balance, amount)? < get_locked_amount(env, asset_id, burner.clone())? {
+        return Err(Error::InsufficientLockedTokens);
+    }
+
+    compliance::check_transfer(env, asset_id, Some(burner.clone()), None, amount)?;
 
     // Update balances
-    ownership.balance -= amount;
+    checkpoint_balance(env, asset_id, &burner, ownership.balance)?;
+    ownership.balance = math::sub(ownership.balance, amount)?;
     ownership.voting_power = ownership.balance;
     ownership.dividend_entitlement = ownership.balance;
 
-    // Recalculate ownership percentage
-    ownership.ownership_percentage = (ownership.balance * 10000) / tokenized_asset.total_supply;
-
-    tokenized_asset.total_supply -= amount;
-    tokenized_asset.tokens_in_circulation -= amount;
+    let supply_before = tokenized_asset.total_supply;
+    tokenized_asset.total_supply = math::sub(tokenized_asset.total_supply, amount)?;
+    tokenized_asset.tokens_in_circulation =
+        math::sub(tokenized_asset.tokens_in_circulation, amount)?;
 
     store.set(&holder_key, &ownership);
+    update_holders_list(env, &mut tokenized_asset, &burner, Some(&ownership))?;
     store.set(&key, &tokenized_asset.clone());
 
     // Emit event: (asset_id, amount, new_supply)
@@ -182,10 +442,154 @@ pub fn burn_tokens(
         ("token", "tokens_burned"),
         (asset_id, amount, tokenized_asset.total_supply),
     );
+    emit_supply_changed(env, asset_id, supply_before, tokenized_asset.total_supply);
 
     Ok(tokenized_asset)
 }
 
+fn emit_supply_changed(env: &Env, asset_id: u64, before: i128, after: i128) {
+    // Emit event: (asset_id, supply_before, supply_after)
+    env.events()
+        .publish(("token", "supply_changed"), (asset_id, before, after));
+}
+
+/// Maximum supply allowed by a policy, if it sets one
+fn policy_cap(policy: &MintingPolicy) -> Option<i128> {
+    match policy {
+        MintingPolicy::Unrestricted => None,
+        MintingPolicy::FixedSupply => Some(0),
+        MintingPolicy::Capped(max_supply) => Some(*max_supply),
+        MintingPolicy::GovernanceApproved(governed) => Some(governed.max_supply),
+    }
+}
+
+/// Amount minted without a vote since the last approved mint
+fn unapproved_mints(env: &Env, asset_id: u64) -> Result<i128, Error> {
+    let stored: Option<UnapprovedMints> = env
+        .storage()
+        .persistent()
+        .get(&TokenDataKey::UnapprovedMints(asset_id));
+    match stored {
+        Some(minted) => scale_amount(
+            &get_corporate_actions(env, asset_id),
+            minted.scale_epoch,
+            minted.amount,
+        ),
+        None => Ok(0),
+    }
+}
+
+fn set_unapproved_mints(env: &Env, asset_id: u64, amount: i128) {
+    env.storage().persistent().set(
+        &TokenDataKey::UnapprovedMints(asset_id),
+        &UnapprovedMints {
+            amount,
+            scale_epoch: current_scale_epoch(env, asset_id),
+        },
+    );
+}
+
+/// Check a mint against the asset's policy. Under governed minting this also
+/// tracks the running total of unapproved mints, which an approved mint resets.
+/// Returns the unapproved-mint total to record for governed assets
+fn check_minting_policy(
+    env: &Env,
+    tokenized_asset: &TokenizedAsset,
+    amount: i128,
+    approved: bool,
+) -> Result<Option<i128>, Error> {
+    let new_supply = math::add(tokenized_asset.total_supply, amount)?;
+    match get_minting_policy(env, tokenized_asset.asset_id) {
+        MintingPolicy::Unrestricted => Ok(None),
+        MintingPolicy::FixedSupply => Err(Error::InvalidTokenSupply),
+        MintingPolicy::Capped(max_supply) if new_supply > max_supply => {
+            Err(Error::InvalidTokenSupply)
+        }
+        MintingPolicy::Capped(_) => Ok(None),
+        MintingPolicy::GovernanceApproved(governed) => {
+            if new_supply > governed.max_supply {
+                return Err(Error::InvalidTokenSupply);
+            }
+            if approved {
+                return Ok(Some(0));
+            }
+            let minted = math::add(unapproved_mints(env, tokenized_asset.asset_id)?, amount)?;
+            if minted > governed.threshold {
+                return Err(Error::Unauthorized);
+            }
+            Ok(Some(minted))
+        }
+    }
+}
+
+/// Whether `mint_tokens` would accept this mint, without minting
+pub fn check_mint(env: &Env, asset_id: u64, amount: i128, minter: &Address) -> Result<(), Error> {
+    if amount <= 0 {
+        return Err(Error::InvalidTokenSupply);
+    }
+    let tokenized_asset = get_tokenized_asset(env, asset_id)?;
+    if tokenized_asset.tokenizer != *minter {
+        return Err(Error::Unauthorized);
+    }
+    check_minting_policy(env, &tokenized_asset, amount, false)?;
+    compliance::check_transfer(env, asset_id, None, Some(minter.clone()), amount)
+}
+
+/// Set an asset's minting policy (tokenizer only). Policies can only get
+/// stricter: a fixed supply is permanent and a cap can be lowered but not raised.
+pub fn set_minting_policy(
+    env: &Env,
+    asset_id: u64,
+    policy: MintingPolicy,
+    caller: Address,
+) -> Result<(), Error> {
+    let tokenized_asset = get_tokenized_asset(env, asset_id)?;
+    if tokenized_asset.tokenizer != caller {
+        return Err(Error::Unauthorized);
+    }
+
+    match &policy {
+        MintingPolicy::Capped(max_supply) if *max_supply < tokenized_asset.total_supply => {
+            return Err(Error::InvalidTokenSupply);
+        }
+        MintingPolicy::GovernanceApproved(governed)
+            if governed.max_supply < tokenized_asset.total_supply || governed.threshold < 0 =>
+        {
+            return Err(Error::InvalidTokenSupply);
+        }
+        _ => {}
+    }
+
+    let current = get_minting_policy(env, asset_id);
+    let loosened = match (policy_cap(&current), policy_cap(&policy)) {
+        (Some(_), None) => true,
+        (Some(current_cap), Some(new_cap)) => {
+            current == MintingPolicy::FixedSupply || new_cap > current_cap
+        }
+        (None, _) => false,
+    };
+    if loosened {
+        return Err(Error::Unauthorized);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&TokenDataKey::MintPolicy(asset_id), &policy);
+
+    // Emit event: (asset_id, policy)
+    env.events()
+        .publish(("token", "minting_policy_set"), (asset_id, policy));
+
+    Ok(())
+}
+
+pub fn get_minting_policy(env: &Env, asset_id: u64) -> MintingPolicy {
+    env.storage()
+        .persistent()
+        .get(&TokenDataKey::MintPolicy(asset_id))
+        .unwrap_or(MintingPolicy::Unrestricted)
+}
+
 /// Transfer tokens from one address to another
 pub fn transfer_tokens(
     env: &Env,
@@ -197,25 +601,71 @@ pub fn transfer_tokens(
     if amount <= 0 {
         return Err(Error::InvalidTokenSupply);
     }
+    let balance = get_token_balance(env, asset_id, from.clone())?;
+    check_transfer_rules(env, asset_id, &from, &to, amount, balance)?;
 
-    let store = env.storage().persistent();
-
-    // Verify asset is tokenized
-    let key = TokenDataKey::TokenizedAsset(asset_id);
-    let tokenized_asset: TokenizedAsset = store.get(&key).ok_or(Error::AssetNotTokenized)?;
+    move_tokens(env, asset_id, from, to, amount)
+}
 
+/// Whether `from` may send `amount` to `to` when holding `balance`, without
+/// moving anything. Callers about to credit `from` pass the credited balance.
+pub fn check_transfer_rules(
+    env: &Env,
+    asset_id: u64,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+    balance: i128,
+) -> Result<(), Error> {
     // Check if from address has locked tokens
     let lock_key = TokenDataKey::TokenLockedUntil(asset_id, from.clone());
-    if let Some(lock_time) = store.get::<_, u64>(&lock_key) {
+    if let Some(lock_time) = env.storage().persistent().get::<_, u64>(&lock_key) {
         if env.ledger().timestamp() < lock_time {
             return Err(Error::TokensAreLocked);
         }
     }
 
+    enforcement::check_transfer(env, asset_id, from, to)?;
+
+    // Locked, unvested and frozen tokens must all stay with the holder
+    let restricted = restricted_amount(env, asset_id, from)?;
+    if restricted > 0 && balance.saturating_sub(amount) < restricted {
+        return Err(if get_locked_amount(env, asset_id, from.clone())? > 0 {
+            Error::InsufficientLockedTokens
+        } else {
+            Error::TokensAreLocked
+        });
+    }
+
+    compliance::check_transfer(env, asset_id, Some(from.clone()), Some(to.clone()), amount)?;
+
+    if balance < amount {
+        return Err(Error::InsufficientBalance);
+    }
+    Ok(())
+}
+
+/// Move tokens between holders without lock or vesting checks.
+/// Callers are responsible for enforcing transfer rules.
+pub fn move_tokens(
+    env: &Env,
+    asset_id: u64,
+    from: Address,
+    to: Address,
+    amount: i128,
+) -> Result<(), Error> {
+    // Clear the sender's expired locks before its record is rewritten
+    release_expired_locks(env, asset_id, from.clone())?;
+
+    let store = env.storage().persistent();
+
+    // Verify asset is tokenized
+    let key = TokenDataKey::TokenizedAsset(asset_id);
+    let mut tokenized_asset: TokenizedAsset = store.get(&key).ok_or(Error::AssetNotTokenized)?;
+
     // Get from balance
     let from_holder_key = TokenDataKey::TokenHolder(asset_id, from.clone());
-    let mut from_ownership: OwnershipRecord =
-        store.get(&from_holder_key).ok_or(Error::HolderNotFound)?;
+    let mut from_ownership = load_ownership(env, asset_id, &from)?.ok_or(Error::HolderNotFound)?;
 
     if from_ownership.balance < amount {
         return Err(Error::InsufficientBalance);
@@ -223,7 +673,7 @@ pub fn transfer_tokens(
 
     // Get to balance (or create new holder)
     let to_holder_key = TokenDataKey::TokenHolder(asset_id, to.clone());
-    let mut to_ownership: OwnershipRecord = match store.get(&to_holder_key) {
+    let mut to_ownership = match load_ownership(env, asset_id, &to)? {
         Some(ownership) => ownership,
         None => {
             // Create new holder
@@ -237,37 +687,36 @@ pub fn transfer_tokens(
                 dividend_entitlement: 0,
                 unclaimed_dividends: 0,
                 ownership_percentage: 0,
+                scale_epoch: current_scale_epoch(env, asset_id),
             }
         }
     };
 
     // Update balances
-    from_ownership.balance -= amount;
+    checkpoint_balance(env, asset_id, &from, from_ownership.balance)?;
+    checkpoint_balance(env, asset_id, &to, to_ownership.balance)?;
+    from_ownership.balance = math::sub(from_ownership.balance, amount)?;
     from_ownership.voting_power = from_ownership.balance;
     from_ownership.dividend_entitlement = from_ownership.balance;
-    from_ownership.ownership_percentage =
-        (from_ownership.balance * 10000) / tokenized_asset.total_supply;
 
-    to_ownership.balance += amount;
+    // Holding starts over for a holder re-entering from a zero balance
+    if to_ownership.balance == 0 {
+        to_ownership.acquisition_timestamp = env.ledger().timestamp();
+    }
+    to_ownership.balance = math::add(to_ownership.balance, amount)?;
     to_ownership.voting_power = to_ownership.balance;
     to_ownership.dividend_entitlement = to_ownership.balance;
-    to_ownership.ownership_percentage =
-        (to_ownership.balance * 10000) / tokenized_asset.total_supply;
 
     store.set(&from_holder_key, &from_ownership);
     store.set(&to_holder_key, &to_ownership);
 
-    // Add to holder list if new
-    let holders_list_key = TokenDataKey::TokenHoldersList(asset_id);
-    let mut holders: Vec<Address> = store
-        .get(&holders_list_key)
-        .ok_or(Error::AssetNotTokenized)?;
+    // Drop emptied holders and add new ones
+    update_holders_list(env, &mut tokenized_asset, &from, Some(&from_ownership))?;
+    update_holders_list(env, &mut tokenized_asset, &to, Some(&to_ownership))?;
+    store.set(&key, &tokenized_asset);
 
-    let is_new_holder = !holders.iter().any(|h| h == to);
-    if is_new_holder {
-        holders.push_back(to.clone());
-        store.set(&holders_list_key, &holders);
-    }
+    // Forced moves can take tokens that were under a partial lock
+    clamp_token_locks(env, asset_id, &from, from_ownership.balance)?;
 
     // Emit event: (asset_id, from, to, amount)
     env.events().publish(
@@ -278,17 +727,189 @@ pub fn transfer_tokens(
     Ok(())
 }
 
-/// Get token balance for an address
-pub fn get_token_balance(env: &Env, asset_id: u64, holder: Address) -> Result<i128, Error> {
+/// Fold a purchase of `amount` tokens at `price` (per whole token) into the
+/// holder's average purchase price. Call after the tokens have been credited.
+pub fn record_purchase_price(
+    env: &Env,
+    asset_id: u64,
+    holder: Address,
+    amount: i128,
+    price: i128,
+) -> Result<(), Error> {
     let store = env.storage().persistent();
-    let key = TokenDataKey::TokenHolder(asset_id, holder);
+    let holder_key = TokenDataKey::TokenHolder(asset_id, holder.clone());
+    let mut ownership = load_ownership(env, asset_id, &holder)?.ok_or(Error::HolderNotFound)?;
+
+    if ownership.balance <= 0 {
+        return Ok(());
+    }
+
+    let prior_balance = math::sub(ownership.balance, amount)?;
+    let prior_cost = math::mul(prior_balance, ownership.average_purchase_price)?;
+    let purchase_cost = math::mul(amount, price)?;
+    let total_cost = math::add(prior_cost, purchase_cost)?;
+
+    ownership.average_purchase_price = total_cost / ownership.balance;
+    store.set(&holder_key, &ownership);
 
-    match store.get::<_, OwnershipRecord>(&key) {
+    Ok(())
+}
+
+/// Get token balance for an address
+pub fn get_token_balance(env: &Env, asset_id: u64, holder: Address) -> Result<i128, Error> {
+    match load_ownership(env, asset_id, &holder)? {
         Some(ownership) => Ok(ownership.balance),
         None => Ok(0),
     }
 }
 
+/// Get the full ownership record for a holder
+pub fn get_ownership_record(
+    env: &Env,
+    asset_id: u64,
+    holder: Address,
+) -> Result<OwnershipRecord, Error> {
+    load_ownership(env, asset_id, &holder)?.ok_or(Error::HolderNotFound)
+}
+
+/// Load a holder's ownership record with token amounts in the current
+/// denomination. Records are rescaled lazily, so corporate actions never
+/// iterate holders; callers that save the record persist the new epoch.
+/// `ownership_percentage` is computed against the current supply on every read.
+pub fn load_ownership(
+    env: &Env,
+    asset_id: u64,
+    holder: &Address,
+) -> Result<Option<OwnershipRecord>, Error> {
+    let stored: Option<OwnershipRecord> =
+        load_scaled_record(env, &TokenDataKey::TokenHolder(asset_id, holder.clone()))?;
+    let Some(mut ownership) = stored else {
+        return Ok(None);
+    };
+
+    let actions = get_corporate_actions(env, asset_id);
+    if ownership.scale_epoch < actions.len() {
+        let epoch = ownership.scale_epoch;
+        ownership.balance = scale_amount(&actions, epoch, ownership.balance)?;
+        ownership.voting_power = scale_amount(&actions, epoch, ownership.voting_power)?;
+        ownership.dividend_entitlement =
+            scale_amount(&actions, epoch, ownership.dividend_entitlement)?;
+        ownership.average_purchase_price =
+            scale_price(&actions, epoch, ownership.average_purchase_price)?;
+        ownership.scale_epoch = actions.len();
+    }
+
+    let total_supply = env
+        .storage()
+        .persistent()
+        .get::<_, TokenizedAsset>(&TokenDataKey::TokenizedAsset(asset_id))
+        .map(|tokenized_asset| tokenized_asset.total_supply)
+        .unwrap_or(0);
+    ownership.ownership_percentage = if total_supply > 0 {
+        math::bps_of(ownership.balance, total_supply)?
+    } else {
+        0
+    };
+    Ok(Some(ownership))
+}
+
+/// Whether a holder must stay listed: it still has tokens, unclaimed
+/// dividends or a freeze that detokenization has to clean up
+fn has_holder_state(
+    env: &Env,
+    asset_id: u64,
+    holder: &Address,
+    ownership: Option<&OwnershipRecord>,
+) -> bool {
+    ownership.is_some_and(|o| o.balance > 0 || o.unclaimed_dividends > 0)
+        || env
+            .storage()
+            .persistent()
+            .has(&enforcement::DataKey::Frozen(asset_id, holder.clone()))
+}
+
+/// Keep `holder` in the holders list exactly while it has holder state and
+/// sync `token_holders_count`. The contract's own escrow is never listed.
+/// Lock and vesting records left behind by a departing holder are dropped.
+/// The caller persists `tokenized_asset`.
+fn update_holders_list(
+    env: &Env,
+    tokenized_asset: &mut TokenizedAsset,
+    holder: &Address,
+    ownership: Option<&OwnershipRecord>,
+) -> Result<(), Error> {
+    if *holder == env.current_contract_address() {
+        return Ok(());
+    }
+    let asset_id = tokenized_asset.asset_id;
+    let listed = has_holder_state(env, asset_id, holder, ownership);
+    let store = env.storage().persistent();
+    let holders_list_key = TokenDataKey::TokenHoldersList(asset_id);
+    let mut holders: Vec<Address> = store
+        .get(&holders_list_key)
+        .ok_or(Error::AssetNotTokenized)?;
+
+    let position = holders.iter().position(|h| h == *holder);
+    match position {
+        None if listed => holders.push_back(holder.clone()),
+        Some(index) if !listed => {
+            holders.remove(index as u32);
+            store.remove(&TokenDataKey::TokenLocks(asset_id, holder.clone()));
+            store.remove(&vesting::DataKey::VestingSchedules(
+                asset_id,
+                holder.clone(),
+            ));
+        }
+        _ => return Ok(()),
+    }
+
+    store.set(&holders_list_key, &holders);
+    tokenized_asset.token_holders_count = holders.len();
+    Ok(())
+}
+
+/// Re-check a holder's place in the holders list after its dividends or
+/// freeze changed
+pub fn sync_holder(env: &Env, asset_id: u64, holder: &Address) -> Result<(), Error> {
+    let store = env.storage().persistent();
+    let key = TokenDataKey::TokenizedAsset(asset_id);
+    let mut tokenized_asset: TokenizedAsset = store.get(&key).ok_or(Error::AssetNotTokenized)?;
+    let ownership = load_ownership(env, asset_id, holder)?;
+    update_holders_list(env, &mut tokenized_asset, holder, ownership.as_ref())?;
+    store.set(&key, &tokenized_asset);
+    Ok(())
+}
+
+/// Page through the cap table in holder-list order
+pub fn get_cap_table(
+    env: &Env,
+    asset_id: u64,
+    start: u32,
+    limit: u32,
+) -> Result<Vec<CapTableEntry>, Error> {
+    let store = env.storage().persistent();
+    let tokenized_asset: TokenizedAsset = store
+        .get(&TokenDataKey::TokenizedAsset(asset_id))
+        .ok_or(Error::AssetNotTokenized)?;
+    let holders = get_token_holders(env, asset_id)?;
+
+    let mut entries = Vec::new(env);
+    let end = start.saturating_add(limit).min(holders.len());
+    for index in start..end {
+        let holder = holders.get_unchecked(index);
+        let ownership = load_ownership(env, asset_id, &holder)?.ok_or(Error::HolderNotFound)?;
+        let ownership_bps = math::bps_of(ownership.balance, tokenized_asset.total_supply)?;
+        entries.push_back(CapTableEntry {
+            holder,
+            balance: ownership.balance,
+            ownership_bps,
+            unclaimed_dividends: ownership.unclaimed_dividends,
+        });
+    }
+
+    Ok(entries)
+}
+
 /// Get all token holders for an asset
 pub fn get_token_holders(env: &Env, asset_id: u64) -> Result<Vec<Address>, Error> {
     let store = env.storage().persistent();
@@ -362,6 +983,275 @@ pub fn is_tokens_locked(env: &Env, asset_id: u64, holder: Address) -> bool {
     }
 }
 
+/// Load a holder's locks with amounts in the current denomination
+fn load_token_locks(env: &Env, asset_id: u64, holder: &Address) -> Result<Vec<TokenLock>, Error> {
+    let locks: Vec<TokenLock> =
+        load_scaled_records(env, &TokenDataKey::TokenLocks(asset_id, holder.clone()))?;
+
+    let actions = get_corporate_actions(env, asset_id);
+    let mut rescaled = Vec::new(env);
+    for mut lock in locks.iter() {
+        lock.amount = scale_amount(&actions, lock.scale_epoch, lock.amount)?;
+        lock.scale_epoch = actions.len();
+        rescaled.push_back(lock);
+    }
+    Ok(rescaled)
+}
+
+fn save_token_locks(env: &Env, asset_id: u64, holder: &Address, locks: &Vec<TokenLock>) {
+    let store = env.storage().persistent();
+    let key = TokenDataKey::TokenLocks(asset_id, holder.clone());
+    if locks.is_empty() {
+        store.remove(&key);
+    } else {
+        store.set(&key, locks);
+    }
+}
+
+/// Adjust the asset-level locked total by `delta`
+fn adjust_locked_total(env: &Env, asset_id: u64, delta: i128) -> Result<(), Error> {
+    let store = env.storage().persistent();
+    let key = TokenDataKey::TokenizedAsset(asset_id);
+    let mut tokenized_asset: TokenizedAsset = store.get(&key).ok_or(Error::AssetNotTokenized)?;
+    tokenized_asset.locked_tokens = math::add(tokenized_asset.locked_tokens, delta)?;
+    store.set(&key, &tokenized_asset);
+    Ok(())
+}
+
+/// Lock part of a holder's balance until `release_at`.
+/// Only the tokenizer can add locks; a holder may carry several at once.
+/// Returns the new lock ID.
+pub fn lock_token_amount(
+    env: &Env,
+    asset_id: u64,
+    holder: Address,
+    amount: i128,
+    release_at: u64,
+    reason: Symbol,
+    caller: Address,
+) -> Result<u32, Error> {
+    let store = env.storage().persistent();
+    let tokenized_asset: TokenizedAsset = store
+        .get(&TokenDataKey::TokenizedAsset(asset_id))
+        .ok_or(Error::AssetNotTokenized)?;
+    if tokenized_asset.tokenizer != caller {
+        return Err(Error::Unauthorized);
+    }
+    if amount <= 0 {
+        return Err(Error::InvalidTokenSupply);
+    }
+    if release_at <= env.ledger().timestamp() {
+        return Err(Error::InvalidTimestamps);
+    }
+
+    place_token_lock(env, asset_id, holder, amount, release_at, reason)
+}
+
+/// Lock part of a holder's unlocked balance for another module (such as a
+/// resting sell order), without the tokenizer check. Returns the new lock ID.
+pub fn place_token_lock(
+    env: &Env,
+    asset_id: u64,
+    holder: Address,
+    amount: i128,
+    release_at: u64,
+    reason: Symbol,
+) -> Result<u32, Error> {
+    let store = env.storage().persistent();
+    release_expired_locks(env, asset_id, holder.clone())?;
+    let balance = get_token_balance(env, asset_id, holder.clone())?;
+    let locked = get_locked_amount(env, asset_id, holder.clone())?;
+    if math::sub(balance, locked)? < amount {
+        return Err(Error::InsufficientLockedTokens);
+    }
+
+    let counter_key = TokenDataKey::TokenLockCounter(asset_id);
+    let lock_id: u32 = store.get(&counter_key).unwrap_or(0) + 1;
+    store.set(&counter_key, &lock_id);
+
+    let mut locks = load_token_locks(env, asset_id, &holder)?;
+    locks.push_back(TokenLock {
+        lock_id,
+        amount,
+        release_at,
+        reason: reason.clone(),
+        created_at: env.ledger().timestamp(),
+        scale_epoch: current_scale_epoch(env, asset_id),
+    });
+    save_token_locks(env, asset_id, &holder, &locks);
+    adjust_locked_total(env, asset_id, amount)?;
+
+    // Emit event: (asset_id, holder, lock_id, amount, release_at, reason)
+    env.events().publish(
+        ("token", "tokens_partially_locked"),
+        (asset_id, holder, lock_id, amount, release_at, reason),
+    );
+
+    Ok(lock_id)
+}
+
+/// Release a single lock before its release time (tokenizer only)
+pub fn release_token_lock(
+    env: &Env,
+    asset_id: u64,
+    holder: Address,
+    lock_id: u32,
+    caller: Address,
+) -> Result<i128, Error> {
+    let tokenized_asset = get_tokenized_asset(env, asset_id)?;
+    if tokenized_asset.tokenizer != caller {
+        return Err(Error::Unauthorized);
+    }
+
+    let mut locks = load_token_locks(env, asset_id, &holder)?;
+    let index = locks
+        .iter()
+        .position(|lock| lock.lock_id == lock_id)
+        .ok_or(Error::HolderNotFound)?;
+    let released = locks.get_unchecked(index as u32).amount;
+    locks.remove(index as u32);
+    save_token_locks(env, asset_id, &holder, &locks);
+    adjust_locked_total(env, asset_id, -released)?;
+
+    // Emit event: (asset_id, holder, lock_id, amount)
+    env.events().publish(
+        ("token", "token_lock_released"),
+        (asset_id, holder, lock_id, released),
+    );
+
+    Ok(released)
+}
+
+/// Release up to `amount` from one of a holder's locks, dropping it once empty.
+/// Returns the amount released, 0 if the lock no longer exists.
+pub fn reduce_token_lock(
+    env: &Env,
+    asset_id: u64,
+    holder: Address,
+    lock_id: u32,
+    amount: i128,
+) -> Result<i128, Error> {
+    let mut locks = load_token_locks(env, asset_id, &holder)?;
+    let Some(index) = locks.iter().position(|lock| lock.lock_id == lock_id) else {
+        return Ok(0);
+    };
+    let mut lock = locks.get_unchecked(index as u32);
+    let released = amount.min(lock.amount);
+    lock.amount = math::sub(lock.amount, released)?;
+    if lock.amount == 0 {
+        locks.remove(index as u32);
+    } else {
+        locks.set(index as u32, lock);
+    }
+    save_token_locks(env, asset_id, &holder, &locks);
+    adjust_locked_total(env, asset_id, -released)?;
+    Ok(released)
+}
+
+/// Shrink a holder's newest locks until they fit within `balance`
+fn clamp_token_locks(
+    env: &Env,
+    asset_id: u64,
+    holder: &Address,
+    balance: i128,
+) -> Result<(), Error> {
+    let locked = get_locked_amount(env, asset_id, holder.clone())?;
+    if locked <= balance {
+        return Ok(());
+    }
+    let mut excess = math::sub(locked, balance)?;
+
+    let mut locks = load_token_locks(env, asset_id, holder)?;
+    let mut released: i128 = 0;
+    while excess > 0 {
+        let Some(mut lock) = locks.pop_back() else {
+            break;
+        };
+        let cut = excess.min(lock.amount);
+        lock.amount = math::sub(lock.amount, cut)?;
+        excess = math::sub(excess, cut)?;
+        released = math::add(released, cut)?;
+        if lock.amount > 0 {
+            locks.push_back(lock);
+            break;
+        }
+    }
+    save_token_locks(env, asset_id, holder, &locks);
+    adjust_locked_total(env, asset_id, -released)
+}
+
+/// Drop a holder's expired locks and update the asset-level locked total.
+/// Permissionless; returns the amount released.
+pub fn release_expired_locks(env: &Env, asset_id: u64, holder: Address) -> Result<i128, Error> {
+    let now = env.ledger().timestamp();
+    let locks = load_token_locks(env, asset_id, &holder)?;
+
+    let mut remaining = Vec::new(env);
+    let mut released: i128 = 0;
+    for lock in locks.iter() {
+        if now >= lock.release_at {
+            released = math::add(released, lock.amount)?;
+        } else {
+            remaining.push_back(lock);
+        }
+    }
+
+    if released > 0 {
+        save_token_locks(env, asset_id, &holder, &remaining);
+        adjust_locked_total(env, asset_id, -released)?;
+
+        // Emit event: (asset_id, holder, amount)
+        env.events().publish(
+            ("token", "expired_locks_released"),
+            (asset_id, holder, released),
+        );
+    }
+
+    Ok(released)
+}
+
+/// Get a holder's partial locks, including expired ones not yet released
+pub fn get_token_locks(env: &Env, asset_id: u64, holder: Address) -> Result<Vec<TokenLock>, Error> {
+    load_token_locks(env, asset_id, &holder)
+}
+
+/// Amount of a holder's balance under partial locks that have not expired.
+/// Read-only: expired locks are skipped here and cleared from storage by the
+/// next call that changes the holder's locks or balance.
+pub fn get_locked_amount(env: &Env, asset_id: u64, holder: Address) -> Result<i128, Error> {
+    let now = env.ledger().timestamp();
+    let mut locked: i128 = 0;
+    for lock in load_token_locks(env, asset_id, &holder)?.iter() {
+        if now < lock.release_at {
+            locked = math::add(locked, lock.amount)?;
+        }
+    }
+    Ok(locked)
+}
+
+/// Tokens a holder must keep: partial locks, unvested grants and freezes
+fn restricted_amount(env: &Env, asset_id: u64, holder: &Address) -> Result<i128, Error> {
+    math::add(
+        math::add(
+            get_locked_amount(env, asset_id, holder.clone())?,
+            vesting::get_unvested_amount(env, asset_id, holder.clone())?,
+        )?,
+        enforcement::get_frozen_amount(env, asset_id, holder.clone())?,
+    )
+}
+
+/// Balance a holder can transfer right now, after whole-balance locks,
+/// partial locks, unvested grants and freezes
+pub fn get_spendable_balance(env: &Env, asset_id: u64, holder: Address) -> Result<i128, Error> {
+    let balance = get_token_balance(env, asset_id, holder.clone())?;
+    if is_tokens_locked(env, asset_id, holder.clone()) {
+        return Ok(0);
+    }
+
+    let restricted = restricted_amount(env, asset_id, &holder)?;
+    Ok(balance.saturating_sub(restricted).max(0))
+}
+
 /// Calculate ownership percentage for a holder (in basis points)
 pub fn calculate_ownership_percentage(
     env: &Env,
@@ -375,15 +1265,14 @@ pub fn calculate_ownership_percentage(
     let tokenized_asset: TokenizedAsset = store.get(&key).ok_or(Error::AssetNotTokenized)?;
 
     // Get holder balance
-    let holder_key = TokenDataKey::TokenHolder(asset_id, holder);
-    let ownership: OwnershipRecord = store.get(&holder_key).ok_or(Error::HolderNotFound)?;
+    let ownership = load_ownership(env, asset_id, &holder)?.ok_or(Error::HolderNotFound)?;
 
     // Calculate percentage: (balance / total_supply) * 10000
     if tokenized_asset.total_supply <= 0 {
         return Ok(0);
     }
 
-    Ok((ownership.balance * 10000) / tokenized_asset.total_supply)
+    math::bps_of(ownership.balance, tokenized_asset.total_supply)
 }
 
 /// Get tokenized asset details
@@ -395,7 +1284,6 @@ pub fn get_tokenized_asset(env: &Env, asset_id: u64) -> Result<TokenizedAsset, E
 }
 
 /// Get token metadata
-#[allow(dead_code)]
 pub fn get_token_metadata(env: &Env, asset_id: u64) -> Result<TokenMetadata, Error> {
     let store = env.storage().persistent();
     let key = TokenDataKey::TokenMetadata(asset_id);
@@ -403,23 +1291,260 @@ pub fn get_token_metadata(env: &Env, asset_id: u64) -> Result<TokenMetadata, Err
     store.get(&key).ok_or(Error::AssetNotTokenized)
 }
 
-/// Update asset valuation
-pub fn update_valuation(env: &Env, asset_id: u64, new_valuation: i128) -> Result<(), Error> {
-    if new_valuation <= 0 {
+/// Update asset valuation.
+/// The new value must be covered by a signed attestation from a registered appraiser.
+pub fn update_valuation(
+    env: &Env,
+    asset_id: u64,
+    new_valuation: i128,
+    method: ValuationMethod,
+    attestor: BytesN<32>,
+    timestamp: u64,
+    signature: BytesN<64>,
+) -> Result<(), Error> {
+    if new_valuation <= 0 || method == ValuationMethod::OraclePrice {
         return Err(Error::InvalidValuation);
     }
 
     let store = env.storage().persistent();
     let key = TokenDataKey::TokenizedAsset(asset_id);
+    if !store.has(&key) {
+        return Err(Error::AssetNotTokenized);
+    }
 
-    let mut tokenized_asset: TokenizedAsset = store.get(&key).ok_or(Error::AssetNotTokenized)?;
+    attestation::verify_and_record(
+        env,
+        asset_id,
+        AttestationField::Valuation,
+        attestation::valuation_hash(env, new_valuation),
+        timestamp,
+        attestor.clone(),
+        signature,
+    )?;
+
+    valuation::apply_valuation(
+        env,
+        asset_id,
+        new_valuation,
+        ValuationSource::Attestor(attestor),
+        method,
+    )
+}
 
-    tokenized_asset.valuation = new_valuation;
-    store.set(&key, &tokenized_asset);
+/// Record an attested document hash (legal docs or valuation report) in the token metadata
+pub fn set_document_hash(
+    env: &Env,
+    asset_id: u64,
+    field: AttestationField,
+    doc_hash: BytesN<32>,
+    attestor: BytesN<32>,
+    timestamp: u64,
+    signature: BytesN<64>,
+) -> Result<(), Error> {
+    if field == AttestationField::Valuation {
+        return Err(Error::InvalidAttestation);
+    }
 
-    // Emit event: (asset_id, new_valuation)
+    let store = env.storage().persistent();
+    let metadata_key = TokenDataKey::TokenMetadata(asset_id);
+    let mut metadata: TokenMetadata = store.get(&metadata_key).ok_or(Error::AssetNotTokenized)?;
+
+    attestation::verify_and_record(
+        env,
+        asset_id,
+        field,
+        doc_hash.clone(),
+        timestamp,
+        attestor,
+        signature,
+    )?;
+
+    if field == AttestationField::LegalDocs {
+        metadata.legal_docs_hash = Some(doc_hash.clone());
+    } else {
+        metadata.valuation_report_hash = Some(doc_hash.clone());
+    }
+    store.set(&metadata_key, &metadata);
+
+    // Emit event: (asset_id, field, doc_hash)
     env.events()
-        .publish(("token", "valuation_updated"), (asset_id, new_valuation));
+        .publish(("token", "document_attested"), (asset_id, field, doc_hash));
 
     Ok(())
 }
+
+/// Split or consolidation history for an asset, oldest first
+pub fn get_corporate_actions(env: &Env, asset_id: u64) -> Vec<CorporateAction> {
+    env.storage()
+        .persistent()
+        .get(&TokenDataKey::CorporateActions(asset_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Number of corporate actions executed on an asset. Stored token amounts
+/// carry the epoch they were last rescaled at.
+pub fn current_scale_epoch(env: &Env, asset_id: u64) -> u32 {
+    get_corporate_actions(env, asset_id).len()
+}
+
+/// Records stored before corporate actions existed have no `scale_epoch`
+/// field; they were written in the original denomination, i.e. epoch 0.
+fn with_scale_epoch(env: &Env, mut record: Map<Symbol, Val>) -> Map<Symbol, Val> {
+    let field = Symbol::new(env, "scale_epoch");
+    if !record.contains_key(field.clone()) {
+        record.set(field, 0u32.into_val(env));
+    }
+    record
+}
+
+/// Load a record that carries a `scale_epoch`, migrating one stored in the
+/// earlier shape without it
+pub fn load_scaled_record<T: TryFromVal<Env, Val>>(
+    env: &Env,
+    key: &impl IntoVal<Env, Val>,
+) -> Result<Option<T>, Error> {
+    let stored: Option<Map<Symbol, Val>> = env.storage().persistent().get(key);
+    match stored {
+        Some(record) => T::try_from_val(env, &with_scale_epoch(env, record).to_val())
+            .map(Some)
+            .map_err(|_| Error::InvalidTokenSupply),
+        None => Ok(None),
+    }
+}
+
+/// List form of `load_scaled_record`; a missing list loads as empty
+pub fn load_scaled_records<T: TryFromVal<Env, Val> + IntoVal<Env, Val>>(
+    env: &Env,
+    key: &impl IntoVal<Env, Val>,
+) -> Result<Vec<T>, Error> {
+    let stored: Vec<Map<Symbol, Val>> = env
+        .storage()
+        .persistent()
+        .get(key)
+        .unwrap_or_else(|| Vec::new(env));
+    let mut records = Vec::new(env);
+    for record in stored.iter() {
+        records.push_back(
+            T::try_from_val(env, &with_scale_epoch(env, record).to_val())
+                .map_err(|_| Error::InvalidTokenSupply)?,
+        );
+    }
+    Ok(records)
+}
+
+/// Bring a token amount recorded at `epoch` into the current denomination,
+/// rounding down at each action
+pub fn scale_amount(
+    actions: &Vec<CorporateAction>,
+    epoch: u32,
+    amount: i128,
+) -> Result<i128, Error> {
+    let mut scaled = amount;
+    for index in epoch..actions.len() {
+        let action = actions.get_unchecked(index);
+        scaled = scale_by_ratio(scaled, action.ratio_from, action.ratio_to)?;
+    }
+    Ok(scaled)
+}
+
+/// Bring a per-token price recorded at `epoch` into the current denomination
+pub fn scale_price(actions: &Vec<CorporateAction>, epoch: u32, price: i128) -> Result<i128, Error> {
+    let mut scaled = price;
+    for index in epoch..actions.len() {
+        let action = actions.get_unchecked(index);
+        scaled = scale_by_ratio(scaled, action.ratio_to, action.ratio_from)?;
+    }
+    Ok(scaled)
+}
+
+/// `amount` re-denominated so that every `ratio_from` tokens become `ratio_to`
+pub fn scale_by_ratio(amount: i128, ratio_from: u32, ratio_to: u32) -> Result<i128, Error> {
+    math::mul_div(amount, ratio_to as i128, ratio_from as i128, Rounding::Down)
+}
+
+/// Re-denominate an asset so every `ratio_from` tokens become `ratio_to`
+/// (1:10 is a split, 10:1 a consolidation). Tokenizer only.
+///
+/// Asset-level totals, compliance limits, open orders and any active offering
+/// are rescaled now. Holder balances, locks, vesting grants, freezes and vote
+/// tallies are rescaled lazily the next time they are read, so holders are
+/// never iterated. Consolidations round each holder and lock down while the
+/// asset-level totals are scaled once; the difference is rounding dust of
+/// under one base unit per holder that stays in the asset's supply and locked
+/// total without belonging to any holder. Unclaimed dividends are payment
+/// amounts and are left unchanged.
+pub fn redenominate_tokens(
+    env: &Env,
+    asset_id: u64,
+    ratio_from: u32,
+    ratio_to: u32,
+    caller: Address,
+) -> Result<CorporateAction, Error> {
+    let store = env.storage().persistent();
+    let key = TokenDataKey::TokenizedAsset(asset_id);
+    let mut tokenized_asset: TokenizedAsset = store.get(&key).ok_or(Error::AssetNotTokenized)?;
+    if tokenized_asset.tokenizer != caller {
+        return Err(Error::Unauthorized);
+    }
+    if ratio_from == 0 || ratio_to == 0 || ratio_from == ratio_to {
+        return Err(Error::InvalidTokenSupply);
+    }
+
+    let scale = |amount: i128| scale_by_ratio(amount, ratio_from, ratio_to);
+    let supply_before = tokenized_asset.total_supply;
+    let supply_after = scale(supply_before)?;
+    if supply_after <= 0 {
+        return Err(Error::InvalidTokenSupply);
+    }
+
+    tokenized_asset.total_supply = supply_after;
+    tokenized_asset.tokens_in_circulation = scale(tokenized_asset.tokens_in_circulation)?;
+    tokenized_asset.locked_tokens = scale(tokenized_asset.locked_tokens)?;
+    tokenized_asset.min_voting_threshold = scale(tokenized_asset.min_voting_threshold)?;
+    store.set(&key, &tokenized_asset);
+
+    let policy = match get_minting_policy(env, asset_id) {
+        MintingPolicy::Capped(max_supply) => Some(MintingPolicy::Capped(scale(max_supply)?)),
+        MintingPolicy::GovernanceApproved(governed) => {
+            Some(MintingPolicy::GovernanceApproved(GovernedMinting {
+                max_supply: scale(governed.max_supply)?,
+                threshold: scale(governed.threshold)?,
+            }))
+        }
+        _ => None,
+    };
+    if let Some(policy) = policy {
+        store.set(&TokenDataKey::MintPolicy(asset_id), &policy);
+    }
+
+    compliance::rescale_modules(env, asset_id, ratio_from, ratio_to)?;
+    marketplace::rescale_open_orders(env, asset_id, ratio_from, ratio_to)?;
+    offering::rescale_offering(env, asset_id, ratio_from, ratio_to)?;
+
+    let mut history = get_corporate_actions(env, asset_id);
+    let action = CorporateAction {
+        action_id: history.len() + 1,
+        kind: if ratio_to > ratio_from {
+            CorporateActionKind::Split
+        } else {
+            CorporateActionKind::Consolidation
+        },
+        ratio_from,
+        ratio_to,
+        supply_before,
+        supply_after,
+        executed_by: caller,
+        executed_at: env.ledger().timestamp(),
+    };
+    history.push_back(action.clone());
+    store.set(&TokenDataKey::CorporateActions(asset_id), &history);
+
+    // Emit event: (asset_id, action_id, ratio_from, ratio_to)
+    env.events().publish(
+        ("token", "tokens_redenominated"),
+        (asset_id, action.action_id, ratio_from, ratio_to),
+    );
+    emit_supply_changed(env, asset_id, supply_before, supply_after);
+
+    Ok(action)
+}
@@ -1,6 +1,7 @@
 use crate::error::Error;
-use crate::types::{TokenDataKey, TransferRestriction};
-use soroban_sdk::{Address, Env, Vec};
+use crate::identity;
+use crate::types::{TokenDataKey, TokenMetadata, TokenizedAsset, TransferRestriction};
+use soroban_sdk::{Address, Env, String, Vec};
 
 /// Set transfer restrictions for an asset
 pub fn set_transfer_restriction(
@@ -83,11 +84,29 @@ pub fn get_whitelist(env: &Env, asset_id: u64) -> Result<Vec<Address>, Error> {
     Ok(store.get(&key).flatten().unwrap_or_else(|| Vec::new(env)))
 }
 
-/// Validate if a transfer is allowed based on restrictions
+/// Validate if a transfer is allowed based on restrictions.
+/// The whitelist applies to the receiver; accreditation and jurisdiction rules
+/// apply to both parties. The tokenizer and the contract's own escrow are exempt.
 pub fn validate_transfer(
     env: &Env,
     asset_id: u64,
-    _from: Address,
+    from: Address,
+    to: Address,
+) -> Result<bool, Error> {
+    validate_parties(env, asset_id, Some(from), to)
+}
+
+/// Validate only the receiving side of a transfer: the whitelist and the
+/// receiver's accreditation and jurisdiction. Used where the sender's own
+/// standing is deliberately bypassed, such as a forced recovery.
+pub fn validate_receiver(env: &Env, asset_id: u64, to: Address) -> Result<bool, Error> {
+    validate_parties(env, asset_id, None, to)
+}
+
+fn validate_parties(
+    env: &Env,
+    asset_id: u64,
+    from: Option<Address>,
     to: Address,
 ) -> Result<bool, Error> {
     let store = env.storage().persistent();
@@ -106,27 +125,95 @@ pub fn validate_transfer(
         }
     }
 
-    let restriction_key = TokenDataKey::TransferRestriction(asset_id);
+    let restriction: Option<TransferRestriction> = store
+        .get(&TokenDataKey::TransferRestriction(asset_id))
+        .flatten();
+    let blocked_countries: Vec<String> = store
+        .get::<_, TokenMetadata>(&TokenDataKey::TokenMetadata(asset_id))
+        .map(|metadata| metadata.geographic_restrictions)
+        .unwrap_or_else(|| Vec::new(env));
 
-    // If no restrictions config, allow transfer
-    let restriction: TransferRestriction = match store.get(&restriction_key) {
-        Some(Some(r)) => r,
-        _ => {
-            return Ok(true);
-        }
+    // If no rules are configured, allow transfer
+    let (require_accredited, allowed_countries) = match restriction {
+        Some(r) => (r.require_accredited, r.geographic_allowed),
+        None => (false, Vec::new(env)),
     };
+    if !require_accredited && allowed_countries.is_empty() && blocked_countries.is_empty() {
+        return Ok(true);
+    }
 
-    // If accredited investor required, check whitelist as MVP proxy
-    if restriction.require_accredited {
-        let is_listed = whitelist.iter().any(|a| a == to);
-        if !is_listed {
+    let tokenizer = store
+        .get::<_, TokenizedAsset>(&TokenDataKey::TokenizedAsset(asset_id))
+        .map(|asset| asset.tokenizer);
+    for party in from.into_iter().chain([to]) {
+        if Some(party.clone()) == tokenizer || party == env.current_contract_address() {
+            continue;
+        }
+
+        let claims = identity::get_valid_identity(env, party);
+        if require_accredited && !claims.as_ref().is_some_and(|c| c.accredited) {
             return Err(Error::AccreditedInvestorRequired);
         }
+        if !allowed_countries.is_empty() || !blocked_countries.is_empty() {
+            let jurisdiction = claims
+                .map(|c| c.jurisdiction)
+                .ok_or(Error::GeographicRestriction)?;
+            if (!allowed_countries.is_empty() && !allowed_countries.contains(&jurisdiction))
+                || blocked_countries.contains(&jurisdiction)
+            {
+                return Err(Error::GeographicRestriction);
+            }
+        }
     }
 
     Ok(true)
 }
 
+/// Replace the list of jurisdictions investors must belong to.
+/// An empty list removes the allowlist.
+pub fn set_allowed_jurisdictions(
+    env: &Env,
+    asset_id: u64,
+    countries: Vec<String>,
+) -> Result<(), Error> {
+    let store = env.storage().persistent();
+    let key = TokenDataKey::TransferRestriction(asset_id);
+    let mut restriction: TransferRestriction =
+        store.get(&key).flatten().unwrap_or(TransferRestriction {
+            require_accredited: false,
+            geographic_allowed: Vec::new(env),
+        });
+    restriction.geographic_allowed = countries.clone();
+    store.set(&key, &restriction);
+
+    // Emit event: (asset_id, countries)
+    env.events()
+        .publish(("transfer", "jurisdictions_allowed"), (asset_id, countries));
+
+    Ok(())
+}
+
+/// Replace the list of jurisdictions barred from holding the asset
+pub fn set_restricted_jurisdictions(
+    env: &Env,
+    asset_id: u64,
+    countries: Vec<String>,
+) -> Result<(), Error> {
+    let store = env.storage().persistent();
+    let key = TokenDataKey::TokenMetadata(asset_id);
+    let mut metadata: TokenMetadata = store.get(&key).ok_or(Error::AssetNotTokenized)?;
+    metadata.geographic_restrictions = countries.clone();
+    store.set(&key, &metadata);
+
+    // Emit event: (asset_id, countries)
+    env.events().publish(
+        ("transfer", "jurisdictions_restricted"),
+        (asset_id, countries),
+    );
+
+    Ok(())
+}
+
 /// Check if transfer restrictions are enabled for an asset
 #[allow(dead_code)]
 pub fn has_transfer_restrictions(env: &Env, asset_id: u64) -> Result<bool, Error> {
@@ -1,5 +1,5 @@
 #![allow(clippy::upper_case_acronyms)]
-use soroban_sdk::{contracttype, Address, BytesN, String, Vec};
+use soroban_sdk::{contracttype, Address, BytesN, String, Symbol, Vec};
 
 /// Represents the fundamental type of asset being managed
 /// Distinguishes between physical and digital assets for different handling requirements
@@ -89,9 +89,13 @@ pub enum TokenDataKey {
     TokenHoldersList(u64),
     /// Stores lock timestamp for (asset_id, holder_address)
     TokenLockedUntil(u64, Address),
+    /// Stores Vec<TokenLock> of amount-based locks for (asset_id, holder_address)
+    TokenLocks(u64, Address),
+    /// Stores the last lock ID (u32) issued for asset_id
+    TokenLockCounter(u64),
     /// Stores vote record for (asset_id, proposal_id, voter_address)
     VoteRecord(u64, u64, Address),
-    /// Stores vote tally (i128) for (asset_id, proposal_id)
+    /// Stores VoteSnapshot for (asset_id, proposal_id)
     VoteTally(u64, u64),
     /// Stores TransferRestriction for asset_id
     TransferRestriction(u64),
@@ -103,6 +107,20 @@ pub enum TokenDataKey {
     DetokenizationProposal(u64),
     /// Stores TokenMetadata for asset_id
     TokenMetadata(u64),
+    /// Stores MintingPolicy for asset_id
+    MintPolicy(u64),
+    /// Stores MintApproval binding proposal (asset_id, proposal_id) to a governed mint
+    MintApproval(u64, u64),
+    /// Stores Vec<CorporateAction> history for asset_id
+    CorporateActions(u64),
+    /// Stores UnapprovedMints for asset_id since its last vote-approved mint
+    UnapprovedMints(u64),
+    /// Stores the number of balance snapshots taken for asset_id
+    SnapshotCounter(u64),
+    /// Stores Vec<BalanceSnapshot> of (asset_id, holder) balances at past snapshots
+    BalanceSnapshots(u64, Address),
+    /// Stores the registry asset ID (BytesN<32>) that asset_id tokenizes
+    RegistryAsset(u64),
 }
 
 /// Represents a tokenized asset on-chain
@@ -117,13 +135,13 @@ pub struct TokenizedAsset {
     pub symbol: String,
     /// Number of decimals for fractional ownership
     pub decimals: u32,
-    /// Total tokens currently locked (non-transferable)
+    /// Total tokens under partial locks across all holders
     pub locked_tokens: i128,
     /// Tokenizer / asset owner
     pub tokenizer: Address,
-    /// Asset valuation (in stroops)
+    /// Asset valuation (in stroops), 0 until the first attested or oracle valuation
     pub valuation: i128,
-    /// Number of unique token holders
+    /// Number of holders with a non-zero balance
     pub token_holders_count: u32,
     /// Tokens currently in circulation (not burned)
     pub tokens_in_circulation: i128,
@@ -175,6 +193,121 @@ pub struct OwnershipRecord {
     pub unclaimed_dividends: i128,
     /// Ownership percentage in basis points (e.g., 5000 = 50%)
     pub ownership_percentage: i128,
+    /// Number of corporate actions already applied to the token amounts above
+    pub scale_epoch: u32,
+}
+
+/// One row of a tokenized asset's cap table
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CapTableEntry {
+    pub holder: Address,
+    pub balance: i128,
+    /// Ownership in basis points of total supply
+    pub ownership_bps: i128,
+    pub unclaimed_dividends: i128,
+}
+
+/// Amount-based lock on part of a holder's balance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenLock {
+    pub lock_id: u32,
+    /// Tokens that cannot be transferred while the lock is active
+    pub amount: i128,
+    /// Timestamp from which the tokens become spendable again
+    pub release_at: u64,
+    pub reason: Symbol,
+    pub created_at: u64,
+    /// Number of corporate actions already applied to `amount`
+    pub scale_epoch: u32,
+}
+
+/// Governed mint a proposal asks holders to approve, in token units as of `scale_epoch`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MintApproval {
+    pub amount: i128,
+    /// Set once the approved mint has been made
+    pub used: bool,
+    /// Balance snapshot that votes on the proposal are weighted by
+    pub snapshot_id: u32,
+    /// Supply outside the tokenizer and contract escrow when proposed; the
+    /// vote must pass against this rather than the total supply
+    pub eligible_supply: i128,
+    pub scale_epoch: u32,
+}
+
+/// A holder's balance when `snapshot_id` was taken, in token units as of
+/// `scale_epoch`. Written the first time the balance changes afterwards.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BalanceSnapshot {
+    pub snapshot_id: u32,
+    pub balance: i128,
+    pub scale_epoch: u32,
+}
+
+/// Total minted without a vote since the last approved mint, in token units
+/// as of `scale_epoch`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnapprovedMints {
+    pub amount: i128,
+    pub scale_epoch: u32,
+}
+
+/// Running vote tally for a proposal, in token units as of `scale_epoch`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteSnapshot {
+    pub tally: i128,
+    pub scale_epoch: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CorporateActionKind {
+    Split,
+    Consolidation,
+}
+
+/// A re-denomination of an asset's tokens: every `ratio_from` tokens became
+/// `ratio_to` tokens
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CorporateAction {
+    pub action_id: u32,
+    pub kind: CorporateActionKind,
+    pub ratio_from: u32,
+    pub ratio_to: u32,
+    pub supply_before: i128,
+    pub supply_after: i128,
+    pub executed_by: Address,
+    pub executed_at: u64,
+}
+
+/// Controls whether and how far the tokenizer can grow the supply after issuance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MintingPolicy {
+    /// No limit beyond the tokenizer's authority (default)
+    Unrestricted,
+    /// Supply can never grow. Cannot be changed once set.
+    FixedSupply,
+    /// Supply can grow up to the given maximum
+    Capped(i128),
+    /// Supply can grow up to `max_supply`; once mints since the last approved
+    /// mint would total more than `threshold`, the next mint needs a passed
+    /// holder vote on its amount
+    GovernanceApproved(GovernedMinting),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernedMinting {
+    pub max_supply: i128,
+    pub threshold: i128,
 }
 
 /// Transfer restrictions for tokens
@@ -0,0 +1,276 @@
+use soroban_sdk::{contractclient, contracttype, Address, BytesN, Env, Symbol, Vec};
+
+use crate::error::Error;
+use crate::math::{self, Rounding};
+use crate::tokenization;
+use crate::types::{TokenDataKey, TokenizedAsset};
+
+/// Decimals used for `TokenizedAsset.valuation` (stroops)
+pub const VALUATION_DECIMALS: u32 = 7;
+
+// ─── Types ────────────────────────────────────────────────────────────────────
+
+/// Asset identifier understood by a SEP-40 price oracle
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OracleAsset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+/// Price quote returned by a SEP-40 price oracle
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// SEP-40 price feed interface used to pull valuations
+#[allow(dead_code)]
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracle {
+    fn lastprice(env: Env, asset: OracleAsset) -> Option<PriceData>;
+    fn decimals(env: Env) -> u32;
+}
+
+/// Price oracle configured for a tokenized asset.
+/// The oracle is expected to quote the whole asset, not a single token.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleConfig {
+    pub oracle: Address,
+    pub asset: OracleAsset,
+    /// Maximum age (in seconds) of a quote accepted as a valuation
+    pub max_age: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ValuationSource {
+    /// Signed off-chain by a registered appraiser (ed25519 public key)
+    Attestor(BytesN<32>),
+    /// Pulled from a SEP-40 price oracle
+    Oracle(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ValuationMethod {
+    MarketComparison,
+    IncomeApproach,
+    CostApproach,
+    OraclePrice,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValuationRecord {
+    pub valuation: i128,
+    pub timestamp: u64,
+    pub source: ValuationSource,
+    pub method: ValuationMethod,
+}
+
+// ─── Storage Keys ─────────────────────────────────────────────────────────────
+
+#[contracttype]
+pub enum DataKey {
+    ValuationHistory(u64),
+    PriceOracle(u64),
+}
+
+// ─── Internal helpers ─────────────────────────────────────────────────────────
+
+fn load_tokenized_asset(env: &Env, asset_id: u64) -> Result<TokenizedAsset, Error> {
+    env.storage()
+        .persistent()
+        .get(&TokenDataKey::TokenizedAsset(asset_id))
+        .ok_or(Error::AssetNotTokenized)
+}
+
+/// Rescale an oracle price from `decimals` to `VALUATION_DECIMALS`
+fn normalize_price(price: i128, decimals: u32) -> Result<i128, Error> {
+    if decimals >= VALUATION_DECIMALS {
+        Ok(price / math::pow10(decimals - VALUATION_DECIMALS)?)
+    } else {
+        math::mul(price, math::pow10(VALUATION_DECIMALS - decimals)?)
+    }
+}
+
+/// Set the asset valuation and append it to the valuation history
+pub fn apply_valuation(
+    env: &Env,
+    asset_id: u64,
+    new_valuation: i128,
+    source: ValuationSource,
+    method: ValuationMethod,
+) -> Result<(), Error> {
+    if new_valuation <= 0 {
+        return Err(Error::InvalidValuation);
+    }
+
+    let store = env.storage().persistent();
+    let key = TokenDataKey::TokenizedAsset(asset_id);
+    let mut tokenized_asset: TokenizedAsset = store.get(&key).ok_or(Error::AssetNotTokenized)?;
+
+    tokenized_asset.valuation = new_valuation;
+    store.set(&key, &tokenized_asset);
+
+    let history_key = DataKey::ValuationHistory(asset_id);
+    let mut history: Vec<ValuationRecord> =
+        store.get(&history_key).unwrap_or_else(|| Vec::new(env));
+    history.push_back(ValuationRecord {
+        valuation: new_valuation,
+        timestamp: env.ledger().timestamp(),
+        source,
+        method,
+    });
+    store.set(&history_key, &history);
+
+    // Emit event: (asset_id, new_valuation)
+    env.events()
+        .publish(("token", "valuation_updated"), (asset_id, new_valuation));
+
+    Ok(())
+}
+
+// ─── Public functions (called from lib.rs) ────────────────────────────────────
+
+/// Configure the price oracle an asset's valuation can be pulled from
+pub fn set_price_oracle(env: &Env, asset_id: u64, config: OracleConfig) -> Result<(), Error> {
+    load_tokenized_asset(env, asset_id)?;
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::PriceOracle(asset_id), &config);
+
+    // Emit event: (asset_id, oracle)
+    env.events()
+        .publish(("token", "oracle_set"), (asset_id, config.oracle));
+
+    Ok(())
+}
+
+pub fn get_price_oracle(env: &Env, asset_id: u64) -> Option<OracleConfig> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PriceOracle(asset_id))
+}
+
+/// Pull the latest quote from the configured oracle and apply it as the valuation
+pub fn update_valuation_from_oracle(env: &Env, asset_id: u64) -> Result<i128, Error> {
+    let config = get_price_oracle(env, asset_id).ok_or(Error::InvalidValuation)?;
+
+    let client = PriceOracleClient::new(env, &config.oracle);
+    let quote = client
+        .lastprice(&config.asset)
+        .ok_or(Error::InvalidValuation)?;
+
+    // Reject stale and future-dated quotes
+    let now = env.ledger().timestamp();
+    if quote.timestamp > now || now - quote.timestamp > config.max_age {
+        return Err(Error::InvalidValuation);
+    }
+
+    let valuation = normalize_price(quote.price, client.decimals())?;
+    apply_valuation(
+        env,
+        asset_id,
+        valuation,
+        ValuationSource::Oracle(config.oracle),
+        ValuationMethod::OraclePrice,
+    )?;
+
+    Ok(valuation)
+}
+
+/// Get the valuation history for an asset, oldest first
+pub fn get_valuation_history(env: &Env, asset_id: u64) -> Vec<ValuationRecord> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ValuationHistory(asset_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Price of one whole token (10^decimals base units), in valuation units
+pub fn get_price_per_token(env: &Env, asset_id: u64) -> Result<i128, Error> {
+    let tokenized_asset = load_tokenized_asset(env, asset_id)?;
+    if tokenized_asset.total_supply <= 0 {
+        return Ok(0);
+    }
+
+    math::mul_div(
+        tokenized_asset.valuation,
+        math::pow10(tokenized_asset.decimals)?,
+        tokenized_asset.total_supply,
+        Rounding::Down,
+    )
+}
+
+/// Net asset value of a holder's position, in valuation units
+pub fn get_holder_nav(env: &Env, asset_id: u64, holder: Address) -> Result<i128, Error> {
+    let tokenized_asset = load_tokenized_asset(env, asset_id)?;
+    if tokenized_asset.total_supply <= 0 {
+        return Ok(0);
+    }
+
+    let balance = tokenization::get_token_balance(env, asset_id, holder)?;
+
+    math::mul_div(
+        balance,
+        tokenized_asset.valuation,
+        tokenized_asset.total_supply,
+        Rounding::Down,
+    )
+}
+
+/// Time-weighted average valuation over `[from, to]`.
+/// Each history entry applies from its timestamp until the next one; time before
+/// the first recorded valuation is excluded from the average.
+pub fn get_time_weighted_valuation(
+    env: &Env,
+    asset_id: u64,
+    from: u64,
+    to: u64,
+) -> Result<i128, Error> {
+    if to < from {
+        return Err(Error::InvalidTimestamps);
+    }
+    load_tokenized_asset(env, asset_id)?;
+
+    let history = get_valuation_history(env, asset_id);
+    let mut weighted_sum: i128 = 0;
+    let mut covered: u64 = 0;
+    let mut in_effect: Option<i128> = None;
+
+    for (i, record) in history.iter().enumerate() {
+        let next_start = history
+            .get(i as u32 + 1)
+            .map(|next| next.timestamp)
+            .unwrap_or(u64::MAX);
+
+        if record.timestamp <= from {
+            in_effect = Some(record.valuation);
+        }
+
+        let start = record.timestamp.max(from);
+        let end = next_start.min(to);
+        if end > start {
+            let span = end - start;
+            weighted_sum = record
+                .valuation
+                .checked_mul(span as i128)
+                .and_then(|v| weighted_sum.checked_add(v))
+                .ok_or(Error::MathOverflow)?;
+            covered += span;
+        }
+    }
+
+    if covered == 0 {
+        // Zero-length window: the valuation in effect at `from`, if any
+        return in_effect.ok_or(Error::InvalidValuation);
+    }
+
+    Ok(weighted_sum / covered as i128)
+}
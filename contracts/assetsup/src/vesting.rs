@@ -0,0 +1,255 @@
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+use crate::error::Error;
+use crate::math::{self, Rounding};
+use crate::tokenization;
+use crate::types::{TokenDataKey, TokenizedAsset};
+
+// ─── Types ────────────────────────────────────────────────────────────────────
+
+/// Cliff + linear vesting grant. Nothing vests before `start + cliff`; the grant
+/// then vests linearly from `start` and is fully vested at `start + duration`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingSchedule {
+    pub total_amount: i128,
+    pub start: u64,
+    /// Cliff length in seconds after `start`
+    pub cliff: u64,
+    /// Total vesting length in seconds after `start`
+    pub duration: u64,
+    pub revocable: bool,
+    pub revoked: bool,
+    pub created_at: u64,
+    /// Number of corporate actions already applied to `total_amount`
+    pub scale_epoch: u32,
+}
+
+// ─── Storage Keys ─────────────────────────────────────────────────────────────
+
+#[contracttype]
+pub enum DataKey {
+    VestingSchedules(u64, Address),
+}
+
+// ─── Internal helpers ─────────────────────────────────────────────────────────
+
+/// Load a holder's schedules with amounts in the current denomination
+fn load_schedules(
+    env: &Env,
+    asset_id: u64,
+    holder: &Address,
+) -> Result<Vec<VestingSchedule>, Error> {
+    let schedules: Vec<VestingSchedule> = tokenization::load_scaled_records(
+        env,
+        &DataKey::VestingSchedules(asset_id, holder.clone()),
+    )?;
+
+    let actions = tokenization::get_corporate_actions(env, asset_id);
+    let mut rescaled = Vec::new(env);
+    for mut schedule in schedules.iter() {
+        schedule.total_amount =
+            tokenization::scale_amount(&actions, schedule.scale_epoch, schedule.total_amount)?;
+        schedule.scale_epoch = actions.len();
+        rescaled.push_back(schedule);
+    }
+    Ok(rescaled)
+}
+
+fn save_schedules(env: &Env, asset_id: u64, holder: &Address, schedules: &Vec<VestingSchedule>) {
+    env.storage().persistent().set(
+        &DataKey::VestingSchedules(asset_id, holder.clone()),
+        schedules,
+    );
+}
+
+/// Amount of a single schedule vested at `now`
+fn vested_at(schedule: &VestingSchedule, now: u64) -> Result<i128, Error> {
+    if schedule.revoked || now >= schedule.start.saturating_add(schedule.duration) {
+        return Ok(schedule.total_amount);
+    }
+    if now < schedule.start.saturating_add(schedule.cliff) {
+        return Ok(0);
+    }
+
+    let elapsed = (now - schedule.start) as i128;
+    math::mul_div(
+        schedule.total_amount,
+        elapsed,
+        schedule.duration as i128,
+        Rounding::Down,
+    )
+}
+
+fn load_tokenized_asset(env: &Env, asset_id: u64) -> Result<TokenizedAsset, Error> {
+    env.storage()
+        .persistent()
+        .get(&TokenDataKey::TokenizedAsset(asset_id))
+        .ok_or(Error::AssetNotTokenized)
+}
+
+// ─── Public functions (called from lib.rs) ────────────────────────────────────
+
+/// Grant vesting tokens from the tokenizer to a holder.
+/// The tokens are credited immediately but only the vested portion is transferable.
+pub fn create_vesting_schedule(
+    env: &Env,
+    asset_id: u64,
+    holder: Address,
+    amount: i128,
+    start: u64,
+    cliff: u64,
+    duration: u64,
+    revocable: bool,
+    caller: Address,
+) -> Result<u32, Error> {
+    let tokenized_asset = load_tokenized_asset(env, asset_id)?;
+    if tokenized_asset.tokenizer != caller {
+        return Err(Error::Unauthorized);
+    }
+    if holder == caller {
+        return Err(Error::Unauthorized);
+    }
+    if amount <= 0 {
+        return Err(Error::InvalidTokenSupply);
+    }
+    if duration == 0 || cliff > duration || start.checked_add(duration).is_none() {
+        return Err(Error::InvalidTimestamps);
+    }
+
+    tokenization::transfer_tokens(env, asset_id, caller, holder.clone(), amount)?;
+
+    let mut schedules = load_schedules(env, asset_id, &holder)?;
+    schedules.push_back(VestingSchedule {
+        total_amount: amount,
+        start,
+        cliff,
+        duration,
+        revocable,
+        revoked: false,
+        created_at: env.ledger().timestamp(),
+        scale_epoch: tokenization::current_scale_epoch(env, asset_id),
+    });
+    save_schedules(env, asset_id, &holder, &schedules);
+    let schedule_index = schedules.len() - 1;
+
+    // Emit event: (asset_id, holder, schedule_index, amount)
+    env.events().publish(
+        ("vesting", "schedule_created"),
+        (asset_id, holder, schedule_index, amount),
+    );
+
+    Ok(schedule_index)
+}
+
+/// Revoke a revocable schedule, returning its unvested tokens to the tokenizer
+pub fn revoke_vesting(
+    env: &Env,
+    asset_id: u64,
+    holder: Address,
+    schedule_index: u32,
+    caller: Address,
+) -> Result<i128, Error> {
+    let tokenized_asset = load_tokenized_asset(env, asset_id)?;
+    if tokenized_asset.tokenizer != caller {
+        return Err(Error::Unauthorized);
+    }
+
+    let mut schedules = load_schedules(env, asset_id, &holder)?;
+    let mut schedule = schedules.get(schedule_index).ok_or(Error::HolderNotFound)?;
+    if !schedule.revocable || schedule.revoked {
+        return Err(Error::Unauthorized);
+    }
+
+    let vested = vested_at(&schedule, env.ledger().timestamp())?;
+    let unvested = math::sub(schedule.total_amount, vested)?;
+
+    // Freeze the grant at its vested amount before moving the remainder
+    schedule.total_amount = vested;
+    schedule.revoked = true;
+    schedules.set(schedule_index, schedule);
+    save_schedules(env, asset_id, &holder, &schedules);
+
+    if unvested > 0 {
+        tokenization::move_tokens(env, asset_id, holder.clone(), caller, unvested)?;
+    }
+
+    // Emit event: (asset_id, holder, schedule_index, unvested_returned)
+    env.events().publish(
+        ("vesting", "schedule_revoked"),
+        (asset_id, holder, schedule_index, unvested),
+    );
+
+    Ok(unvested)
+}
+
+/// Shrink a holder's newest unvested grants until they fit within `balance`,
+/// e.g. after a forced transfer. A running grant restarts now with what is
+/// left of it, keeping its cliff date and end date.
+pub fn clamp_unvested(
+    env: &Env,
+    asset_id: u64,
+    holder: &Address,
+    balance: i128,
+) -> Result<(), Error> {
+    let unvested = get_unvested_amount(env, asset_id, holder.clone())?;
+    if unvested <= balance {
+        return Ok(());
+    }
+    let mut excess = math::sub(unvested, balance)?;
+
+    let now = env.ledger().timestamp();
+    let mut schedules = load_schedules(env, asset_id, holder)?;
+    let mut index = schedules.len();
+    while excess > 0 && index > 0 {
+        index -= 1;
+        let mut schedule = schedules.get_unchecked(index);
+        let remaining = math::sub(schedule.total_amount, vested_at(&schedule, now)?)?;
+        if remaining <= 0 {
+            continue;
+        }
+
+        let cut = excess.min(remaining);
+        if now > schedule.start {
+            let cliff_end = schedule.start.saturating_add(schedule.cliff);
+            let end = schedule.start.saturating_add(schedule.duration);
+            schedule.start = now;
+            schedule.cliff = cliff_end.saturating_sub(now);
+            schedule.duration = end.saturating_sub(now);
+        }
+        schedule.total_amount = math::sub(remaining, cut)?;
+        schedules.set(index, schedule);
+        excess = math::sub(excess, cut)?;
+    }
+    save_schedules(env, asset_id, holder, &schedules);
+    Ok(())
+}
+
+pub fn get_vesting_schedules(
+    env: &Env,
+    asset_id: u64,
+    holder: Address,
+) -> Result<Vec<VestingSchedule>, Error> {
+    load_schedules(env, asset_id, &holder)
+}
+
+/// Total vested amount across a holder's schedules
+pub fn get_vested_amount(env: &Env, asset_id: u64, holder: Address) -> Result<i128, Error> {
+    let now = env.ledger().timestamp();
+    let mut vested: i128 = 0;
+    for schedule in load_schedules(env, asset_id, &holder)?.iter() {
+        vested = math::add(vested, vested_at(&schedule, now)?)?;
+    }
+    Ok(vested)
+}
+
+/// Total unvested amount across a holder's schedules
+pub fn get_unvested_amount(env: &Env, asset_id: u64, holder: Address) -> Result<i128, Error> {
+    let now = env.ledger().timestamp();
+    let mut unvested: i128 = 0;
+    for schedule in load_schedules(env, asset_id, &holder)?.iter() {
+        let remaining = math::sub(schedule.total_amount, vested_at(&schedule, now)?)?;
+        unvested = math::add(unvested, remaining)?;
+    }
+    Ok(unvested)
+}
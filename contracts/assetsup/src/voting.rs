@@ -1,8 +1,41 @@
 use crate::error::Error;
-use crate::types::{OwnershipRecord, TokenDataKey, TokenizedAsset};
-use soroban_sdk::{Address, Env, Vec};
+use crate::math::{self, Rounding};
+use crate::tokenization;
+use crate::types::{TokenDataKey, TokenizedAsset, VoteSnapshot};
+use soroban_sdk::{Address, Env, TryFromVal, Val, Vec};
+
+/// Tally for a proposal in the current denomination. Tallies stored as a
+/// bare `i128` predate corporate actions and read as epoch 0.
+fn load_tally(env: &Env, asset_id: u64, proposal_id: u64) -> Result<i128, Error> {
+    let stored: Option<Val> = env
+        .storage()
+        .persistent()
+        .get(&TokenDataKey::VoteTally(asset_id, proposal_id));
+    let snapshot = match stored {
+        Some(value) => Some(match i128::try_from_val(env, &value) {
+            Ok(tally) => VoteSnapshot {
+                tally,
+                scale_epoch: 0,
+            },
+            Err(_) => {
+                VoteSnapshot::try_from_val(env, &value).map_err(|_| Error::InvalidProposal)?
+            }
+        }),
+        None => None,
+    };
+    match snapshot {
+        Some(snapshot) => tokenization::scale_amount(
+            &tokenization::get_corporate_actions(env, asset_id),
+            snapshot.scale_epoch,
+            snapshot.tally,
+        ),
+        None => Ok(0),
+    }
+}
 
-/// Cast a vote on a proposal
+/// Cast a vote on a proposal. Votes on a mint proposal are weighted by the
+/// voter's balance at the proposal's snapshot, and the tokenizer and contract
+/// escrow cannot vote on them; other proposals use the current balance.
 pub fn cast_vote(env: &Env, asset_id: u64, proposal_id: u64, voter: Address) -> Result<(), Error> {
     let store = env.storage().persistent();
 
@@ -10,12 +43,28 @@ pub fn cast_vote(env: &Env, asset_id: u64, proposal_id: u64, voter: Address) ->
     let key = TokenDataKey::TokenizedAsset(asset_id);
     let tokenized_asset: TokenizedAsset = store.get(&key).ok_or(Error::AssetNotTokenized)?;
 
-    // Get voter's balance
-    let holder_key = TokenDataKey::TokenHolder(asset_id, voter.clone());
-    let ownership: OwnershipRecord = store.get(&holder_key).ok_or(Error::HolderNotFound)?;
+    // Get voter's weight
+    let weight = match tokenization::load_mint_approval(env, asset_id, proposal_id)? {
+        Some(approval) => {
+            if voter == tokenized_asset.tokenizer || voter == env.current_contract_address() {
+                return Err(Error::Unauthorized);
+            }
+            let weight =
+                tokenization::balance_at_snapshot(env, asset_id, &voter, approval.snapshot_id)?;
+            if weight <= 0 {
+                return Err(Error::HolderNotFound);
+            }
+            weight
+        }
+        None => {
+            tokenization::load_ownership(env, asset_id, &voter)?
+                .ok_or(Error::HolderNotFound)?
+                .balance
+        }
+    };
 
     // Check if voter has sufficient voting power
-    if ownership.balance < tokenized_asset.min_voting_threshold {
+    if weight < tokenized_asset.min_voting_threshold {
         return Err(Error::InsufficientVotingPower);
     }
 
@@ -30,15 +79,18 @@ pub fn cast_vote(env: &Env, asset_id: u64, proposal_id: u64, voter: Address) ->
 
     // Update vote tally
     let tally_key = TokenDataKey::VoteTally(asset_id, proposal_id);
-    let current_tally: i128 = store.get::<_, i128>(&tally_key).unwrap_or(0);
+    let current_tally = load_tally(env, asset_id, proposal_id)?;
 
-    let new_tally = current_tally + ownership.balance;
-    store.set(&tally_key, &new_tally);
+    let snapshot = VoteSnapshot {
+        tally: math::add(current_tally, weight)?,
+        scale_epoch: tokenization::current_scale_epoch(env, asset_id),
+    };
+    store.set(&tally_key, &snapshot);
 
     // Emit event: (asset_id, proposal_id, voter, weight)
     env.events().publish(
         ("voting", "vote_cast"),
-        (asset_id, proposal_id, voter, ownership.balance),
+        (asset_id, proposal_id, voter, weight),
     );
 
     Ok(())
@@ -52,9 +104,7 @@ pub fn get_vote_tally(env: &Env, asset_id: u64, proposal_id: u64) -> Result<i128
     let key = TokenDataKey::TokenizedAsset(asset_id);
     let _: TokenizedAsset = store.get(&key).ok_or(Error::AssetNotTokenized)?;
 
-    let tally_key = TokenDataKey::VoteTally(asset_id, proposal_id);
-
-    Ok(store.get::<_, i128>(&tally_key).unwrap_or(0))
+    load_tally(env, asset_id, proposal_id)
 }
 
 /// Check if an address has voted on a proposal
@@ -75,7 +125,8 @@ pub fn has_voted(
     Ok(store.has(&vote_key))
 }
 
-/// Check if a proposal passed (vote tally > 50% of total supply)
+/// Check if a proposal passed (vote tally above the detokenize threshold
+/// percentage of total supply, or of the eligible supply for a mint proposal)
 pub fn proposal_passed(env: &Env, asset_id: u64, proposal_id: u64) -> Result<bool, Error> {
     let store = env.storage().persistent();
 
@@ -84,12 +135,19 @@ pub fn proposal_passed(env: &Env, asset_id: u64, proposal_id: u64) -> Result<boo
     let tokenized_asset: TokenizedAsset = store.get(&key).ok_or(Error::AssetNotTokenized)?;
 
     // Get vote tally
-    let tally_key = TokenDataKey::VoteTally(asset_id, proposal_id);
-    let tally: i128 = store.get::<_, i128>(&tally_key).unwrap_or(0);
+    let tally = load_tally(env, asset_id, proposal_id)?;
 
     // Calculate required threshold
-    let threshold =
-        (tokenized_asset.total_supply * tokenized_asset.detokenize_threshold as i128) / 100;
+    let supply = match tokenization::load_mint_approval(env, asset_id, proposal_id)? {
+        Some(approval) => approval.eligible_supply,
+        None => tokenized_asset.total_supply,
+    };
+    let threshold = math::mul_div(
+        supply,
+        tokenized_asset.detokenize_threshold as i128,
+        100,
+        Rounding::Down,
+    )?;
 
     Ok(tally > threshold)
 }